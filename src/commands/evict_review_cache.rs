@@ -0,0 +1,17 @@
+use tracing::info;
+
+use crate::{prelude::*, services::ServiceContext};
+
+// Cached misses are always evicted, stale matches only when their title is given
+pub fn evict_review_cache(ctx: &ServiceContext, titles: &[String]) -> Result<()> {
+    let movies = ctx.movies.evict(titles)?;
+    let tv_shows = ctx.tv_shows.evict(titles)?;
+    let books = ctx.books.evict(titles)?;
+    let albums = ctx.music.evict(titles)?;
+
+    info!(
+        "Evicted {movies} movies, {tv_shows} tv shows, {books} books and {albums} albums from the review cache"
+    );
+
+    Ok(())
+}
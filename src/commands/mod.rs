@@ -1,2 +1,3 @@
+pub mod evict_review_cache;
 pub mod render_site;
 // pub mod create_content;
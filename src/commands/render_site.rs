@@ -7,8 +7,11 @@ use crate::build_data::BUILD_DATE;
 use crate::domain::models::data::Data;
 
 use crate::prelude::*;
+use crate::renderer::formatters::expand_shortcodes::expand_shortcodes;
+use crate::renderer::formatters::format_markdown::syntax_highlighting_css;
+use crate::renderer::formatters::markdown_cache::{load_markdown_cache, save_markdown_cache};
 use crate::renderer::render_pages;
-use crate::services::file_service::{FileService, ReadableFile};
+use crate::services::file_service::{FileService, ReadableFile, WritableFile};
 use crate::services::page_renderer::PageRenderer;
 
 use tracing::{info, instrument};
@@ -42,6 +45,9 @@ fn copy_assets() -> Result<()> {
         Path::new(&format!("./output/assets/css/{}", css_file_name)),
     )?;
 
+    FileService::output(format!("assets/css/syntax-{}.css", BUILD_DATE).into())
+        .write_text(&syntax_highlighting_css()?)?;
+
     Ok(())
 }
 
@@ -63,8 +69,12 @@ fn read_disallowed_routes_from_robot_file() -> Result<Vec<String>> {
     Ok(disallowed_routes)
 }
 
+pub struct RenderOptions {
+    pub cache_markdown: bool,
+}
+
 #[instrument(skip_all)]
-pub fn render_site(data: Data) -> Result<()> {
+pub fn render_site(data: Data, options: &RenderOptions) -> Result<()> {
     info!("Rendering site");
 
     let start = Utc::now();
@@ -72,9 +82,19 @@ pub fn render_site(data: Data) -> Result<()> {
     prepare_folders()?;
     copy_assets()?;
 
-    let renderer = PageRenderer::new();
+    if options.cache_markdown {
+        load_markdown_cache()?;
+    }
+
+    let data = expand_shortcodes(data)?;
+
+    let renderer = PageRenderer::new(data.site_links.clone());
     render_pages(&data, &renderer)?;
 
+    if options.cache_markdown {
+        save_markdown_cache()?;
+    }
+
     let disallowed_routes = read_disallowed_routes_from_robot_file()?;
 
     let page_count = renderer.build_sitemap(&disallowed_routes)?;
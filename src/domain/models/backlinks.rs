@@ -0,0 +1,40 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::slug::Slug;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backlink {
+    pub slug: Slug,
+    pub title: String,
+    pub date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Backlinks {
+    backlinks: HashMap<Slug, Vec<Backlink>>,
+}
+
+impl Backlinks {
+    pub fn add(&mut self, target: Slug, backlink: Backlink) {
+        let backlinks = self.backlinks.entry(target).or_default();
+
+        if backlinks
+            .iter()
+            .any(|existing| existing.slug == backlink.slug)
+        {
+            return;
+        }
+
+        backlinks.push(backlink);
+        backlinks.sort_by_key(|backlink| Reverse(backlink.date));
+    }
+
+    pub fn for_slug(&self, slug: &Slug) -> &[Backlink] {
+        self.backlinks
+            .get(slug)
+            .map(|backlinks| backlinks.as_slice())
+            .unwrap_or_default()
+    }
+}
@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::{image::Image, media::Media, page::Page, slug::Slug, tag::Tag};
+use crate::utils::read_time::{count_words, read_time_minutes};
+
+use super::{
+    blog_series::BlogPostSeriesPart, image::Image, mastodon_post::MastodonAnnouncement,
+    media::Media, page::Page, post_status::PostStatus, site_config::SITE_CONFIG, slug::Slug,
+    tag::Tag,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlogPost {
@@ -12,7 +18,14 @@ pub struct BlogPost {
     pub tags: Vec<Tag>,
     pub hero_image: Option<Image>,
     pub content: String,
+    pub word_count: usize,
+    // Front matter can force the table of contents on or off, otherwise it depends on length
+    pub table_of_contents: Option<bool>,
     pub media: Vec<Media>,
+    pub mastodon_announcement: Option<MastodonAnnouncement>,
+    #[serde(default)]
+    pub status: PostStatus,
+    pub series: Option<BlogPostSeriesPart>,
 }
 
 impl BlogPost {
@@ -31,16 +44,53 @@ impl BlogPost {
             description,
             tags,
             hero_image: None,
+            word_count: count_words(&content),
             content,
+            table_of_contents: None,
             media: vec![],
+            mastodon_announcement: None,
+            status: PostStatus::default(),
+            series: None,
         }
     }
 
+    pub fn with_series(mut self, series: BlogPostSeriesPart) -> Self {
+        self.series = Some(series);
+        self
+    }
+
+    pub fn is_hidden_from_blog_list(&self) -> bool {
+        self.series
+            .as_ref()
+            .is_some_and(|series| SITE_CONFIG.blog_series(&series.id).hide_from_blog_list)
+    }
+
+    pub fn with_status(mut self, status: PostStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_content(mut self, content: String) -> Self {
+        self.word_count = count_words(&content);
+        self.content = content;
+        self
+    }
+
     pub fn with_hero_image(mut self, hero_image: Image) -> Self {
         self.hero_image = Some(hero_image);
         self
     }
 
+    pub fn with_table_of_contents(mut self, table_of_contents: bool) -> Self {
+        self.table_of_contents = Some(table_of_contents);
+        self
+    }
+
+    pub fn show_table_of_contents(&self, min_words: usize) -> bool {
+        self.table_of_contents
+            .unwrap_or(self.word_count >= min_words)
+    }
+
     pub fn with_media(mut self, media: Vec<Media>) -> Self {
         self.media = media;
         self
@@ -51,6 +101,15 @@ impl BlogPost {
         self
     }
 
+    pub fn with_mastodon_announcement(mut self, announcement: MastodonAnnouncement) -> Self {
+        self.mastodon_announcement = Some(announcement);
+        self
+    }
+
+    pub fn read_time(&self) -> String {
+        format!("{} min read", read_time_minutes(self.word_count))
+    }
+
     pub fn permalink(&self) -> String {
         self.slug.permalink_string()
     }
@@ -62,7 +121,10 @@ impl BlogPost {
             Some(self.description.clone()),
         )
         .with_date(self.date)
-        .with_tags(self.tags.clone());
+        .with_read_time(&self.read_time())
+        .with_word_count(self.word_count)
+        .with_tags(self.tags.clone())
+        .with_status(self.status);
 
         if let Some(image) = &self.hero_image {
             page = page.with_image(image.clone().into());
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::domain::models::{blog_post::BlogPost, site_config::BlogSeriesConfig, slug::Slug};
+
+// Where a post sits in a series, from its front matter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlogPostSeriesPart {
+    pub id: String,
+    pub part: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlogSeries<'p> {
+    pub config: BlogSeriesConfig,
+    pub posts: Vec<&'p BlogPost>,
+}
+
+impl<'p> BlogSeries<'p> {
+    // Posts are ordered by part, falling back to date for any that share a part number
+    pub fn from_posts(
+        posts: impl Iterator<Item = &'p BlogPost>,
+        configs: &[BlogSeriesConfig],
+    ) -> Vec<Self> {
+        let mut posts_by_id: HashMap<&str, Vec<&'p BlogPost>> = HashMap::new();
+
+        for post in posts {
+            if let Some(series) = &post.series {
+                posts_by_id.entry(&series.id).or_default().push(post);
+            }
+        }
+
+        let mut series = posts_by_id
+            .into_iter()
+            .map(|(id, mut posts)| {
+                posts.sort_by_key(|post| (post.series.as_ref().map(|s| s.part), post.date));
+
+                let series = Self {
+                    config: BlogSeriesConfig::find(configs, id),
+                    posts,
+                };
+
+                for pair in series.posts.windows(2) {
+                    if series.part(pair[0]) == series.part(pair[1]) {
+                        warn!(
+                            "Series {} has more than one part {}: {} and {}",
+                            id,
+                            series.part(pair[0]),
+                            pair[0].slug,
+                            pair[1].slug
+                        );
+                    }
+                }
+
+                series
+            })
+            .collect::<Vec<Self>>();
+
+        series.sort_by(|a, b| a.config.id.cmp(&b.config.id));
+
+        series
+    }
+
+    pub fn slug(&self) -> Slug {
+        Slug::new(&format!("/blog/series/{}", self.config.id))
+    }
+
+    // As numbered in the post's front matter
+    pub fn part(&self, post: &BlogPost) -> u32 {
+        post.series
+            .as_ref()
+            .map(|series| series.part)
+            .unwrap_or_default()
+    }
+
+    pub fn position(&self, slug: &Slug) -> Option<usize> {
+        self.posts.iter().position(|post| &post.slug == slug)
+    }
+
+    pub fn previous(&self, index: usize) -> Option<&'p BlogPost> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.posts.get(i).copied())
+    }
+
+    pub fn next(&self, index: usize) -> Option<&'p BlogPost> {
+        self.posts.get(index + 1).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(slug: &str, date: &str, part: u32) -> BlogPost {
+        BlogPost::new(
+            Slug::new(slug),
+            date.parse().unwrap(),
+            slug.to_string(),
+            String::new(),
+            vec![],
+            String::new(),
+        )
+        .with_series(BlogPostSeriesPart {
+            id: "hackathons".to_string(),
+            part,
+        })
+    }
+
+    #[test]
+    fn it_should_order_series_by_part_and_link_neighbours() {
+        let posts = [
+            post("/blog/accelos", "2023-11-19T23:30:00Z", 2),
+            post("/blog/hack-pompey", "2023-03-25T22:30:00Z", 1),
+        ];
+
+        let configs = [BlogSeriesConfig {
+            id: "hackathons".to_string(),
+            title: "Hackathons".to_string(),
+            description: None,
+            hide_from_blog_list: false,
+        }];
+
+        let series = BlogSeries::from_posts(posts.iter(), &configs);
+
+        assert_eq!(series.len(), 1);
+
+        let series = &series[0];
+
+        assert_eq!(series.config.title, "Hackathons");
+        assert_eq!(series.part(series.posts[1]), 2);
+        assert_eq!(series.slug(), Slug::new("/blog/series/hackathons"));
+
+        let index = series.position(&Slug::new("/blog/hack-pompey")).unwrap();
+
+        assert_eq!(index, 0);
+        assert!(series.previous(index).is_none());
+        assert_eq!(
+            series.next(index).map(|post| &post.slug),
+            Some(&Slug::new("/blog/accelos"))
+        );
+    }
+}
@@ -5,13 +5,17 @@ use crate::domain::models::{image::Image, slug::Slug};
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize, Eq, Hash)]
 #[serde(tag = "type")]
 pub enum BookID {
+    // Cover id
     OpenLibrary { id: u32 },
+    // Books without a cover on OpenLibrary use their work, e.g. 45804 for OL45804W
+    OpenLibraryWork { id: u32 },
 }
 
 impl BookID {
     pub fn as_string(&self) -> String {
         match self {
             Self::OpenLibrary { id } => id.to_string(),
+            Self::OpenLibraryWork { id } => format!("OL{}W", id),
         }
     }
 }
@@ -21,6 +25,19 @@ pub struct Book {
     pub title: String,
     pub cover: Image,
     pub id: BookID,
+    // e.g. /works/OL45804W
+    #[serde(default)]
+    pub work_key: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub page_count: Option<u32>,
+    #[serde(default)]
+    pub first_publish_year: Option<u16>,
+    #[serde(default)]
+    pub subjects: Vec<String>,
+    #[serde(default)]
+    pub metadata_version: u32,
 }
 
 impl Book {
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::book::Book;
+
+#[derive(Debug, Clone)]
+pub struct ShelvedBook {
+    pub book: Book,
+    pub author: String,
+    pub date_added: DateTime<Utc>,
+}
+
+// Books from reading exports that aren't finished, newest first
+#[derive(Debug, Clone, Default)]
+pub struct BookShelves {
+    pub currently_reading: Vec<ShelvedBook>,
+    pub want_to_read: Vec<ShelvedBook>,
+}
+
+impl BookShelves {
+    pub fn is_empty(&self) -> bool {
+        self.currently_reading.is_empty() && self.want_to_read.is_empty()
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+
+use crate::domain::models::{
+    site_config::{HeaderLink, PageLink},
+    slug::Slug,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentPageLayout {
+    #[default]
+    Narrow,
+    Wide,
+}
+
+impl ContentPageLayout {
+    pub fn main_class(&self) -> &'static str {
+        match self {
+            ContentPageLayout::Narrow => "content-page",
+            ContentPageLayout::Wide => "content-page-wide",
+        }
+    }
+}
+
+// Footer is the name of the page_links group to add the page to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ContentPageNav {
+    #[serde(default)]
+    pub header: bool,
+    pub footer: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentPage {
+    pub slug: Slug,
+    pub title: String,
+    pub description: Option<String>,
+    pub nav: ContentPageNav,
+    pub layout: ContentPageLayout,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentPages {
+    pages: Vec<ContentPage>,
+}
+
+impl ContentPages {
+    pub fn from_pages(pages: Vec<ContentPage>) -> Self {
+        Self { pages }
+    }
+
+    pub fn all(&self) -> &[ContentPage] {
+        &self.pages
+    }
+
+    pub fn header_links(&self) -> Vec<HeaderLink> {
+        self.pages
+            .iter()
+            .filter(|page| page.nav.header)
+            .map(|page| HeaderLink {
+                name: page.title.clone(),
+                url: page.slug.relative_string(),
+            })
+            .collect()
+    }
+
+    pub fn footer_links(&self) -> Vec<(String, PageLink)> {
+        self.pages
+            .iter()
+            .filter_map(|page| {
+                let group = page.nav.footer.clone()?;
+
+                Some((
+                    group,
+                    PageLink {
+                        name: page.title.clone(),
+                        url: page.slug.relative_string(),
+                        rel: None,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
@@ -1,6 +1,12 @@
 use crate::domain::models::albums::Albums;
+use crate::domain::models::backlinks::Backlinks;
+use crate::domain::models::book_shelves::BookShelves;
+use crate::domain::models::content_page::ContentPages;
 use crate::domain::models::credits::Credits;
+use crate::domain::models::listening::Listening;
 use crate::domain::models::projects::Projects;
+use crate::domain::models::shortcode::Shortcodes;
+use crate::domain::models::site_config::SiteLinks;
 use crate::domain::models::timeline_event::TimelineEvents;
 
 use super::about_text::AboutText;
@@ -22,5 +28,11 @@ pub struct Data {
     pub albums: Albums,
     pub projects: Projects,
     pub timeline_events: TimelineEvents,
+    pub backlinks: Backlinks,
     pub credits: Credits,
+    pub content_pages: ContentPages,
+    pub book_shelves: BookShelves,
+    pub listening: Listening,
+    pub shortcodes: Shortcodes,
+    pub site_links: SiteLinks,
 }
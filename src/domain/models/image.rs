@@ -6,7 +6,7 @@ use crate::services::cdn_service::CdnFile;
 
 use super::media::{MediaDimensions, MediaOrientation};
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct SizedImage {
     pub file: CdnFile,
     pub dimensions: MediaDimensions,
@@ -24,7 +24,7 @@ pub enum ImageLinkOnClick {
     InternalSlug,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct Image {
     pub original: SizedImage,
     pub large: SizedImage,
@@ -38,4 +38,31 @@ impl Image {
     pub fn orientation(&self) -> MediaOrientation {
         self.original.dimensions.orientation()
     }
+
+    pub fn has_description(&self) -> bool {
+        !self.description.trim().is_empty()
+    }
+}
+
+#[cfg(test)]
+impl Image {
+    // As if already processed, e.g. /2024/05/knight.jpg with knight-large.jpg at half the size
+    // and knight-small.jpg at a tenth
+    pub fn test_fixture(path: &str, width: u32, height: u32) -> Self {
+        let (stem, extension) = path.rsplit_once('.').unwrap_or((path, "jpg"));
+
+        let sized = |suffix: &str, scale: u32| SizedImage {
+            file: CdnFile::from_path(&format!("{}{}.{}", stem, suffix, extension)),
+            dimensions: MediaDimensions::new(width / scale, height / scale),
+        };
+
+        Self {
+            original: sized("", 1),
+            large: sized("-large", 2),
+            small: sized("-small", 10),
+            description: String::new(),
+            link_on_click: None,
+            date: None,
+        }
+    }
 }
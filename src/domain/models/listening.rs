@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenCount {
+    pub name: String,
+    // Empty for artists
+    pub artist: String,
+    pub listens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListeningMonth {
+    // First day of the month
+    pub month: NaiveDate,
+    pub listens: usize,
+    // Most listened first
+    pub top_artists: Vec<ListenCount>,
+    pub top_albums: Vec<ListenCount>,
+}
+
+// Scrobble history grouped by month, newest first
+#[derive(Debug, Clone, Default)]
+pub struct Listening {
+    pub months: Vec<ListeningMonth>,
+}
+
+impl Listening {
+    pub fn is_empty(&self) -> bool {
+        self.months.is_empty()
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -8,6 +8,91 @@ use crate::domain::models::media::Media;
 
 use super::{page::Page, slug::Slug, tag::Tag};
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MastodonReplyAccount {
+    pub display_name: String,
+    pub acct: String,
+    pub url: Url,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MastodonReply {
+    pub id: String,
+    pub in_reply_to_id: Option<String>,
+    pub url: Url,
+    pub created_at: DateTime<Utc>,
+    pub content: String,
+    pub account: MastodonReplyAccount,
+}
+
+pub struct MastodonReplyThread<'l> {
+    pub reply: &'l MastodonReply,
+    pub children: Vec<MastodonReplyThread<'l>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct MastodonPostEngagement {
+    pub reblogs_count: u32,
+    pub favourites_count: u32,
+    pub replies_count: u32,
+    pub replies: Vec<MastodonReply>,
+}
+
+impl MastodonPostEngagement {
+    pub fn new(reblogs_count: u32, favourites_count: u32, replies_count: u32) -> Self {
+        Self {
+            reblogs_count,
+            favourites_count,
+            replies_count,
+            replies: vec![],
+        }
+    }
+
+    pub fn with_replies(mut self, replies: Vec<MastodonReply>) -> Self {
+        self.replies = replies;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reblogs_count == 0 && self.favourites_count == 0 && self.replies.is_empty()
+    }
+
+    // Replies come back from the API as a flat list, so we rebuild the tree from in_reply_to_id
+    pub fn reply_threads(&self, root_id: &str) -> Vec<MastodonReplyThread<'_>> {
+        self.replies_to(root_id)
+    }
+
+    fn replies_to(&self, parent_id: &str) -> Vec<MastodonReplyThread<'_>> {
+        let mut children = self
+            .replies
+            .iter()
+            .filter(|reply| reply.in_reply_to_id.as_deref() == Some(parent_id))
+            .map(|reply| MastodonReplyThread {
+                reply,
+                children: self.replies_to(&reply.id),
+            })
+            .collect::<Vec<MastodonReplyThread>>();
+
+        children.sort_by_key(|thread| thread.reply.created_at);
+
+        children
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MastodonAnnouncement {
+    pub id: String,
+    pub original_uri: Url,
+    pub engagement: MastodonPostEngagement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MastodonTombstone {
+    pub id: String,
+    pub original_uri: Url,
+    pub deleted_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MastodonPostNonSpoiler {
     id: String,
@@ -15,8 +100,12 @@ pub struct MastodonPostNonSpoiler {
     created_at: DateTime<Utc>,
     content: String,
     media: Vec<Media>,
+    #[serde(default)]
+    attachment_ids: Vec<String>,
     tags: Vec<Tag>,
     updated_at: DateTime<Utc>,
+    #[serde(default)]
+    engagement: MastodonPostEngagement,
 }
 
 impl MastodonPostNonSpoiler {
@@ -34,12 +123,15 @@ impl MastodonPostNonSpoiler {
             created_at,
             content,
             media: vec![],
+            attachment_ids: vec![],
             tags,
             updated_at,
+            engagement: MastodonPostEngagement::default(),
         }
     }
 
-    pub fn add_media(&mut self, media: Media) {
+    pub fn add_attachment(&mut self, attachment_id: String, media: Media) {
+        self.attachment_ids.push(attachment_id);
         self.media.push(media);
     }
 }
@@ -51,10 +143,14 @@ pub struct MastodonPostSpoiler {
     pub created_at: DateTime<Utc>,
     pub content: String,
     pub media: Vec<Media>,
+    #[serde(default)]
+    pub attachment_ids: Vec<String>,
     pub media_previews: Vec<Media>,
     pub spoiler_text: String,
     pub tags: Vec<Tag>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub engagement: MastodonPostEngagement,
 }
 
 impl MastodonPostSpoiler {
@@ -73,14 +169,17 @@ impl MastodonPostSpoiler {
             created_at,
             content,
             media: vec![],
+            attachment_ids: vec![],
             media_previews: vec![],
             spoiler_text,
             tags,
             updated_at,
+            engagement: MastodonPostEngagement::default(),
         }
     }
 
-    pub fn add_media(&mut self, media: Media) {
+    pub fn add_attachment(&mut self, attachment_id: String, media: Media) {
+        self.attachment_ids.push(attachment_id);
         self.media.push(media);
     }
 }
@@ -139,10 +238,54 @@ impl MastodonPost {
         Slug::new(&format!("micros/{}", self.id()))
     }
 
-    pub fn add_media(&mut self, media: Media) {
+    pub fn add_attachment(&mut self, attachment_id: String, media: Media) {
+        match self {
+            MastodonPost::NonSpoiler(post) => post.add_attachment(attachment_id, media),
+            MastodonPost::Spoiler(post) => post.add_attachment(attachment_id, media),
+        }
+    }
+
+    // Posts archived before attachment ids were recorded won't have any
+    pub fn attachment_ids(&self) -> &Vec<String> {
+        match self {
+            MastodonPost::NonSpoiler(post) => &post.attachment_ids,
+            MastodonPost::Spoiler(post) => &post.attachment_ids,
+        }
+    }
+
+    pub fn attachments(&self) -> impl Iterator<Item = (&String, &Media)> {
+        self.attachment_ids().iter().zip(self.media().iter())
+    }
+
+    fn media_mut(&mut self) -> &mut Vec<Media> {
         match self {
-            MastodonPost::NonSpoiler(post) => post.add_media(media),
-            MastodonPost::Spoiler(post) => post.add_media(media),
+            MastodonPost::NonSpoiler(post) => &mut post.media,
+            MastodonPost::Spoiler(post) => &mut post.media,
+        }
+    }
+
+    pub fn set_attachment_description(&mut self, attachment_id: &str, description: &str) {
+        let index = self
+            .attachment_ids()
+            .iter()
+            .position(|id| id == attachment_id);
+
+        if let Some(Media::Image(image)) = index.and_then(|index| self.media_mut().get_mut(index)) {
+            image.description = description.to_string();
+        }
+    }
+
+    pub fn engagement(&self) -> &MastodonPostEngagement {
+        match self {
+            MastodonPost::NonSpoiler(post) => &post.engagement,
+            MastodonPost::Spoiler(post) => &post.engagement,
+        }
+    }
+
+    pub fn set_engagement(&mut self, engagement: MastodonPostEngagement) {
+        match self {
+            MastodonPost::NonSpoiler(post) => post.engagement = engagement,
+            MastodonPost::Spoiler(post) => post.engagement = engagement,
         }
     }
 
@@ -199,13 +342,120 @@ impl MastodonPost {
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct MastodonPosts {
     mastodon_posts: HashMap<String, MastodonPost>,
+    #[serde(default)]
+    announcements: HashMap<Slug, MastodonAnnouncement>,
+    #[serde(default)]
+    tombstones: HashMap<String, MastodonTombstone>,
+    #[serde(default)]
+    imported_ids: HashSet<String>,
 }
 
 impl MastodonPosts {
     pub fn add(&mut self, post: MastodonPost) {
+        self.tombstones.remove(post.id());
+        self.imported_ids.remove(post.id());
         self.mastodon_posts.insert(post.id().to_string(), post);
     }
 
+    // Posts from an account export may be older than the API lets us page back to
+    pub fn add_imported(&mut self, post: MastodonPost) {
+        self.imported_ids.insert(post.id().to_string());
+        self.mastodon_posts.insert(post.id().to_string(), post);
+    }
+
+    pub fn is_imported(&self, id: &str) -> bool {
+        self.imported_ids.contains(id)
+    }
+
+    pub fn add_announcement(&mut self, slug: Slug, announcement: MastodonAnnouncement) {
+        self.tombstones.remove(&announcement.id);
+        self.announcements.insert(slug, announcement);
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<&MastodonPost> {
+        self.mastodon_posts.get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.mastodon_posts.contains_key(id) || self.contains_announcement(id)
+    }
+
+    pub fn contains_announcement(&self, id: &str) -> bool {
+        self.announcements
+            .values()
+            .any(|announcement| announcement.id == id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.mastodon_posts.keys().map(|id| id.as_str()).chain(
+            self.announcements
+                .values()
+                .map(|announcement| announcement.id.as_str()),
+        )
+    }
+
+    // Deleted statuses are kept as tombstones so we know not to bring them back
+    pub fn tombstone(&mut self, id: &str, deleted_at: DateTime<Utc>) {
+        self.imported_ids.remove(id);
+
+        let original_uri = match self.mastodon_posts.remove(id) {
+            Some(post) => Some(post.original_uri().clone()),
+            None => {
+                let slug = self
+                    .announcements
+                    .iter()
+                    .find(|(_, announcement)| announcement.id == id)
+                    .map(|(slug, _)| slug.clone());
+
+                slug.and_then(|slug| self.announcements.remove(&slug))
+                    .map(|announcement| announcement.original_uri)
+            }
+        };
+
+        if let Some(original_uri) = original_uri {
+            self.tombstones.insert(
+                id.to_string(),
+                MastodonTombstone {
+                    id: id.to_string(),
+                    original_uri,
+                    deleted_at,
+                },
+            );
+        }
+    }
+
+    pub fn apply_alt_text_overrides(&mut self, overrides: &HashMap<String, String>) {
+        for post in self.mastodon_posts.values_mut() {
+            let ids = post.attachment_ids().clone();
+
+            for id in ids {
+                if let Some(description) = overrides.get(&id) {
+                    post.set_attachment_description(&id, description);
+                }
+            }
+        }
+    }
+
+    pub fn is_tombstoned(&self, id: &str) -> bool {
+        self.tombstones.contains_key(id)
+    }
+
+    pub fn announcement_for(&self, slug: &Slug) -> Option<&MastodonAnnouncement> {
+        self.announcements.get(slug)
+    }
+
+    // Replies are only refetched when a status is, so keep whatever we already had on failure
+    pub fn find_replies_by_id(&self, id: &str) -> Option<&Vec<MastodonReply>> {
+        if let Some(post) = self.mastodon_posts.get(id) {
+            return Some(&post.engagement().replies);
+        }
+
+        self.announcements
+            .values()
+            .find(|announcement| announcement.id == id)
+            .map(|announcement| &announcement.engagement.replies)
+    }
+
     pub fn posts(&self) -> Vec<&MastodonPost> {
         let mut posts = self.mastodon_posts.values().collect::<Vec<&MastodonPost>>();
 
@@ -222,3 +472,79 @@ impl MastodonPosts {
         self.posts().len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::image::Image;
+
+    fn reply(id: &str, in_reply_to_id: &str, minute: u32) -> MastodonReply {
+        MastodonReply {
+            id: id.to_string(),
+            in_reply_to_id: Some(in_reply_to_id.to_string()),
+            url: format!("https://example.com/@someone/{}", id)
+                .parse()
+                .unwrap(),
+            created_at: format!("2024-01-01T00:{:02}:00Z", minute).parse().unwrap(),
+            content: "<p>Hello</p>".to_string(),
+            account: MastodonReplyAccount {
+                display_name: "Someone".to_string(),
+                acct: "someone@example.com".to_string(),
+                url: "https://example.com/@someone".parse().unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn it_should_apply_alt_text_overrides_by_attachment_id() {
+        let image = Image::test_fixture("2024/08/16/walk.jpg", 4032, 3024);
+
+        let mut post = MastodonPost::NonSpoiler(MastodonPostNonSpoiler::new(
+            "113000000000000003".to_string(),
+            "https://social.lol/users/geekyaubergine/statuses/113000000000000003"
+                .parse()
+                .unwrap(),
+            "2024-08-16T12:00:45Z".parse().unwrap(),
+            "<p>Morning walk</p>".to_string(),
+            vec![],
+            "2024-08-16T12:00:45Z".parse().unwrap(),
+        ));
+        post.add_attachment("113000000000000103".to_string(), image.into());
+
+        let mut posts = MastodonPosts::default();
+        posts.add(post);
+        posts.apply_alt_text_overrides(&HashMap::from([(
+            "113000000000000103".to_string(),
+            "A foggy path through some trees".to_string(),
+        )]));
+
+        let post = posts.find_by_id("113000000000000003").unwrap();
+
+        match &post.media()[0] {
+            Media::Image(image) => {
+                assert!(image.has_description());
+                assert_eq!(image.description, "A foggy path through some trees");
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_thread_replies_by_parent() {
+        let engagement = MastodonPostEngagement::new(1, 2, 4).with_replies(vec![
+            reply("4", "2", 4),
+            reply("3", "root", 3),
+            reply("2", "root", 2),
+            reply("5", "4", 5),
+        ]);
+
+        let threads = engagement.reply_threads("root");
+
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].reply.id, "2");
+        assert_eq!(threads[1].reply.id, "3");
+        assert_eq!(threads[0].children.len(), 1);
+        assert_eq!(threads[0].children[0].reply.id, "4");
+        assert_eq!(threads[0].children[0].children[0].reply.id, "5");
+        assert!(threads[1].children.is_empty());
+    }
+}
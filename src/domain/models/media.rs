@@ -6,7 +6,7 @@ use crate::domain::models::image::Image;
 // Had a few images where they're just a few pixels off square, so we want to catch them too or they look weird
 const SQUARE_MEDIA_MARGIN_OF_ERROR: f32 = 0.1;
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct MediaDimensions {
     pub width: u32,
     pub height: u32,
@@ -69,7 +69,7 @@ impl MediaOrientation {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Media {
     Image(Image),
 }
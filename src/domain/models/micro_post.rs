@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::media::Media;
+use super::post_status::PostStatus;
 use super::slug::Slug;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +15,8 @@ pub struct MicroPost {
     pub description: Option<String>,
     pub media: Vec<Media>,
     pub tags: Vec<Tag>,
+    #[serde(default)]
+    pub status: PostStatus,
 }
 
 impl MicroPost {
@@ -32,9 +35,15 @@ impl MicroPost {
             description,
             media,
             tags,
+            status: PostStatus::default(),
         }
     }
 
+    pub fn with_status(mut self, status: PostStatus) -> Self {
+        self.status = status;
+        self
+    }
+
     pub fn permalink(&self) -> String {
         self.slug.permalink_string()
     }
@@ -54,7 +63,8 @@ impl MicroPost {
     pub fn page(&self) -> Page {
         let mut page = Page::new(self.slug.clone(), None, self.description.clone())
             .with_date(self.date)
-            .with_tags(self.tags.clone());
+            .with_tags(self.tags.clone())
+            .with_status(self.status);
 
         if let Some(first) = self.media.first() {
             match first {
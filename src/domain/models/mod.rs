@@ -1,27 +1,36 @@
 pub mod about_text;
 pub mod albums;
+pub mod backlinks;
 pub mod blog_post;
+pub mod blog_series;
 pub mod book;
+pub mod book_shelves;
 pub mod cache_path;
+pub mod content_page;
 pub mod credits;
 pub mod data;
 pub mod faq;
 pub mod games;
 pub mod image;
 pub mod lego;
+pub mod listening;
 pub mod mastodon_post;
 pub mod media;
 pub mod micro_post;
 pub mod movie;
+pub mod music_album;
 pub mod network_response;
 pub mod now_text;
 pub mod page;
+pub mod post_status;
 pub mod projects;
 pub mod referral;
 pub mod review;
+pub mod shortcode;
 pub mod silly_names;
 pub mod site_config;
 pub mod slug;
+pub mod structured_data;
 pub mod tag;
 pub mod timeline_event;
 pub mod tv_show;
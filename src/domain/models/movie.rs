@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::domain::models::{image::Image, slug::Slug};
+use crate::domain::models::{image::Image, review::reviewed_work::name_slug, slug::Slug};
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(tag = "type")]
@@ -15,6 +15,8 @@ impl MovieId {
     }
 }
 
+// Metadata fields default so movies cached before they existed still load, the movie service
+// then refetches them using metadata_version
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Movie {
     pub title: String,
@@ -22,16 +24,26 @@ pub struct Movie {
     pub poster: Image,
     pub id: MovieId,
     pub link: Url,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    // Minutes
+    #[serde(default)]
+    pub runtime: Option<u16>,
+    #[serde(default)]
+    pub directors: Vec<String>,
+    #[serde(default)]
+    pub cast: Vec<String>,
+    #[serde(default)]
+    pub metadata_version: u32,
 }
 
 impl Movie {
     pub fn slug(&self) -> Slug {
-        let title = self
-            .title
-            .replace(['&', ':'], "")
-            .replace(' ', "-")
-            .to_lowercase();
-        Slug::new(&format!("/interests/movies/{}-{}", title, self.year))
+        Slug::new(&format!(
+            "/interests/movies/{}-{}",
+            name_slug(&self.title),
+            self.year
+        ))
     }
 }
 
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::domain::models::{image::Image, review::reviewed_work::name_slug, slug::Slug};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(tag = "type")]
+pub enum MusicAlbumId {
+    // Release group id
+    MusicBrainz { id: String },
+}
+
+impl MusicAlbumId {
+    pub fn musicbrainz(id: &str) -> Self {
+        Self::MusicBrainz { id: id.to_string() }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MusicAlbum {
+    pub title: String,
+    pub artist: String,
+    pub cover: Image,
+    pub id: MusicAlbumId,
+    pub link: Url,
+    pub year: Option<u16>,
+    pub genres: Vec<String>,
+}
+
+impl MusicAlbum {
+    pub fn slug(&self) -> Slug {
+        Slug::new(&format!(
+            "/interests/music/{}-{}",
+            name_slug(&self.artist),
+            name_slug(&self.title)
+        ))
+    }
+}
@@ -4,7 +4,8 @@ use serde::Deserialize;
 use crate::{build_data::BUILD_DATE, utils::paginator::PaginatorPage};
 
 use super::{
-    site_config::{HeaderLink, PageImage, PageLinkGroup, SITE_CONFIG, SocialNetworkLink},
+    post_status::PostStatus,
+    site_config::{HeaderLink, PageImage, SITE_CONFIG, SocialNetworkLink},
     slug::Slug,
     tag::Tag,
 };
@@ -96,12 +97,12 @@ pub struct Page {
     pub image: PageImage,
     pub language: String,
     pub build_date: String,
-    pub header_links: Vec<HeaderLink>,
-    pub page_links: Vec<PageLinkGroup>,
     pub date: Option<DateTime<Utc>>,
     pub read_time: Option<String>,
+    pub word_count: Option<usize>,
     pub tags: Vec<Tag>,
     pub page_pagination: Option<PagePaginationData>,
+    pub no_index: bool,
 }
 
 impl Page {
@@ -119,12 +120,12 @@ impl Page {
             image: SITE_CONFIG.image.clone(),
             language: SITE_CONFIG.language.to_string(),
             build_date: BUILD_DATE.to_string(),
-            header_links: SITE_CONFIG.header_links.clone(),
-            page_links: SITE_CONFIG.page_links.clone(),
             date: None,
             read_time: None,
+            word_count: None,
             tags: vec![],
             page_pagination: None,
+            no_index: false,
         }
     }
 
@@ -163,11 +164,21 @@ impl Page {
         self
     }
 
+    pub fn with_word_count(mut self, word_count: usize) -> Self {
+        self.word_count = Some(word_count);
+        self
+    }
+
     pub fn with_tags(mut self, tags: Vec<Tag>) -> Self {
         self.tags = tags;
         self
     }
 
+    pub fn with_status(mut self, status: PostStatus) -> Self {
+        self.no_index = !status.is_listed();
+        self
+    }
+
     pub fn with_pagination(mut self, pagination: PagePaginationData) -> Self {
         self.page_pagination = Some(pagination);
         self
@@ -224,10 +235,6 @@ impl Page {
         &self.image.alt
     }
 
-    pub fn header_links(&self) -> &[HeaderLink] {
-        &self.header_links
-    }
-
     pub fn date(&self) -> Option<&DateTime<Utc>> {
         self.date.as_ref()
     }
@@ -236,6 +243,10 @@ impl Page {
         self.read_time.as_deref()
     }
 
+    pub fn word_count(&self) -> Option<usize> {
+        self.word_count
+    }
+
     pub fn tags(&self) -> &[Tag] {
         &self.tags
     }
@@ -243,12 +254,4 @@ impl Page {
     pub fn page_pagination(&self) -> Option<&PagePaginationData> {
         self.page_pagination.as_ref()
     }
-
-    pub fn first_half_of_header_links(&self) -> &[HeaderLink] {
-        &self.header_links[0..self.header_links.len() / 2]
-    }
-
-    pub fn second_half_of_header_links(&self) -> &[HeaderLink] {
-        &self.header_links[self.header_links.len() / 2..]
-    }
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    // Rendered at its url, but left out of lists, feeds, tags and the sitemap
+    Unlisted,
+    #[default]
+    Published,
+}
+
+impl PostStatus {
+    pub fn is_listed(&self) -> bool {
+        !matches!(self, PostStatus::Unlisted)
+    }
+}
@@ -0,0 +1,109 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::error::MusicError;
+use crate::prelude::*;
+
+// Titles can link to the album, e.g. to give its MusicBrainz id
+static REGEX_LINKED_TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(.*)\]\(.*\)$").unwrap());
+static REGEX_SCORE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d)/5(?: - (.*))?$").unwrap());
+static REGEX_MUSICBRAINZ: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"musicbrainz\.org/release-group/([0-9a-f-]{36})|\bmusicbrainz:\s*([0-9a-f-]{36})")
+        .unwrap()
+});
+
+// Posted like books, e.g.
+// Blackstar by David Bowie
+//
+// 5/5 - A goodbye in every sense
+#[derive(Debug, Clone)]
+pub struct AlbumReview {
+    pub title: String,
+    pub artist: String,
+    pub score: u8,
+    pub review: String,
+    // Release group, so every edition of an album is the same work
+    pub musicbrainz: Option<String>,
+}
+
+impl AlbumReview {
+    pub fn from_content(content: &str) -> Result<AlbumReview> {
+        let content = content
+            .replace("\\n", "\n")
+            .replace("</p><p>", "\n")
+            .replace("<p>", "")
+            .replace("</p>", "");
+
+        let lines = content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+
+        let (Some(first_line), Some(second_line)) = (lines.first(), lines.get(1)) else {
+            return Err(MusicError::unable_to_parse_album_review(
+                content.to_string(),
+            ));
+        };
+
+        // Split on the last "by", album titles are more likely to contain it than artists
+        let (Some((title, artist)), Some(captures)) = (
+            first_line.rsplit_once(" by "),
+            REGEX_SCORE.captures(second_line),
+        ) else {
+            return Err(MusicError::unable_to_parse_album_review(
+                content.to_string(),
+            ));
+        };
+
+        let title = REGEX_LINKED_TITLE
+            .captures(title.trim())
+            .and_then(|captures| captures.get(1))
+            .map_or(title.trim(), |title| title.as_str());
+
+        Ok(AlbumReview {
+            title: title.to_string(),
+            artist: artist.trim().to_string(),
+            score: captures[1].parse().unwrap(),
+            review: captures
+                .get(2)
+                .map(|review| review.as_str().trim().to_string())
+                .unwrap_or_default(),
+            musicbrainz: REGEX_MUSICBRAINZ
+                .captures(&content)
+                .and_then(|captures| captures.get(1).or_else(|| captures.get(2)))
+                .map(|id| id.as_str().to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_album_reviews_from_micro_and_mastodon_posts() {
+        let review = AlbumReview::from_content(
+            "[Blackstar](https://musicbrainz.org/release-group/917b9e4a-0d6d-4a7c-9ab6-2e4f4b4e1c1a) by David Bowie\n\n5/5 - A goodbye in every sense",
+        )
+        .unwrap();
+
+        assert_eq!(review.title, "Blackstar");
+        assert_eq!(review.artist, "David Bowie");
+        assert_eq!(review.score, 5);
+        assert_eq!(review.review, "A goodbye in every sense");
+        assert_eq!(
+            review.musicbrainz,
+            Some("917b9e4a-0d6d-4a7c-9ab6-2e4f4b4e1c1a".to_string())
+        );
+
+        let review =
+            AlbumReview::from_content("<p>Stand By Me by Ben E. King</p><p>4/5</p>").unwrap();
+
+        assert_eq!(review.title, "Stand By Me");
+        assert_eq!(review.artist, "Ben E. King");
+        assert_eq!(review.review, "");
+
+        assert!(AlbumReview::from_content("Listening to Blackstar by David Bowie").is_err());
+    }
+}
@@ -1,18 +1,23 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::domain::models::review::{review_ids::ReviewIds, reviewed_work::ReviewedWorkKind};
 use crate::error::BookError;
 use crate::prelude::*;
 
 static REGEX_LEGACY_STYLE_AUTHOR_TITLE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\[(.*)\].*by (.*) 📚").unwrap());
 
+// Titles can link to the book, e.g. to give its OpenLibrary id
+static REGEX_LINKED_TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(.*)\]\(.*\)$").unwrap());
+
 #[derive(Debug, Clone)]
 pub struct BookReview {
     pub title: String,
     pub author: String,
     pub score: u8,
     pub review: String,
+    pub ids: ReviewIds,
 }
 
 impl BookReview {
@@ -62,7 +67,8 @@ fn parse_legacy_post(content: &str) -> Result<BookReview> {
                 title: title.as_str().to_string(),
                 author: author.as_str().to_string(),
                 score,
-                review: review.trim().to_string(),
+                review: ReviewIds::strip_markers(review),
+                ids: ReviewIds::from_content(&content, ReviewedWorkKind::Book),
             });
         }
     }
@@ -109,11 +115,17 @@ fn parse_new_style(content: &str) -> Result<BookReview> {
             second_line_split.get(1),
         ) {
             let score = score.trim().parse().unwrap();
+            let title = REGEX_LINKED_TITLE
+                .captures(title.trim())
+                .and_then(|captures| captures.get(1))
+                .map_or(title.trim(), |title| title.as_str());
+
             return Ok(BookReview {
-                title: title.trim().to_string(),
+                title: title.to_string(),
                 author: author.trim().to_string(),
                 score,
-                review: review.trim().to_string(),
+                review: ReviewIds::strip_markers(review),
+                ids: ReviewIds::from_content(&content, ReviewedWorkKind::Book),
             });
         }
     }
@@ -183,4 +195,24 @@ mod tests {
             "Excellent book, the descriptions of both the [Blood Angles](https://warhammer40k.fandom.com/wiki/Blood_Angels) and [Tryranids](https://warhammer40k.fandom.com/wiki/Tyranids) are amazing. I would highly recommend it to anyone who's a fan of either faction (or space marines and Warhammer in general)."
         );
     }
+
+    #[test]
+    fn test_parse_markdown_modern_post_with_explicit_ids() {
+        let content = r#"[Horus Rising](https://openlibrary.org/works/OL5735363W) by Dan Abnett
+
+        5/5 - Where it all starts
+
+        ISBN 978-1-84416-294-9"#;
+
+        let review = parse_markdown_into_book_review(content).unwrap();
+
+        assert_eq!(review.title, "Horus Rising");
+        assert_eq!(review.author, "Dan Abnett");
+        assert_eq!(review.ids.open_library, Some("OL5735363W".to_string()));
+        assert_eq!(review.ids.isbn, Some("9781844162949".to_string()));
+        assert_eq!(
+            review.ids.open_library_cache_key(),
+            Some("openlibrary:OL5735363W".to_string())
+        );
+    }
 }
@@ -0,0 +1,12 @@
+use crate::domain::models::{micro_post::MicroPost, review::movie_review::MovieReview};
+
+// Reviews from exports of other sites. The post is what's shown on the site, exports that
+// already have structured data carry their review rather than having it parsed from the post
+#[derive(Debug, Clone)]
+pub enum ImportedReview {
+    Post(MicroPost),
+    Movie {
+        review: MovieReview,
+        post: MicroPost,
+    },
+}
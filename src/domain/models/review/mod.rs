@@ -1,8 +1,14 @@
 use crate::error::Error;
 
+pub mod album_review;
 pub mod book_review;
+pub mod imported_review;
 pub mod movie_review;
+pub mod review_ids;
+pub mod review_overrides;
 pub mod review_source;
+pub mod review_stats;
+pub mod reviewed_work;
 pub mod tv_show_review;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
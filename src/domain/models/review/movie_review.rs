@@ -1,6 +1,7 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::domain::models::review::{review_ids::ReviewIds, reviewed_work::ReviewedWorkKind};
 use crate::error::MovieError;
 use crate::prelude::*;
 
@@ -16,6 +17,8 @@ pub struct MovieReview {
     pub year: u16,
     pub score: u8,
     pub review: String,
+    pub rewatch: bool,
+    pub ids: ReviewIds,
 }
 
 impl MovieReview {
@@ -68,8 +71,15 @@ fn parse_markdown_into_movie_review(content: &str) -> Result<MovieReview> {
             .to_string(),
     };
 
-    let year = MOVIE_YEAR_REGEX
-        .captures(first_line)
+    let year_captures = MOVIE_YEAR_REGEX.captures(first_line);
+
+    // Rewatches are posted with a suffix on the year, "(2021 - Rewatch)"
+    let rewatch = year_captures
+        .as_ref()
+        .and_then(|captures| captures.get(2))
+        .is_some_and(|suffix| suffix.as_str().to_lowercase().contains("rewatch"));
+
+    let year = year_captures
         .ok_or(MovieError::unable_to_parse_and_find_movie_year(
             content.to_string(),
         ))?
@@ -87,11 +97,12 @@ fn parse_markdown_into_movie_review(content: &str) -> Result<MovieReview> {
             .ok_or(MovieError::unable_to_parse_and_find_movie_review(
                 content.to_string(),
             ))?
-            .as_str()
-            .to_string(),
-        None => "".to_string(),
+            .as_str(),
+        None => "",
     };
 
+    let review = ReviewIds::strip_markers(review);
+
     let score = SCORE_AND_MAX_REGEX
         .captures(second_line)
         .ok_or(MovieError::unable_to_parse_and_find_movie_score(
@@ -110,6 +121,8 @@ fn parse_markdown_into_movie_review(content: &str) -> Result<MovieReview> {
         year,
         score,
         review,
+        rewatch,
+        ids: ReviewIds::from_content(&content, ReviewedWorkKind::Movie),
     })
 }
 
@@ -126,6 +139,11 @@ mod test {
             year: 2005,
             score: 3,
             review: "Nice easy watch, some good moments and laughs".to_string(),
+            rewatch: false,
+            ids: ReviewIds {
+                imdb: Some("tt0371606".to_string()),
+                ..Default::default()
+            },
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
@@ -144,6 +162,11 @@ mod test {
             year: 1985,
             score: 3,
             review: "".to_string(),
+            rewatch: false,
+            ids: ReviewIds {
+                imdb: Some("tt0089015".to_string()),
+                ..Default::default()
+            },
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
@@ -160,6 +183,11 @@ mod test {
             year: 1980,
             score: 5,
             review: "This film gets better every time I watch it.".to_string(),
+            rewatch: false,
+            ids: ReviewIds {
+                imdb: Some("tt0080455".to_string()),
+                ..Default::default()
+            },
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
@@ -176,6 +204,11 @@ mod test {
             year: 2022,
             score: 3,
             review: "I see why others enjoyed it, but a lot of it felt like gore for the sake of gore. The performances are great.".to_string(),
+            rewatch: false,
+            ids: ReviewIds {
+                imdb: Some("tt1016150".to_string()),
+                ..Default::default()
+            },
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
@@ -192,6 +225,8 @@ mod test {
             year: 2022,
             score: 2,
             review: "Interesting, but not for me".to_string(),
+            rewatch: false,
+            ids: ReviewIds::default(),
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
@@ -208,10 +243,34 @@ mod test {
             year: 1983,
             score: 3,
             review: "".to_string(),
+            rewatch: false,
+            ids: ReviewIds::default(),
         };
 
         let review = parse_markdown_into_movie_review(&post).unwrap();
 
         assert_eq!(review.title, expected.title);
     }
+
+    #[test]
+    fn it_should_parse_an_explicit_tmdb_id_from_the_title_link() {
+        let post = "[Dune](https://www.themoviedb.org/movie/841) (1984)\n\n2/5 - Not the good one";
+
+        let review = parse_markdown_into_movie_review(&post).unwrap();
+
+        assert_eq!(review.title, "Dune");
+        assert_eq!(review.year, 1984);
+        assert_eq!(review.ids.tmdb, Some(841));
+        assert_eq!(review.ids.tmdb_cache_key(), Some("tmdb:841".to_string()));
+    }
+
+    #[test]
+    fn it_should_parse_a_rewatch_from_the_year() {
+        let post = "[Dune](https://www.themoviedb.org/movie/438631) (2021 - Rewatch)\n\n4/5";
+
+        let review = parse_markdown_into_movie_review(&post).unwrap();
+
+        assert_eq!(review.year, 2021);
+        assert!(review.rewatch);
+    }
 }
@@ -0,0 +1,165 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::domain::models::review::reviewed_work::ReviewedWorkKind;
+
+// TMDB numbers movies and TV separately, so a link is only read for the matching kind
+static TMDB_MOVIE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"themoviedb\.org/movie/(\d+)|\btmdb:\s*(\d+)").unwrap());
+static TMDB_TV_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"themoviedb\.org/tv/(\d+)|\btmdb:\s*(\d+)").unwrap());
+static IMDB_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"imdb\.com/title/(tt\d+)|\bimdb:\s*(tt\d+)").unwrap());
+static ISBN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)openlibrary\.org/isbn/([\dX]+)|\bisbn(?:-1[03])?:?\s*([\d-]{9,16}[\dX])")
+        .unwrap()
+});
+static OPEN_LIBRARY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"openlibrary\.org/works/(OL\d+W)|\bopenlibrary:\s*(OL\d+W)").unwrap());
+// The written out ids, links are left alone as they're part of the text
+static ID_MARKER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\btmdb:\s*\d+|\bimdb:\s*tt\d+|(?i:\bisbn(?:-1[03])?:?\s*[\d-]{9,16}[\dX])|\bopenlibrary:\s*OL\d+W")
+        .unwrap()
+});
+
+fn find_id<'c>(regex: &Regex, content: &'c str) -> Option<&'c str> {
+    let captures = regex.captures(content)?;
+
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|id| id.as_str())
+}
+
+// Identifiers written anywhere in a review post, usually as the title link, e.g.
+// [Dune](https://www.themoviedb.org/movie/438631) or isbn: 978-0-575-08424-0
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewIds {
+    pub tmdb: Option<u32>,
+    pub imdb: Option<String>,
+    pub isbn: Option<String>,
+    pub open_library: Option<String>,
+}
+
+impl ReviewIds {
+    pub fn from_content(content: &str, kind: ReviewedWorkKind) -> Self {
+        let isbn = find_id(&ISBN_REGEX, content)
+            .map(|isbn| isbn.replace('-', "").to_uppercase())
+            .filter(|isbn| isbn.len() == 10 || isbn.len() == 13);
+
+        let tmdb_regex = match kind {
+            ReviewedWorkKind::Movie => Some(&TMDB_MOVIE_REGEX),
+            ReviewedWorkKind::TvShow => Some(&TMDB_TV_REGEX),
+            ReviewedWorkKind::Book | ReviewedWorkKind::Album => None,
+        };
+
+        Self {
+            tmdb: tmdb_regex
+                .and_then(|regex| find_id(regex, content))
+                .and_then(|id| id.parse().ok()),
+            imdb: find_id(&IMDB_REGEX, content).map(|id| id.to_string()),
+            isbn,
+            open_library: find_id(&OPEN_LIBRARY_REGEX, content).map(|id| id.to_string()),
+        }
+    }
+
+    // The ids are for looking the work up, not for reading
+    pub fn strip_markers(text: &str) -> String {
+        ID_MARKER_REGEX
+            .replace_all(text, "")
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+                    .trim_matches(',')
+                    .trim()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    // Lookups by id are cached under the id, so works sharing a title don't collide
+    pub fn tmdb_cache_key(&self) -> Option<String> {
+        match (&self.tmdb, &self.imdb) {
+            (Some(id), _) => Some(format!("tmdb:{}", id)),
+            (None, Some(id)) => Some(format!("imdb:{}", id)),
+            (None, None) => None,
+        }
+    }
+
+    pub fn open_library_cache_key(&self) -> Option<String> {
+        match (&self.open_library, &self.isbn) {
+            (Some(id), _) => Some(format!("openlibrary:{}", id)),
+            (None, Some(isbn)) => Some(format!("isbn:{}", isbn)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_find_ids_in_links_and_prefixed_text() {
+        let ids = ReviewIds::from_content(
+            "[Dune](https://www.themoviedb.org/movie/438631-dune) imdb: tt1160419\n\nISBN 978-0-575-08424-0, openlibrary:OL893415W",
+            ReviewedWorkKind::Movie,
+        );
+
+        assert_eq!(
+            ids,
+            ReviewIds {
+                tmdb: Some(438631),
+                imdb: Some("tt1160419".to_string()),
+                isbn: Some("9780575084240".to_string()),
+                open_library: Some("OL893415W".to_string()),
+            }
+        );
+        assert_eq!(ids.tmdb_cache_key(), Some("tmdb:438631".to_string()));
+
+        assert_eq!(
+            ReviewIds::from_content(
+                "The Menu (2022)\n\n2/5 - Interesting, but not for me",
+                ReviewedWorkKind::Movie
+            ),
+            ReviewIds::default()
+        );
+    }
+
+    #[test]
+    fn it_should_only_read_tmdb_links_for_the_same_kind_of_work() {
+        let content = "[The Office](https://www.themoviedb.org/tv/2996) (S1)";
+
+        assert_eq!(
+            ReviewIds::from_content(content, ReviewedWorkKind::TvShow).tmdb,
+            Some(2996)
+        );
+        assert_eq!(
+            ReviewIds::from_content(content, ReviewedWorkKind::Movie).tmdb,
+            None
+        );
+    }
+
+    #[test]
+    fn it_should_strip_written_ids_from_review_text() {
+        assert_eq!(
+            ReviewIds::strip_markers("The original, still the best tmdb:2996"),
+            "The original, still the best"
+        );
+        assert_eq!(
+            ReviewIds::strip_markers(
+                "Slow start ISBN 978-0-575-08424-0, openlibrary:OL893415W\nWorth it"
+            ),
+            "Slow start\nWorth it"
+        );
+        assert_eq!(
+            ReviewIds::strip_markers("See [TMDB](https://www.themoviedb.org/movie/841)"),
+            "See [TMDB](https://www.themoviedb.org/movie/841)"
+        );
+    }
+}
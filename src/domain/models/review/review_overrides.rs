@@ -0,0 +1,112 @@
+use serde::Deserialize;
+
+use crate::services::cdn_service::CdnFile;
+
+// Pins a reviewed title to a specific TMDB or OpenLibrary entry when the search picks the wrong
+// one, and/or replaces its cover with a local image. Covers are paths relative to content
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReviewOverrides {
+    #[serde(default)]
+    movies: Vec<MovieOverride>,
+    #[serde(default)]
+    tv_shows: Vec<TvShowOverride>,
+    #[serde(default)]
+    books: Vec<BookOverride>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MovieOverride {
+    pub title: String,
+    pub year: u16,
+    pub tmdb: Option<u32>,
+    pub cover: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TvShowOverride {
+    pub title: String,
+    pub tmdb: Option<u32>,
+    pub cover: Option<String>,
+}
+
+// Without an author the override applies to every book with the title
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookOverride {
+    pub title: String,
+    pub author: Option<String>,
+    // Work key, e.g. OL45804W
+    pub open_library: Option<String>,
+    pub cover: Option<String>,
+}
+
+fn same_title(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+// Kept apart from searched images so swapping back to the search result re-processes the cover
+pub fn override_cover_cdn_file(directory: &str, cover: &str) -> CdnFile {
+    CdnFile::from_path(&format!(
+        "{}/overrides/{}",
+        directory,
+        cover.trim_start_matches('/')
+    ))
+}
+
+impl ReviewOverrides {
+    pub fn movie(&self, title: &str, year: u16) -> Option<&MovieOverride> {
+        self.movies
+            .iter()
+            .find(|movie| movie.year == year && same_title(&movie.title, title))
+    }
+
+    pub fn tv_show(&self, title: &str) -> Option<&TvShowOverride> {
+        self.tv_shows
+            .iter()
+            .find(|tv_show| same_title(&tv_show.title, title))
+    }
+
+    pub fn book(&self, title: &str, author: &str) -> Option<&BookOverride> {
+        self.books.iter().find(|book| {
+            same_title(&book.title, title)
+                && book
+                    .author
+                    .as_ref()
+                    .is_none_or(|book_author| same_title(book_author, author))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_match_overrides_by_title_year_and_author() {
+        let overrides: ReviewOverrides = serde_yaml::from_str(
+            r#"
+movies:
+  - title: Dune
+    year: 2021
+    tmdb: 438631
+books:
+  - title: Horus Rising
+    author: Dan Abnett
+    open_library: OL5735363W
+  - title: Dune
+    cover: images/covers/dune.jpg
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(overrides.movie("dune", 2021).unwrap().tmdb, Some(438631));
+        assert!(overrides.movie("Dune", 1984).is_none());
+        assert!(overrides.tv_show("Dune").is_none());
+
+        assert!(overrides.book("Horus Rising", "Dan Abnett").is_some());
+        assert!(overrides.book("Horus Rising", "Someone Else").is_none());
+        assert_eq!(
+            overrides.book("Dune", "Frank Herbert").unwrap().cover,
+            Some("images/covers/dune.jpg".to_string())
+        );
+    }
+}
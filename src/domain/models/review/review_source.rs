@@ -2,8 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::models::{
-    mastodon_post::MastodonPost, media::Media, micro_post::MicroPost, page::Page, slug::Slug,
-    tag::Tag,
+    mastodon_post::MastodonPost, media::Media, micro_post::MicroPost, page::Page,
+    post_status::PostStatus, slug::Slug, tag::Tag,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +48,13 @@ impl ReviewSource {
         }
     }
 
+    pub fn status(&self) -> PostStatus {
+        match self {
+            Self::MicroPost(micro_post) => micro_post.status,
+            Self::MastodonPost(_) => PostStatus::Published,
+        }
+    }
+
     pub fn content(&self) -> &str {
         match self {
             Self::MicroPost(micro_post) => &micro_post.content,
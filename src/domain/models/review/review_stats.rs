@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::domain::models::{
+    review::reviewed_work::{ReviewedWorkKind, reviewed_works},
+    timeline_event::TimelineEvents,
+};
+
+const MONTH_COUNT: u32 = 24;
+const GENRE_COUNT: usize = 15;
+// Fewer reviews than this makes for a meaningless average
+const MIN_GENRE_REVIEWS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDistribution {
+    pub kind: ReviewedWorkKind,
+    // Index is the score, 0 to 5
+    pub counts: [usize; 6],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AverageScore {
+    pub label: String,
+    pub average: f32,
+    pub reviews: usize,
+}
+
+// Consecutive days with at least one review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReviewStreak {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl ReviewStreak {
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReviewStats {
+    pub total: usize,
+    pub score_distributions: Vec<ScoreDistribution>,
+    pub per_year: Vec<(i32, usize)>,
+    // First day of each month, ending with the latest review's month
+    pub per_month: Vec<(NaiveDate, usize)>,
+    pub by_decade: Vec<AverageScore>,
+    // Most reviewed first
+    pub by_genre: Vec<AverageScore>,
+    pub longest_streak: Option<ReviewStreak>,
+    pub longest_streaks: Vec<(ReviewedWorkKind, ReviewStreak)>,
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn longest_streak(dates: &BTreeSet<NaiveDate>) -> Option<ReviewStreak> {
+    let mut longest: Option<ReviewStreak> = None;
+    let mut current: Option<ReviewStreak> = None;
+
+    for date in dates.iter().copied() {
+        current = match current {
+            Some(streak) if streak.end.checked_add_days(Days::new(1)) == Some(date) => {
+                Some(ReviewStreak {
+                    end: date,
+                    ..streak
+                })
+            }
+            _ => Some(ReviewStreak {
+                start: date,
+                end: date,
+            }),
+        };
+
+        // Ties go to the earliest, the first time it happened
+        if longest.is_none_or(|longest| current.unwrap().days() > longest.days()) {
+            longest = current;
+        }
+    }
+
+    longest
+}
+
+fn months_until(counts: &BTreeMap<NaiveDate, usize>) -> Vec<(NaiveDate, usize)> {
+    let Some(last) = counts.keys().next_back().copied() else {
+        return vec![];
+    };
+
+    (0..MONTH_COUNT)
+        .rev()
+        .filter_map(|offset| last.checked_sub_months(Months::new(offset)))
+        .map(|month| (month, counts.get(&month).copied().unwrap_or(0)))
+        .collect()
+}
+
+fn average_scores(scores: HashMap<String, Vec<u8>>) -> Vec<AverageScore> {
+    scores
+        .into_iter()
+        .map(|(label, scores)| AverageScore {
+            label,
+            average: scores.iter().map(|score| *score as f32).sum::<f32>() / scores.len() as f32,
+            reviews: scores.len(),
+        })
+        .collect()
+}
+
+impl ReviewStats {
+    pub fn from_events(events: &TimelineEvents) -> Self {
+        let mut stats = ReviewStats::default();
+
+        let mut per_year: BTreeMap<i32, usize> = BTreeMap::new();
+        let mut per_month: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        let mut by_decade: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut by_genre: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut all_dates = BTreeSet::new();
+
+        for kind in ReviewedWorkKind::ALL {
+            let works = reviewed_works(kind, events);
+
+            if works.is_empty() {
+                continue;
+            }
+
+            let mut counts = [0; 6];
+            let mut dates = BTreeSet::new();
+
+            for work in &works {
+                for review in &work.reviews {
+                    let date = review.date().date_naive();
+
+                    stats.total += 1;
+                    counts[review.score.min(5) as usize] += 1;
+                    dates.insert(date);
+                    *per_year.entry(date.year()).or_default() += 1;
+                    *per_month.entry(month_start(date)).or_default() += 1;
+
+                    if let Some(decade) = work.decade() {
+                        by_decade
+                            .entry(format!("{}s", decade))
+                            .or_default()
+                            .push(review.score);
+                    }
+
+                    for genre in work.genres {
+                        by_genre
+                            .entry(genre.clone())
+                            .or_default()
+                            .push(review.score);
+                    }
+                }
+            }
+
+            stats
+                .score_distributions
+                .push(ScoreDistribution { kind, counts });
+
+            if let Some(streak) = longest_streak(&dates) {
+                stats.longest_streaks.push((kind, streak));
+            }
+
+            all_dates.extend(dates);
+        }
+
+        stats.per_year = per_year.into_iter().collect();
+        stats.per_month = months_until(&per_month);
+        stats.longest_streak = longest_streak(&all_dates);
+
+        stats.by_decade = average_scores(by_decade);
+        stats.by_decade.sort_by(|a, b| a.label.cmp(&b.label));
+
+        stats.by_genre = average_scores(by_genre)
+            .into_iter()
+            .filter(|genre| genre.reviews >= MIN_GENRE_REVIEWS)
+            .collect();
+        stats.by_genre.sort_by(|a, b| {
+            b.reviews
+                .cmp(&a.reviews)
+                .then_with(|| a.label.cmp(&b.label))
+        });
+        stats.by_genre.truncate(GENRE_COUNT);
+
+        stats
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn it_should_find_the_longest_streak_and_fill_empty_months() {
+        let dates = [
+            "2024-01-30",
+            "2024-01-31",
+            "2024-02-01",
+            "2024-03-05",
+            "2024-03-06",
+        ]
+        .into_iter()
+        .map(date)
+        .collect();
+
+        assert_eq!(
+            longest_streak(&dates),
+            Some(ReviewStreak {
+                start: date("2024-01-30"),
+                end: date("2024-02-01"),
+            })
+        );
+
+        let counts = BTreeMap::from([(date("2023-12-01"), 2), (date("2024-02-01"), 1)]);
+        let months = months_until(&counts);
+
+        assert_eq!(months.len(), MONTH_COUNT as usize);
+        assert_eq!(
+            months[months.len() - 3..],
+            [
+                (date("2023-12-01"), 2),
+                (date("2024-01-01"), 0),
+                (date("2024-02-01"), 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::domain::models::{
+    book::BookID,
+    image::Image,
+    movie::MovieId,
+    music_album::MusicAlbumId,
+    review::review_source::ReviewSource,
+    slug::Slug,
+    timeline_event::{TimelineEvent, TimelineEventReview, TimelineEvents},
+    tv_show::TvShowId,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WorkId {
+    Movie(MovieId),
+    TvShow(TvShowId),
+    Book(BookID),
+    Album(MusicAlbumId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReviewedWorkKind {
+    Movie,
+    TvShow,
+    Book,
+    Album,
+}
+
+impl ReviewedWorkKind {
+    pub const ALL: [ReviewedWorkKind; 4] = [
+        ReviewedWorkKind::Movie,
+        ReviewedWorkKind::TvShow,
+        ReviewedWorkKind::Book,
+        ReviewedWorkKind::Album,
+    ];
+
+    pub fn slug(&self) -> Slug {
+        match self {
+            ReviewedWorkKind::Movie => Slug::new("/interests/movies"),
+            ReviewedWorkKind::TvShow => Slug::new("/interests/tv"),
+            ReviewedWorkKind::Book => Slug::new("/interests/books"),
+            ReviewedWorkKind::Album => Slug::new("/interests/music"),
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ReviewedWorkKind::Movie => "Movies",
+            ReviewedWorkKind::TvShow => "TV",
+            ReviewedWorkKind::Book => "Books",
+            ReviewedWorkKind::Album => "Albums",
+        }
+    }
+
+    // Used in urls and titles, e.g. /interests/books/read/2024/
+    pub fn verb(&self) -> &'static str {
+        match self {
+            ReviewedWorkKind::Movie | ReviewedWorkKind::TvShow => "watched",
+            ReviewedWorkKind::Book => "read",
+            ReviewedWorkKind::Album => "listened",
+        }
+    }
+
+    // Only books are indexed by their creators, directors and show creators aren't
+    pub fn has_author_pages(&self) -> bool {
+        matches!(self, ReviewedWorkKind::Book)
+    }
+
+    pub fn genre_slug(&self, genre: &str) -> Slug {
+        self.slug().append(&format!("genre/{}", name_slug(genre)))
+    }
+
+    pub fn authors_slug(&self) -> Slug {
+        self.slug().append("authors")
+    }
+
+    pub fn author_slug(&self, author: &str) -> Slug {
+        self.authors_slug().append(&name_slug(author))
+    }
+
+    // Scrobbles are only imported for music
+    pub fn has_listening_page(&self) -> bool {
+        matches!(self, ReviewedWorkKind::Album)
+    }
+
+    pub fn listening_slug(&self) -> Slug {
+        self.slug().append("listening")
+    }
+}
+
+// e.g. Science Fiction & Fantasy becomes science-fiction-fantasy
+pub fn name_slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkReview<'d> {
+    pub score: u8,
+    // What the review covered, e.g. the seasons of a show
+    pub detail: Option<String>,
+    pub source: &'d ReviewSource,
+}
+
+impl<'d> WorkReview<'d> {
+    pub fn date(&self) -> &'d DateTime<Utc> {
+        self.source.date()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewedWork<'d> {
+    pub id: WorkId,
+    pub kind: ReviewedWorkKind,
+    pub slug: Slug,
+    pub title: &'d str,
+    pub image: &'d Image,
+    pub release_year: Option<u16>,
+    pub genres: &'d [String],
+    // Directors, show creators or authors
+    pub creators: &'d [String],
+    // Newest first, never empty
+    pub reviews: Vec<WorkReview<'d>>,
+}
+
+impl<'d> ReviewedWork<'d> {
+    pub fn latest_review(&self) -> &WorkReview<'d> {
+        &self.reviews[0]
+    }
+
+    pub fn score(&self) -> u8 {
+        self.latest_review().score
+    }
+
+    pub fn average_score(&self) -> f32 {
+        self.reviews
+            .iter()
+            .map(|review| review.score as f32)
+            .sum::<f32>()
+            / self.reviews.len() as f32
+    }
+
+    pub fn reviews_by_date(&self) -> impl Iterator<Item = &WorkReview<'d>> {
+        self.reviews.iter().rev()
+    }
+
+    pub fn reviewed_years(&self) -> Vec<i32> {
+        let mut years = self
+            .reviews
+            .iter()
+            .map(|review| review.date().year())
+            .collect::<Vec<i32>>();
+
+        years.dedup();
+
+        years
+    }
+
+    pub fn decade(&self) -> Option<u16> {
+        self.release_year.map(|year| year / 10 * 10)
+    }
+}
+
+fn work_from_event(event: &TimelineEvent) -> Option<ReviewedWork<'_>> {
+    let TimelineEvent::Review(review) = event else {
+        return None;
+    };
+
+    let work = match review {
+        TimelineEventReview::MovieReview {
+            review,
+            movie,
+            source,
+        } => ReviewedWork {
+            id: WorkId::Movie(movie.id),
+            kind: ReviewedWorkKind::Movie,
+            slug: movie.slug(),
+            title: &movie.title,
+            image: &movie.poster,
+            release_year: Some(movie.year),
+            genres: &movie.genres,
+            creators: &movie.directors,
+            reviews: vec![WorkReview {
+                score: review.score,
+                detail: None,
+                source,
+            }],
+        },
+        TimelineEventReview::TvShowReview {
+            review,
+            tv_show,
+            source,
+        } => ReviewedWork {
+            id: WorkId::TvShow(tv_show.id),
+            kind: ReviewedWorkKind::TvShow,
+            slug: tv_show.slug(),
+            title: &tv_show.title,
+            image: &tv_show.poster,
+            release_year: tv_show.year,
+            genres: &tv_show.genres,
+            creators: &tv_show.creators,
+            reviews: vec![WorkReview {
+                score: review.average_score_u8(),
+                detail: Some(review.season_text()),
+                source,
+            }],
+        },
+        TimelineEventReview::BookReview {
+            review,
+            book,
+            source,
+        } => ReviewedWork {
+            id: WorkId::Book(book.id),
+            kind: ReviewedWorkKind::Book,
+            slug: book.slug(),
+            title: &book.title,
+            image: &book.cover,
+            release_year: book.first_publish_year,
+            genres: &[],
+            creators: &book.authors,
+            reviews: vec![WorkReview {
+                score: review.score,
+                detail: None,
+                source,
+            }],
+        },
+        TimelineEventReview::AlbumReview {
+            review,
+            album,
+            source,
+        } => ReviewedWork {
+            id: WorkId::Album(album.id.clone()),
+            kind: ReviewedWorkKind::Album,
+            slug: album.slug(),
+            title: &album.title,
+            image: &album.cover,
+            release_year: album.year,
+            genres: &album.genres,
+            creators: std::slice::from_ref(&album.artist),
+            reviews: vec![WorkReview {
+                score: review.score,
+                detail: None,
+                source,
+            }],
+        },
+    };
+
+    Some(work)
+}
+
+// One entry per work, most recently reviewed first, with every review of it
+pub fn reviewed_works(kind: ReviewedWorkKind, events: &TimelineEvents) -> Vec<ReviewedWork<'_>> {
+    let mut works: Vec<ReviewedWork> = vec![];
+    let mut index_by_id: HashMap<WorkId, usize> = HashMap::new();
+
+    for work in events
+        .all_by_date()
+        .iter()
+        .filter_map(work_from_event)
+        .filter(|work| work.kind == kind)
+    {
+        match index_by_id.get(&work.id) {
+            Some(index) => works[*index].reviews.extend(work.reviews),
+            None => {
+                index_by_id.insert(work.id.clone(), works.len());
+                works.push(work);
+            }
+        }
+    }
+
+    works
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::domain::models::{
+        micro_post::MicroPost,
+        movie::Movie,
+        review::{movie_review::MovieReview, review_ids::ReviewIds},
+    };
+
+    // A movie reviewed in a micro post, shared with the reviewed works pages tests
+    pub fn movie_review_event(
+        title: &str,
+        year: u16,
+        id: u32,
+        date: &str,
+        score: u8,
+    ) -> TimelineEvent {
+        let date = date.parse::<DateTime<Utc>>().unwrap();
+        let slug = Slug::new(&format!("/micros/{}", date.timestamp()));
+
+        TimelineEvent::Review(TimelineEventReview::MovieReview {
+            review: MovieReview {
+                title: title.to_string(),
+                year,
+                score,
+                review: String::new(),
+                rewatch: false,
+                ids: ReviewIds::default(),
+            },
+            movie: Movie {
+                title: title.to_string(),
+                year,
+                poster: Image::test_fixture("/movies/poster.jpg", 400, 600),
+                id: MovieId::tmdb(id),
+                link: format!("https://www.themoviedb.org/movie/{}", id)
+                    .parse()
+                    .unwrap(),
+                genres: vec!["Science Fiction".to_string()],
+                runtime: None,
+                directors: vec![],
+                cast: vec![],
+                metadata_version: 0,
+            },
+            source: ReviewSource::MicroPost(MicroPost::new(
+                slug,
+                date,
+                String::new(),
+                None,
+                vec![],
+                vec![],
+            )),
+        })
+    }
+
+    pub fn movie_review_events() -> TimelineEvents {
+        TimelineEvents::from_events(vec![
+            movie_review_event("Dune", 2021, 438631, "2023-02-01T20:00:00Z", 3),
+            movie_review_event("Dune", 2021, 438631, "2024-01-05T20:00:00Z", 4),
+            movie_review_event("Dune", 2021, 438631, "2024-06-10T20:00:00Z", 5),
+            movie_review_event("Alien", 1979, 348, "2024-03-01T20:00:00Z", 4),
+        ])
+    }
+
+    #[test]
+    fn it_should_group_reviews_of_a_work_newest_first() {
+        let events = movie_review_events();
+        let works = reviewed_works(ReviewedWorkKind::Movie, &events);
+
+        assert_eq!(
+            works.iter().map(|work| work.title).collect::<Vec<&str>>(),
+            vec!["Dune", "Alien"]
+        );
+
+        let dune = &works[0];
+
+        assert_eq!(
+            dune.reviews
+                .iter()
+                .map(|review| review.score)
+                .collect::<Vec<u8>>(),
+            vec![5, 4, 3]
+        );
+        assert_eq!(dune.score(), 5);
+        assert_eq!(dune.average_score(), 4.0);
+        assert_eq!(dune.reviewed_years(), vec![2024, 2023]);
+        assert_eq!(dune.decade(), Some(2020));
+        assert_eq!(works[1].decade(), Some(1970));
+
+        assert!(reviewed_works(ReviewedWorkKind::Book, &events).is_empty());
+    }
+
+    #[test]
+    fn it_should_make_genre_and_author_slugs() {
+        assert_eq!(
+            ReviewedWorkKind::Movie.genre_slug("Science Fiction & Fantasy"),
+            Slug::new("/interests/movies/genre/science-fiction-fantasy")
+        );
+        assert_eq!(
+            ReviewedWorkKind::Book.author_slug("Aaron Dembski-Bowden"),
+            Slug::new("/interests/books/authors/aaron-dembski-bowden")
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::{error::TvShowsError, prelude::*};
+use crate::{
+    domain::models::review::{review_ids::ReviewIds, reviewed_work::ReviewedWorkKind},
+    error::TvShowsError,
+    prelude::*,
+};
 
 static LINK_TITLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[(.*)\]").unwrap());
 static SEASON_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\((S.*)\)").unwrap());
@@ -17,6 +21,7 @@ pub struct TvShowReview {
     pub seasons: Vec<u8>,
     pub scores: Vec<u8>,
     pub review: String,
+    pub ids: ReviewIds,
 }
 
 impl TvShowReview {
@@ -111,11 +116,12 @@ fn parse_markdown_into_tv_show_review(content: &str) -> Result<TvShowReview> {
             .ok_or(TvShowsError::unable_to_parse_and_find_tv_show_review(
                 content.to_string(),
             ))?
-            .as_str()
-            .to_string(),
-        None => "".to_string(),
+            .as_str(),
+        None => "",
     };
 
+    let review = ReviewIds::strip_markers(review);
+
     let score = SCORE_AND_MAX_REGEX
         .captures(second_line)
         .ok_or(TvShowsError::unable_to_parse_and_find_tv_show_score(
@@ -134,6 +140,7 @@ fn parse_markdown_into_tv_show_review(content: &str) -> Result<TvShowReview> {
         seasons: seasons.clone(),
         scores: seasons.iter().map(|_| score).collect::<Vec<u8>>(),
         review: review.clone(),
+        ids: ReviewIds::from_content(&content, ReviewedWorkKind::TvShow),
     })
 }
 
@@ -150,6 +157,10 @@ mod tests {
             scores: vec![4, 4, 4],
             seasons: vec![3, 4, 5],
             review: "The show continues to improve. The last two seasons touch on much more serious subjects and the show really shines for it.".to_string(),
+            ids: ReviewIds {
+                imdb: Some("tt4326894".to_string()),
+                ..Default::default()
+            },
         };
 
         let result = parse_markdown_into_tv_show_review(content).unwrap();
@@ -166,10 +177,22 @@ mod tests {
             scores: vec![2],
             seasons: vec![7],
             review: "The worst so far. Let&#39;s see how bad S8 is.".to_string(),
+            ids: ReviewIds::default(),
         };
 
         let result = parse_markdown_into_tv_show_review(content).unwrap();
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn it_should_parse_an_explicit_tmdb_id_after_the_review() {
+        let content = "The Office (S1)\n\n4/5 - The original\n\ntmdb:2996";
+
+        let result = parse_markdown_into_tv_show_review(content).unwrap();
+
+        assert_eq!(result.title, "The Office");
+        assert_eq!(result.seasons, vec![1]);
+        assert_eq!(result.ids.tmdb, Some(2996));
+    }
 }
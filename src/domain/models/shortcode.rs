@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+use crate::{
+    domain::models::{
+        albums::{album::Album, album_photo::AlbumPhoto},
+        book::Book,
+        mastodon_post::MastodonPost,
+        movie::Movie,
+    },
+    prelude::*,
+    utils::code_blocks::map_lines_outside_code_blocks,
+};
+
+// e.g. {{< movie "Dune" 2021 >}}
+static SHORTCODE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\{\{<\s*(?P<name>[a-z_]+)(?P<arguments>(?:\s+(?:"[^"]*"|[^\s"]+))*?)\s*>\}\}"#)
+        .unwrap()
+});
+static ARGUMENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]*)"|(\S+)"#).unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcodeCall {
+    // As written in the content, used to look up what it resolved to
+    pub text: String,
+    pub name: String,
+    pub arguments: Vec<String>,
+}
+
+fn parse_arguments(arguments: &str) -> Vec<String> {
+    ARGUMENT_REGEX
+        .captures_iter(arguments)
+        .filter_map(|cap| cap.get(1).or(cap.get(2)))
+        .map(|argument| argument.as_str().to_string())
+        .collect()
+}
+
+// Replaces each shortcode outside of code blocks with what f returns for it
+pub fn map_shortcodes<F>(content: &str, mut f: F) -> Result<String>
+where
+    F: FnMut(&ShortcodeCall) -> Result<String>,
+{
+    map_lines_outside_code_blocks(content, |line| {
+        let mut mapped = String::new();
+        let mut last = 0;
+
+        for cap in SHORTCODE_REGEX.captures_iter(line) {
+            let whole = cap.get(0).unwrap();
+
+            let call = ShortcodeCall {
+                text: whole.as_str().to_string(),
+                name: cap["name"].to_string(),
+                arguments: parse_arguments(&cap["arguments"]),
+            };
+
+            mapped.push_str(&line[last..whole.start()]);
+            mapped.push_str(&f(&call)?);
+
+            last = whole.end();
+        }
+
+        mapped.push_str(&line[last..]);
+
+        Ok(mapped)
+    })
+}
+
+pub fn find_shortcodes(content: &str) -> Result<Vec<ShortcodeCall>> {
+    let mut calls = vec![];
+
+    map_shortcodes(content, |call| {
+        calls.push(call.clone());
+        Ok(call.text.clone())
+    })?;
+
+    Ok(calls)
+}
+
+// What a shortcode refers to, looked up while processing and rendered into the content later
+#[derive(Debug, Clone)]
+pub enum Shortcode {
+    Album(Album),
+    Photo(AlbumPhoto),
+    YouTube(String),
+    OwnToot(MastodonPost),
+    RemoteToot(Url),
+    Movie(Movie),
+    Book(Book),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Shortcodes {
+    by_text: HashMap<String, Shortcode>,
+}
+
+impl Shortcodes {
+    pub fn insert(&mut self, call: &ShortcodeCall, shortcode: Shortcode) {
+        self.by_text.insert(call.text.clone(), shortcode);
+    }
+
+    pub fn find(&self, call: &ShortcodeCall) -> Option<&Shortcode> {
+        self.by_text.get(&call.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_find_shortcodes_with_quoted_and_bare_arguments_outside_code_blocks() {
+        let content = "Watched {{< movie \"The Fall Guy\" 2024 >}} last night\n\n```md\n{{< youtube dQw4w9WgXcQ >}}\n```";
+
+        assert_eq!(
+            find_shortcodes(content).unwrap(),
+            vec![ShortcodeCall {
+                text: r#"{{< movie "The Fall Guy" 2024 >}}"#.to_string(),
+                name: "movie".to_string(),
+                arguments: vec!["The Fall Guy".to_string(), "2024".to_string()],
+            }]
+        );
+    }
+}
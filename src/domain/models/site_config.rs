@@ -82,6 +82,31 @@ impl From<&Image> for PageImage {
     }
 }
 
+// Series that aren't configured still work, titled by their id
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlogSeriesConfig {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub hide_from_blog_list: bool,
+}
+
+impl BlogSeriesConfig {
+    pub fn find(configs: &[Self], id: &str) -> Self {
+        configs
+            .iter()
+            .find(|series| series.id == id)
+            .cloned()
+            .unwrap_or_else(|| Self {
+                id: id.to_string(),
+                title: id.to_string(),
+                description: None,
+                hide_from_blog_list: false,
+            })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct PageConfig {
     pub url: String,
@@ -92,6 +117,15 @@ pub struct PageConfig {
     pub language: String,
     pub header_links: Vec<HeaderLink>,
     pub page_links: Vec<PageLinkGroup>,
+    pub table_of_contents_min_words: usize,
+    #[serde(default)]
+    pub blog_series: Vec<BlogSeriesConfig>,
+}
+
+impl PageConfig {
+    pub fn blog_series(&self, id: &str) -> BlogSeriesConfig {
+        BlogSeriesConfig::find(&self.blog_series, id)
+    }
 }
 
 pub static SITE_CONFIG: Lazy<PageConfig> = Lazy::new(|| {
@@ -99,3 +133,39 @@ pub static SITE_CONFIG: Lazy<PageConfig> = Lazy::new(|| {
 
     serde_json::from_str(&contents).unwrap()
 });
+
+// The configured links plus any content pages that put themselves in the navigation, which
+// aren't known until they're loaded
+#[derive(Debug, Clone, Default)]
+pub struct SiteLinks {
+    pub header_links: Vec<HeaderLink>,
+    pub page_links: Vec<PageLinkGroup>,
+}
+
+impl SiteLinks {
+    // Footer links go in the group with the same name, or a new group after the configured ones
+    pub fn new(header_links: Vec<HeaderLink>, footer_links: Vec<(String, PageLink)>) -> Self {
+        let mut links = Self {
+            header_links: SITE_CONFIG.header_links.clone(),
+            page_links: SITE_CONFIG.page_links.clone(),
+        };
+
+        links.header_links.extend(header_links);
+
+        for (group_name, link) in footer_links {
+            match links
+                .page_links
+                .iter_mut()
+                .find(|group| group.name == group_name)
+            {
+                Some(group) => group.links.push(link),
+                None => links.page_links.push(PageLinkGroup {
+                    name: group_name,
+                    links: vec![link],
+                }),
+            }
+        }
+
+        links
+    }
+}
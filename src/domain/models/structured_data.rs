@@ -0,0 +1,509 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::domain::models::{
+    albums::{album::Album, album_photo::AlbumPhoto},
+    blog_post::BlogPost,
+    image::Image,
+    movie::MovieId,
+    music_album::MusicAlbumId,
+    review::{review_ids::ReviewIds, review_source::ReviewSource},
+    site_config::SITE_CONFIG,
+    timeline_event::TimelineEventReview,
+    tv_show::TvShowId,
+};
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+const IMDB_LINK_URL: &str = "https://www.imdb.com/title/";
+const MAX_SCORE: u8 = 5;
+
+fn image_url(image: &Image) -> String {
+    image.large.file.as_cdn_url().to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Person {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    same_as: Vec<String>,
+}
+
+impl Person {
+    // The site is named after its author, and its rel="me" links are their other profiles
+    pub fn site_author() -> Self {
+        Self {
+            name: SITE_CONFIG.title.clone(),
+            url: Some(SITE_CONFIG.url.clone()),
+            same_as: SITE_CONFIG
+                .page_links
+                .iter()
+                .flat_map(|group| &group.links)
+                .filter(|link| link.rel.as_deref() == Some("me"))
+                .map(|link| link.url.clone())
+                .collect(),
+        }
+    }
+
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            url: None,
+            same_as: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct PropertyValue {
+    #[serde(rename = "propertyID")]
+    property_id: &'static str,
+    value: String,
+}
+
+impl PropertyValue {
+    fn new(property_id: &'static str, value: impl ToString) -> Self {
+        Self {
+            property_id,
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Rating {
+    rating_value: u8,
+    best_rating: u8,
+    worst_rating: u8,
+}
+
+impl Rating {
+    fn out_of_five(score: u8) -> Self {
+        Self {
+            rating_value: score,
+            best_rating: MAX_SCORE,
+            worst_rating: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename = "Movie", rename_all = "camelCase")]
+pub struct ReviewedMovie {
+    name: String,
+    date_created: String,
+    image: String,
+    director: Vec<Person>,
+    same_as: Vec<String>,
+    identifier: Vec<PropertyValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename = "TVSeries", rename_all = "camelCase")]
+pub struct ReviewedTvSeries {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    image: String,
+    creator: Vec<Person>,
+    same_as: Vec<String>,
+    identifier: Vec<PropertyValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename = "Book", rename_all = "camelCase")]
+pub struct ReviewedBook {
+    name: String,
+    author: Vec<Person>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    isbn: Option<String>,
+    image: String,
+    same_as: Vec<String>,
+    identifier: Vec<PropertyValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename = "MusicAlbum", rename_all = "camelCase")]
+pub struct ReviewedMusicAlbum {
+    name: String,
+    by_artist: Person,
+    image: String,
+    same_as: Vec<String>,
+    identifier: Vec<PropertyValue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ReviewedItem {
+    Movie(ReviewedMovie),
+    TvSeries(ReviewedTvSeries),
+    Book(ReviewedBook),
+    MusicAlbum(ReviewedMusicAlbum),
+}
+
+// IMDb ids are only known when they're written in the review post
+fn imdb_link(ids: &ReviewIds) -> Option<String> {
+    ids.imdb
+        .as_ref()
+        .map(|imdb| format!("{}{}/", IMDB_LINK_URL, imdb))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Review {
+    item_reviewed: ReviewedItem,
+    review_rating: Rating,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    review_body: String,
+    author: Person,
+    date_published: DateTime<Utc>,
+    url: String,
+}
+
+impl Review {
+    fn new(
+        item_reviewed: ReviewedItem,
+        score: u8,
+        review_body: &str,
+        source: &ReviewSource,
+    ) -> Self {
+        Self {
+            item_reviewed,
+            review_rating: Rating::out_of_five(score),
+            review_body: review_body.to_string(),
+            author: Person::site_author(),
+            date_published: *source.date(),
+            url: source.slug().permalink_string(),
+        }
+    }
+}
+
+impl From<&TimelineEventReview> for Review {
+    fn from(event: &TimelineEventReview) -> Self {
+        match event {
+            TimelineEventReview::MovieReview {
+                review,
+                movie,
+                source,
+            } => {
+                let MovieId::Tmdb { id } = movie.id;
+
+                let item = ReviewedMovie {
+                    name: movie.title.clone(),
+                    date_created: movie.year.to_string(),
+                    image: image_url(&movie.poster),
+                    director: movie
+                        .directors
+                        .iter()
+                        .map(|name| Person::named(name))
+                        .collect(),
+                    same_as: std::iter::once(movie.link.to_string())
+                        .chain(imdb_link(&review.ids))
+                        .collect(),
+                    identifier: vec![PropertyValue::new("tmdb", id)],
+                };
+
+                Review::new(
+                    ReviewedItem::Movie(item),
+                    review.score,
+                    &review.review,
+                    source,
+                )
+            }
+            TimelineEventReview::TvShowReview {
+                review,
+                tv_show,
+                source,
+            } => {
+                let TvShowId::Tmdb { id } = tv_show.id;
+
+                let item = ReviewedTvSeries {
+                    name: tv_show.title.clone(),
+                    start_date: tv_show.year.map(|year| year.to_string()),
+                    image: image_url(&tv_show.poster),
+                    creator: tv_show
+                        .creators
+                        .iter()
+                        .map(|name| Person::named(name))
+                        .collect(),
+                    same_as: std::iter::once(tv_show.link.to_string())
+                        .chain(imdb_link(&review.ids))
+                        .collect(),
+                    identifier: vec![PropertyValue::new("tmdb", id)],
+                };
+
+                Review::new(
+                    ReviewedItem::TvSeries(item),
+                    review.average_score_u8(),
+                    &review.review,
+                    source,
+                )
+            }
+            TimelineEventReview::BookReview {
+                review,
+                book,
+                source,
+            } => {
+                // The book id can be a cover id, only the work identifies the book itself
+                let identifier = book
+                    .work_key
+                    .iter()
+                    .map(|work_key| {
+                        PropertyValue::new(
+                            "openlibrary_work",
+                            work_key.trim_start_matches("/works/"),
+                        )
+                    })
+                    .collect();
+
+                let item = ReviewedBook {
+                    name: book.title.clone(),
+                    author: book
+                        .authors
+                        .iter()
+                        .map(|name| Person::named(name))
+                        .collect(),
+                    isbn: review.ids.isbn.clone(),
+                    image: image_url(&book.cover),
+                    same_as: book.cover.link_on_click.iter().cloned().collect(),
+                    identifier,
+                };
+
+                Review::new(
+                    ReviewedItem::Book(item),
+                    review.score,
+                    &review.review,
+                    source,
+                )
+            }
+            TimelineEventReview::AlbumReview {
+                review,
+                album,
+                source,
+            } => {
+                let MusicAlbumId::MusicBrainz { id } = &album.id;
+
+                let item = ReviewedMusicAlbum {
+                    name: album.title.clone(),
+                    by_artist: Person::named(&album.artist),
+                    image: image_url(&album.cover),
+                    same_as: vec![album.link.to_string()],
+                    identifier: vec![PropertyValue::new("musicbrainz", id)],
+                };
+
+                Review::new(
+                    ReviewedItem::MusicAlbum(item),
+                    review.score,
+                    &review.review,
+                    source,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct BlogPosting {
+    headline: String,
+    description: String,
+    url: String,
+    date_published: DateTime<Utc>,
+    author: Person,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    keywords: Vec<String>,
+    word_count: usize,
+}
+
+impl From<&BlogPost> for BlogPosting {
+    fn from(post: &BlogPost) -> Self {
+        Self {
+            headline: post.title.clone(),
+            description: post.description.clone(),
+            url: post.permalink(),
+            date_published: post.date,
+            author: Person::site_author(),
+            image: post.hero_image.as_ref().map(image_url),
+            keywords: post.tags.iter().map(|tag| tag.title()).collect(),
+            word_count: post.word_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Photograph {
+    name: String,
+    url: String,
+    content_url: String,
+    thumbnail_url: String,
+    width: u32,
+    height: u32,
+    date_created: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<Person>,
+}
+
+impl Photograph {
+    // The author is only given at the top level, not on every photo in a gallery
+    fn from_photo(photo: &AlbumPhoto, author: Option<Person>) -> Self {
+        Self {
+            name: photo.description.clone(),
+            url: photo.slug.permalink_string(),
+            content_url: photo.image.original.file.as_cdn_url().to_string(),
+            thumbnail_url: photo.image.small.file.as_cdn_url().to_string(),
+            width: photo.image.original.dimensions.width,
+            height: photo.image.original.dimensions.height,
+            date_created: photo.date,
+            keywords: photo.tags.iter().map(|tag| tag.title()).collect(),
+            author,
+        }
+    }
+}
+
+impl From<&AlbumPhoto> for Photograph {
+    fn from(photo: &AlbumPhoto) -> Self {
+        Self::from_photo(photo, Some(Person::site_author()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct ImageGallery {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    url: String,
+    date_published: DateTime<Utc>,
+    author: Person,
+    image: Vec<Photograph>,
+}
+
+impl From<&Album> for ImageGallery {
+    fn from(album: &Album) -> Self {
+        Self {
+            name: album.title.clone(),
+            description: album.description.clone(),
+            url: album.slug.permalink_string(),
+            date_published: album.date,
+            author: Person::site_author(),
+            image: album
+                .photos
+                .iter()
+                .map(|photo| Photograph::from_photo(photo, None))
+                .collect(),
+        }
+    }
+}
+
+// JSON-LD for a page's <head>, https://schema.org
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StructuredData {
+    Review(Box<Review>),
+    BlogPosting(BlogPosting),
+    ImageGallery(ImageGallery),
+    Photograph(Photograph),
+    Person(Person),
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JsonLd<'l> {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(flatten)]
+    data: &'l StructuredData,
+}
+
+impl StructuredData {
+    // Escapes "<" so nothing in the data can close the script tag early
+    pub fn to_json_ld(&self) -> Option<String> {
+        let json_ld = JsonLd {
+            context: SCHEMA_CONTEXT,
+            data: self,
+        };
+
+        match serde_json::to_string(&json_ld) {
+            Ok(json) => Some(json.replace('<', "\\u003c")),
+            Err(e) => {
+                warn!("Unable to serialize structured data: {e}");
+                None
+            }
+        }
+    }
+}
+
+impl From<&TimelineEventReview> for StructuredData {
+    fn from(event: &TimelineEventReview) -> Self {
+        Self::Review(Box::new(event.into()))
+    }
+}
+
+impl From<&BlogPost> for StructuredData {
+    fn from(post: &BlogPost) -> Self {
+        Self::BlogPosting(post.into())
+    }
+}
+
+impl From<&Album> for StructuredData {
+    fn from(album: &Album) -> Self {
+        Self::ImageGallery(album.into())
+    }
+}
+
+impl From<&AlbumPhoto> for StructuredData {
+    fn from(photo: &AlbumPhoto) -> Self {
+        Self::Photograph(photo.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_tag_nested_types_and_escape_script_tags() {
+        let data = StructuredData::Review(Box::new(Review {
+            item_reviewed: ReviewedItem::MusicAlbum(ReviewedMusicAlbum {
+                name: "</script>".to_string(),
+                by_artist: Person::named("David Bowie"),
+                image: "https://cdn.example.com/cover.jpg".to_string(),
+                same_as: vec![],
+                identifier: vec![PropertyValue::new("musicbrainz", "abc")],
+            }),
+            review_rating: Rating::out_of_five(4),
+            review_body: String::new(),
+            author: Person::named("Zoe Aubert"),
+            date_published: DateTime::from_timestamp(1714521600, 0).unwrap(),
+            url: "https://zoeaubert.me/micros/2024/05/01/review".to_string(),
+        }));
+
+        let json_ld = data.to_json_ld().unwrap();
+
+        assert!(!json_ld.contains("</script>"));
+
+        let value: serde_json::Value = serde_json::from_str(&json_ld).unwrap();
+
+        assert_eq!(value["@context"], "https://schema.org");
+        assert_eq!(value["@type"], "Review");
+        assert_eq!(value["itemReviewed"]["@type"], "MusicAlbum");
+        assert_eq!(value["itemReviewed"]["name"], "</script>");
+        assert_eq!(value["itemReviewed"]["byArtist"]["@type"], "Person");
+        assert_eq!(
+            value["itemReviewed"]["identifier"][0]["propertyID"],
+            "musicbrainz"
+        );
+        assert_eq!(value["reviewRating"]["@type"], "Rating");
+        assert_eq!(value["reviewRating"]["ratingValue"], 4);
+        assert_eq!(value["reviewRating"]["bestRating"], 5);
+        assert!(value.get("reviewBody").is_none());
+    }
+}
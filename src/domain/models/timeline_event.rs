@@ -8,13 +8,17 @@ use crate::domain::models::{
     mastodon_post::MastodonPost,
     micro_post::MicroPost,
     movie::Movie,
+    music_album::MusicAlbum,
+    post_status::PostStatus,
     review::{
-        book_review::BookReview, movie_review::MovieReview, review_source::ReviewSource,
-        tv_show_review::TvShowReview,
+        album_review::AlbumReview, book_review::BookReview, movie_review::MovieReview,
+        review_source::ReviewSource, tv_show_review::TvShowReview,
     },
+    slug::Slug,
     tag::Tag,
     tv_show::TvShow,
 };
+use crate::prelude::*;
 
 use chrono::{DateTime, Utc};
 
@@ -42,6 +46,67 @@ pub enum TimelineEventReview {
         tv_show: TvShow,
         source: ReviewSource,
     },
+    AlbumReview {
+        review: AlbumReview,
+        album: Box<MusicAlbum>,
+        source: ReviewSource,
+    },
+}
+
+impl TimelineEventReview {
+    fn try_map_source_content<F>(self, f: &F) -> Result<Self>
+    where
+        F: Fn(&Slug, &str) -> Result<String>,
+    {
+        let map_source = |source: ReviewSource| -> Result<ReviewSource> {
+            Ok(match source {
+                ReviewSource::MicroPost(mut post) => {
+                    post.content = f(&post.slug, &post.content)?;
+                    ReviewSource::MicroPost(post)
+                }
+                source => source,
+            })
+        };
+
+        Ok(match self {
+            Self::BookReview {
+                review,
+                book,
+                source,
+            } => Self::BookReview {
+                review,
+                book,
+                source: map_source(source)?,
+            },
+            Self::MovieReview {
+                review,
+                movie,
+                source,
+            } => Self::MovieReview {
+                review,
+                movie,
+                source: map_source(source)?,
+            },
+            Self::TvShowReview {
+                review,
+                tv_show,
+                source,
+            } => Self::TvShowReview {
+                review,
+                tv_show,
+                source: map_source(source)?,
+            },
+            Self::AlbumReview {
+                review,
+                album,
+                source,
+            } => Self::AlbumReview {
+                review,
+                album,
+                source: map_source(source)?,
+            },
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +138,7 @@ impl TimelineEvent {
                 TimelineEventReview::BookReview { source, .. } => source.slug().to_string(),
                 TimelineEventReview::MovieReview { source, .. } => source.slug().to_string(),
                 TimelineEventReview::TvShowReview { source, .. } => source.slug().to_string(),
+                TimelineEventReview::AlbumReview { source, .. } => source.slug().to_string(),
             },
             TimelineEvent::GameAchievementUnlock(achievement) => match achievement {
                 TimelineEventGameAchievementUnlock::SteamAchievementUnlocked { game, .. } => {
@@ -84,6 +150,40 @@ impl TimelineEvent {
         }
     }
 
+    pub fn title(&self) -> String {
+        let dated = |date: &DateTime<Utc>| format!("Post from {}", date.format("%B %e, %Y"));
+
+        match self {
+            TimelineEvent::Post(post) => match post {
+                TimelineEventPost::BlogPost(post) => post.title.clone(),
+                TimelineEventPost::MicroPost(post) => post
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| dated(&post.date)),
+                TimelineEventPost::MastodonPost(post) => dated(post.created_at()),
+            },
+            TimelineEvent::Review(review) => match review {
+                TimelineEventReview::BookReview { book, .. } => format!("Review of {}", book.title),
+                TimelineEventReview::MovieReview { movie, .. } => {
+                    format!("Review of {}", movie.title)
+                }
+                TimelineEventReview::TvShowReview { tv_show, .. } => {
+                    format!("Review of {}", tv_show.title)
+                }
+                TimelineEventReview::AlbumReview { album, .. } => {
+                    format!("Review of {} by {}", album.title, album.artist)
+                }
+            },
+            TimelineEvent::GameAchievementUnlock(achievement) => match achievement {
+                TimelineEventGameAchievementUnlock::SteamAchievementUnlocked { game, .. } => {
+                    game.name.clone()
+                }
+            },
+            TimelineEvent::Album(album) => album.title.clone(),
+            TimelineEvent::AlbumPhoto { photo, .. } => photo.description.clone(),
+        }
+    }
+
     pub fn date(&self) -> &DateTime<Utc> {
         match self {
             TimelineEvent::Post(post) => match post {
@@ -95,6 +195,7 @@ impl TimelineEvent {
                 TimelineEventReview::BookReview { source, .. } => source.date(),
                 TimelineEventReview::MovieReview { source, .. } => source.date(),
                 TimelineEventReview::TvShowReview { source, .. } => source.date(),
+                TimelineEventReview::AlbumReview { source, .. } => source.date(),
             },
             TimelineEvent::GameAchievementUnlock(achievement) => match achievement {
                 TimelineEventGameAchievementUnlock::SteamAchievementUnlocked {
@@ -118,12 +219,27 @@ impl TimelineEvent {
                 TimelineEventReview::BookReview { source, .. } => Some(source.tags()),
                 TimelineEventReview::MovieReview { source, .. } => Some(source.tags()),
                 TimelineEventReview::TvShowReview { source, .. } => Some(source.tags()),
+                TimelineEventReview::AlbumReview { source, .. } => Some(source.tags()),
             },
             TimelineEvent::GameAchievementUnlock(_) => None,
             TimelineEvent::Album(_) => None,
             TimelineEvent::AlbumPhoto { photo, .. } => Some(&photo.tags),
         }
     }
+
+    pub fn status(&self) -> PostStatus {
+        match self {
+            TimelineEvent::Post(TimelineEventPost::BlogPost(post)) => post.status,
+            TimelineEvent::Post(TimelineEventPost::MicroPost(post)) => post.status,
+            TimelineEvent::Review(
+                TimelineEventReview::BookReview { source, .. }
+                | TimelineEventReview::MovieReview { source, .. }
+                | TimelineEventReview::TvShowReview { source, .. }
+                | TimelineEventReview::AlbumReview { source, .. },
+            ) => source.status(),
+            _ => PostStatus::Published,
+        }
+    }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq)]
@@ -135,6 +251,8 @@ struct EventKey {
 #[derive(Debug, Clone, Default)]
 pub struct TimelineEvents {
     events_by_date: Vec<TimelineEvent>,
+    // Kept apart so nothing that lists events can pick them up by accident
+    unlisted_events_by_date: Vec<TimelineEvent>,
 }
 
 impl TimelineEvents {
@@ -157,8 +275,13 @@ impl TimelineEvents {
 
         events.sort_by(|a, b| b.date().cmp(a.date()));
 
+        let (events_by_date, unlisted_events_by_date) = events
+            .into_iter()
+            .partition(|event| event.status().is_listed());
+
         Self {
-            events_by_date: events,
+            events_by_date,
+            unlisted_events_by_date,
         }
     }
 
@@ -166,6 +289,29 @@ impl TimelineEvents {
         &self.events_by_date
     }
 
+    // For rendering each event's own page, never for lists
+    pub fn all_including_unlisted(&self) -> impl Iterator<Item = &TimelineEvent> {
+        self.events_by_date
+            .iter()
+            .chain(self.unlisted_events_by_date.iter())
+    }
+
+    pub fn blog_posts_including_unlisted(&self) -> impl Iterator<Item = &BlogPost> {
+        self.all_including_unlisted()
+            .filter_map(|event| match event {
+                TimelineEvent::Post(TimelineEventPost::BlogPost(post)) => Some(post.as_ref()),
+                _ => None,
+            })
+    }
+
+    pub fn micro_posts_including_unlisted(&self) -> impl Iterator<Item = &MicroPost> {
+        self.all_including_unlisted()
+            .filter_map(|event| match event {
+                TimelineEvent::Post(TimelineEventPost::MicroPost(post)) => Some(post.as_ref()),
+                _ => None,
+            })
+    }
+
     pub fn blog_posts_by_date(&self) -> impl Iterator<Item = &BlogPost> {
         self.all_by_date().iter().filter_map(|event| match event {
             TimelineEvent::Post(TimelineEventPost::BlogPost(post)) => Some(post.as_ref()),
@@ -187,6 +333,16 @@ impl TimelineEvents {
         })
     }
 
+    pub fn try_map_markdown_content<F>(self, f: F) -> Result<Self>
+    where
+        F: Fn(&Slug, &str) -> Result<String>,
+    {
+        Ok(Self {
+            events_by_date: map_markdown_content(self.events_by_date, &f)?,
+            unlisted_events_by_date: map_markdown_content(self.unlisted_events_by_date, &f)?,
+        })
+    }
+
     pub fn game_achievment_unlocks_by_date(
         &self,
     ) -> impl Iterator<Item = &TimelineEventGameAchievementUnlock> {
@@ -197,6 +353,34 @@ impl TimelineEvents {
     }
 }
 
+// Mastodon posts are HTML from the server, so only our own Markdown is rewritten
+fn map_markdown_content<F>(events: Vec<TimelineEvent>, f: &F) -> Result<Vec<TimelineEvent>>
+where
+    F: Fn(&Slug, &str) -> Result<String>,
+{
+    events
+        .into_iter()
+        .map(|event| {
+            Ok(match event {
+                TimelineEvent::Post(TimelineEventPost::BlogPost(post)) => {
+                    let content = f(&post.slug, &post.content)?;
+                    TimelineEvent::Post(TimelineEventPost::BlogPost(Box::new(
+                        post.with_content(content),
+                    )))
+                }
+                TimelineEvent::Post(TimelineEventPost::MicroPost(mut post)) => {
+                    post.content = f(&post.slug, &post.content)?;
+                    TimelineEvent::Post(TimelineEventPost::MicroPost(post))
+                }
+                TimelineEvent::Review(review) => {
+                    TimelineEvent::Review(review.try_map_source_content(&f)?)
+                }
+                event => event,
+            })
+        })
+        .collect()
+}
+
 impl From<ReviewSource> for TimelineEvent {
     fn from(value: ReviewSource) -> Self {
         match value {
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::domain::models::{image::Image, slug::Slug};
+use crate::domain::models::{image::Image, review::reviewed_work::name_slug, slug::Slug};
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(tag = "type")]
@@ -21,16 +21,22 @@ pub struct TvShow {
     pub poster: Image,
     pub id: TvShowId,
     pub link: Url,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub creators: Vec<String>,
+    #[serde(default)]
+    pub season_count: Option<u16>,
+    // Year the first episode aired
+    #[serde(default)]
+    pub year: Option<u16>,
+    #[serde(default)]
+    pub metadata_version: u32,
 }
 
 impl TvShow {
     pub fn slug(&self) -> Slug {
-        let title = self
-            .title
-            .replace(['&', ':'], "")
-            .replace(' ', "-")
-            .to_lowercase();
-        Slug::new(&format!("/interests/tv/{}", title))
+        Slug::new(&format!("/interests/tv/{}", name_slug(&self.title)))
     }
 }
 
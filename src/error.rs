@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::services::file_service::ContentFile;
+use crate::{domain::models::slug::Slug, services::file_service::ContentFile};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -55,9 +55,21 @@ pub enum Error {
     #[error("Book error: {0}")]
     BookError(#[from] BookError),
 
+    #[error("Music error: {0}")]
+    MusicError(#[from] MusicError),
+
     #[error("Site Build Error: {0}")]
     SiteBuildError(#[from] SiteBuildError),
 
+    #[error("Shortcode error: {0}")]
+    ShortcodeError(#[from] ShortcodeError),
+
+    #[error("Wiki link error: {0}")]
+    WikiLinkError(#[from] WikiLinkError),
+
+    #[error("Content page error: {0}")]
+    ContentPageError(#[from] ContentPageError),
+
     #[error("Inquire Error: {0}")]
     InquireError(#[from] inquire::error::InquireError),
 
@@ -150,6 +162,9 @@ pub enum MarkdownError {
 
     #[error("Could not find body for code block")]
     CouldNotFindBodyForCodeBlock(),
+
+    #[error("Unable to highlight code block: {0}")]
+    UnableToHighlightCodeBlock(syntect::Error),
 }
 
 impl MarkdownError {
@@ -160,6 +175,10 @@ impl MarkdownError {
     pub fn could_not_find_body_for_code_block() -> Error {
         Error::MarkdownError(Self::CouldNotFindBodyForCodeBlock())
     }
+
+    pub fn unable_to_highlight_code_block<E: Into<syntect::Error>>(error: E) -> Error {
+        Error::MarkdownError(Self::UnableToHighlightCodeBlock(error.into()))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -226,6 +245,9 @@ pub enum BlogPostError {
 
     #[error("Unable to parse blog post")]
     UnparsableBlogPost(),
+
+    #[error("Blog post {0} links to missing anchors {1:?}")]
+    BrokenAnchorLinks(String, Vec<String>),
 }
 
 impl BlogPostError {
@@ -236,6 +258,82 @@ impl BlogPostError {
     pub fn unparsable_blog_post() -> Error {
         Error::BlogPostError(Self::UnparsableBlogPost())
     }
+
+    pub fn broken_anchor_links(slug: String, anchors: Vec<String>) -> Error {
+        Error::BlogPostError(Self::BrokenAnchorLinks(slug, anchors))
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ShortcodeError {
+    #[error("Unknown shortcode [{0}] in {1}")]
+    UnknownShortcode(String, String),
+
+    #[error("Shortcode [{0}] in {1} has invalid arguments {2:?}")]
+    InvalidArguments(String, String, Vec<String>),
+
+    #[error("Shortcode [{0}] in {1} references unknown target [{2}]")]
+    UnknownTarget(String, String, String),
+}
+
+impl ShortcodeError {
+    pub fn unknown_shortcode(name: &str, slug: &Slug) -> Error {
+        Error::ShortcodeError(Self::UnknownShortcode(name.to_string(), slug.to_string()))
+    }
+
+    pub fn invalid_arguments(name: &str, slug: &Slug, arguments: &[String]) -> Error {
+        Error::ShortcodeError(Self::InvalidArguments(
+            name.to_string(),
+            slug.to_string(),
+            arguments.to_vec(),
+        ))
+    }
+
+    pub fn unknown_target(name: &str, slug: &Slug, target: &str) -> Error {
+        Error::ShortcodeError(Self::UnknownTarget(
+            name.to_string(),
+            slug.to_string(),
+            target.to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum WikiLinkError {
+    #[error("{0} links to pages that don't exist {1:?}")]
+    UnresolvedLinks(String, Vec<String>),
+}
+
+impl WikiLinkError {
+    pub fn unresolved_links(slug: &Slug, targets: Vec<String>) -> Error {
+        Error::WikiLinkError(Self::UnresolvedLinks(slug.to_string(), targets))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentPageError {
+    #[error("Unable to parse front matter in {0}: {1}")]
+    UnparsableFrontMatter(ContentFile, serde_yaml::Error),
+
+    #[error("Page has no front matter {0}")]
+    PageHasNoFrontMatter(ContentFile),
+
+    #[error("More than one page uses the slug {0}")]
+    DuplicateSlug(String),
+}
+
+impl ContentPageError {
+    pub fn unparsable_front_matter(file: ContentFile, error: serde_yaml::Error) -> Error {
+        Error::ContentPageError(Self::UnparsableFrontMatter(file, error))
+    }
+
+    pub fn no_front_matter(file: ContentFile) -> Error {
+        Error::ContentPageError(Self::PageHasNoFrontMatter(file))
+    }
+
+    pub fn duplicate_slug(slug: &Slug) -> Error {
+        Error::ContentPageError(Self::DuplicateSlug(slug.to_string()))
+    }
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -269,6 +367,9 @@ pub enum NetworkError {
 
     #[error("Unable to send to url: {0}")]
     SendError(reqwest::Error),
+
+    #[error("Url not found: {0}")]
+    NotFound(url::Url),
 }
 
 impl NetworkError {
@@ -279,6 +380,10 @@ impl NetworkError {
     pub fn send_error(error: reqwest::Error) -> Error {
         Error::NetworkError(Self::SendError(error))
     }
+
+    pub fn not_found(url: &url::Url) -> Error {
+        Error::NetworkError(Self::NotFound(url.clone()))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -524,6 +629,25 @@ impl BookError {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum MusicError {
+    #[error("Unable to parse album review: {0}")]
+    UnableToParseAlbumReview(String),
+
+    #[error("Unable to read scrobbles: {0}")]
+    UnableToReadScrobbles(String),
+}
+
+impl MusicError {
+    pub fn unable_to_parse_album_review(error: String) -> Error {
+        Error::MusicError(Self::UnableToParseAlbumReview(error))
+    }
+
+    pub fn unable_to_read_scrobbles(error: String) -> Error {
+        Error::MusicError(Self::UnableToReadScrobbles(error))
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SiteBuildError {
     #[error("Unable to compile Tailwind CSS")]
@@ -534,6 +658,9 @@ pub enum SiteBuildError {
 
     #[error("Unable to create _assets/css directory")]
     UnableToCreateAssetsCssDirectory(),
+
+    #[error("More than one page renders to {0}")]
+    PathRenderedTwice(String),
 }
 
 impl SiteBuildError {
@@ -548,4 +675,8 @@ impl SiteBuildError {
     pub fn unable_to_create_assets_css_directory() -> Error {
         Error::SiteBuildError(Self::UnableToCreateAssetsCssDirectory())
     }
+
+    pub fn path_rendered_twice(path: &str) -> Error {
+        Error::SiteBuildError(Self::PathRenderedTwice(path.to_string()))
+    }
 }
@@ -9,15 +9,24 @@ pub mod utils;
 
 pub mod services;
 
+use std::path::PathBuf;
+
 use build_data::BUILD_DATE;
 use chrono::Utc;
 use clap::{Parser, Subcommand};
-use commands::render_site::render_site;
+use commands::evict_review_cache::evict_review_cache;
+use commands::render_site::{RenderOptions, render_site};
 use tracing::info;
 use tracing_appender::rolling;
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::{prelude::*, processors::process_data, services::ServiceContext};
+use crate::{
+    prelude::*,
+    processors::{
+        ProcessOptions, ResyncTarget, mastodon_export::import_mastodon_export, process_data,
+    },
+    services::ServiceContext,
+};
 
 pub mod build_data {
     include!(concat!(env!("OUT_DIR"), "/build_data.rs"));
@@ -35,7 +44,43 @@ enum Commands {
     #[command(name = "create", about = "Create new content", alias = "c")]
     Create,
     #[command(name = "build", about = "Build the site", alias = "b")]
-    Build,
+    Build {
+        #[arg(
+            long = "full-resync",
+            value_enum,
+            help = "Refetch everything from a source, ignoring query limits"
+        )]
+        full_resync: Vec<ResyncTarget>,
+        #[arg(
+            long = "cache-markdown",
+            help = "Reuse rendered markdown from previous builds, saved in .cache"
+        )]
+        cache_markdown: bool,
+        #[arg(long = "drafts", help = "Include posts with a draft status")]
+        drafts: bool,
+    },
+    #[command(
+        name = "import-mastodon-export",
+        about = "Merge a Mastodon account export into the archive"
+    )]
+    ImportMastodonExport {
+        #[arg(
+            default_value = "mastodon-export",
+            help = "Directory containing outbox.json and media_attachments, relative to .archive"
+        )]
+        dir: PathBuf,
+    },
+    #[command(
+        name = "evict-review-cache",
+        about = "Remove missing or stale movie, tv show and book lookups so they're fetched again"
+    )]
+    EvictReviewCache {
+        #[arg(
+            long = "title",
+            help = "Also evict the cached lookup for this title, or an id such as imdb:tt0080455"
+        )]
+        titles: Vec<String>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -69,16 +114,32 @@ fn main() -> Result<()> {
         Commands::Create => {
             // create_content(&state).await?;
         }
-        Commands::Build => {
+        Commands::Build {
+            full_resync,
+            cache_markdown,
+            drafts,
+        } => {
             info!("Build date: {}", BUILD_DATE);
 
             let start = Utc::now();
 
-            let data = process_data(&ctx)?;
-            render_site(data)?;
+            let options = ProcessOptions {
+                full_resync,
+                include_drafts: drafts,
+                build_time: start,
+            };
+
+            let data = process_data(&ctx, &options)?;
+            render_site(data, &RenderOptions { cache_markdown })?;
 
             info!("Site Build {}ms", (Utc::now() - start).num_milliseconds());
         }
+        Commands::ImportMastodonExport { dir } => {
+            import_mastodon_export(&ctx, &dir)?;
+        }
+        Commands::EvictReviewCache { titles } => {
+            evict_review_cache(&ctx, &titles)?;
+        }
     }
 
     Ok(())
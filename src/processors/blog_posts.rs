@@ -3,7 +3,10 @@ use tracing::info;
 use url::Url;
 
 use crate::{
-    domain::models::{blog_post::BlogPost, slug::Slug, tag::Tag},
+    domain::models::{
+        blog_post::BlogPost, blog_series::BlogPostSeriesPart, post_status::PostStatus, slug::Slug,
+        tag::Tag,
+    },
     error::BlogPostError,
     prelude::*,
     processors::tasks::{ProcessorTask, run_processor_tasks},
@@ -32,6 +35,10 @@ pub struct BlogPostFileFrontMatter {
     hero_width: Option<u32>,
     #[serde(rename = "heroHeight")]
     hero_height: Option<u32>,
+    toc: Option<bool>,
+    #[serde(default)]
+    status: PostStatus,
+    series: Option<BlogPostSeriesPart>,
 }
 
 pub fn front_matter_from_string(s: &str) -> Result<BlogPostFileFrontMatter> {
@@ -78,6 +85,16 @@ impl ProcessorTask for ProcessBlogPost {
                     content.to_owned().to_owned(),
                 );
 
+                post = post.with_status(front_matter.status);
+
+                if let Some(series) = front_matter.series {
+                    post = post.with_series(series);
+                }
+
+                if let Some(toc) = front_matter.toc {
+                    post = post.with_table_of_contents(toc);
+                }
+
                 if let (Some(url), Some(alt)) = (front_matter.hero, front_matter.hero_alt) {
                     let url: Url = url.parse().unwrap();
                     let cdn_file = CdnFile::from_path(url.path());
@@ -0,0 +1,133 @@
+use std::{collections::HashSet, path::Path};
+
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    domain::models::{
+        content_page::{ContentPage, ContentPageLayout, ContentPageNav, ContentPages},
+        slug::Slug,
+    },
+    error::ContentPageError,
+    prelude::*,
+    services::file_service::{ContentFile, FileService, ReadableFile},
+};
+
+pub const CONTENT_PAGES_DIR: &str = "pages";
+
+#[derive(Debug, Clone, Deserialize)]
+struct ContentPageFrontMatter {
+    title: String,
+    description: Option<String>,
+    slug: Option<String>,
+    #[serde(default)]
+    nav: ContentPageNav,
+    #[serde(default)]
+    layout: ContentPageLayout,
+}
+
+fn split_front_matter(file_contents: &str) -> Option<(&str, &str)> {
+    let (front_matter, content) = file_contents
+        .trim_start()
+        .strip_prefix("---")?
+        .split_once("\n---")?;
+
+    Some((front_matter, content.trim_start_matches('-').trim()))
+}
+
+// Without a slug in the front matter, content/pages/colophon.md becomes /colophon
+fn slug_from_path(file_path: &str) -> Slug {
+    let path = Path::new(file_path);
+
+    let relative = path
+        .strip_prefix(Path::new("content").join(CONTENT_PAGES_DIR))
+        .unwrap_or(path)
+        .with_extension("");
+
+    Slug::new(&relative.to_string_lossy())
+}
+
+fn load_content_page(file_path: &str) -> Result<ContentPage> {
+    let file = FileService::content(file_path.into());
+
+    let file_contents = file.read_text()?;
+
+    let (front_matter, content) = split_front_matter(&file_contents)
+        .ok_or_else(|| ContentPageError::no_front_matter(file.clone()))?;
+
+    let front_matter: ContentPageFrontMatter = serde_yaml::from_str(front_matter)
+        .map_err(|e| ContentPageError::unparsable_front_matter(file.clone(), e))?;
+
+    let slug = match &front_matter.slug {
+        Some(slug) => Slug::new(slug),
+        None => slug_from_path(file_path),
+    };
+
+    Ok(ContentPage {
+        slug,
+        title: front_matter.title,
+        description: front_matter.description,
+        nav: front_matter.nav,
+        layout: front_matter.layout,
+        content: content.to_string(),
+    })
+}
+
+pub fn load_content_pages() -> Result<ContentPages> {
+    info!("Processing Content Pages");
+
+    let dir: ContentFile = FileService::content(CONTENT_PAGES_DIR.into());
+
+    if !dir.exists()? {
+        return Ok(ContentPages::default());
+    }
+
+    let mut pages = dir
+        .find_files_recursive("md")?
+        .iter()
+        .map(|file_path| load_content_page(file_path))
+        .collect::<Result<Vec<ContentPage>>>()?;
+
+    pages.sort_by(|a, b| a.slug.as_str().cmp(b.slug.as_str()));
+
+    let mut slugs = HashSet::new();
+
+    for page in &pages {
+        if !slugs.insert(&page.slug) {
+            return Err(ContentPageError::duplicate_slug(&page.slug));
+        }
+    }
+
+    Ok(ContentPages::from_pages(pages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_split_front_matter_and_default_the_slug_to_the_file_path() {
+        let (front_matter, content) = split_front_matter(
+            "---\ntitle: Colophon\nnav:\n  footer: About\nlayout: wide\n---\n\nHow this site is built",
+        )
+        .unwrap();
+
+        let front_matter: ContentPageFrontMatter = serde_yaml::from_str(front_matter).unwrap();
+
+        assert_eq!(front_matter.title, "Colophon");
+        assert_eq!(front_matter.layout, ContentPageLayout::Wide);
+        assert_eq!(
+            front_matter.nav,
+            ContentPageNav {
+                header: false,
+                footer: Some("About".to_string()),
+            }
+        );
+        assert_eq!(content, "How this site is built");
+
+        assert_eq!(
+            slug_from_path("content/pages/about/colophon.md"),
+            Slug::new("/about/colophon")
+        );
+    }
+}
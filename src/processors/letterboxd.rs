@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    domain::models::{
+        micro_post::MicroPost,
+        review::{
+            imported_review::ImportedReview, movie_review::MovieReview, review_ids::ReviewIds,
+        },
+        slug::Slug,
+        tag::Tag,
+    },
+    prelude::*,
+    services::file_service::{FileService, ReadableFile},
+    utils::date::parse_date,
+};
+
+const EXPORT_DIR: &str = "letterboxd-export";
+const DIARY_FILE: &str = "diary.csv";
+const REVIEWS_FILE: &str = "reviews.csv";
+
+const MOVIE_REVIEW_TAG: &str = "Movies";
+
+#[derive(Debug, Clone, Deserialize)]
+struct DiaryRow {
+    #[serde(rename = "Date")]
+    date: String,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Year")]
+    year: Option<u16>,
+    #[serde(rename = "Letterboxd URI")]
+    uri: String,
+    #[serde(rename = "Rating")]
+    rating: Option<f32>,
+    #[serde(rename = "Rewatch")]
+    rewatch: Option<String>,
+    #[serde(rename = "Watched Date")]
+    watched_date: Option<String>,
+    // Only in reviews.csv
+    #[serde(rename = "Review")]
+    review: Option<String>,
+}
+
+impl DiaryRow {
+    // Logged date is when it was entered, which can be days after watching
+    fn watched_date(&self) -> &str {
+        self.watched_date
+            .as_deref()
+            .filter(|date| !date.is_empty())
+            .unwrap_or(&self.date)
+    }
+
+    fn key(&self) -> (String, Option<u16>, String) {
+        (
+            self.name.clone(),
+            self.year,
+            self.watched_date().to_string(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LetterboxdEntry {
+    // Last part of the entry's boxd.it link, unique per viewing
+    id: String,
+    date: DateTime<Utc>,
+    title: String,
+    year: u16,
+    score: u8,
+    rewatch: bool,
+    review: String,
+    uri: String,
+}
+
+impl LetterboxdEntry {
+    fn slug(&self) -> Slug {
+        Slug::new(&format!(
+            "letterboxd/{}/{}",
+            self.date.format("%Y/%m/%d"),
+            self.id
+        ))
+    }
+
+    // Written the way movie reviews are posted so it reads like any other review on the site.
+    // The review itself is carried separately, see `to_movie_review`
+    fn content(&self) -> String {
+        let year = match self.rewatch {
+            true => format!("{} - Rewatch", self.year),
+            false => self.year.to_string(),
+        };
+
+        let score = match self.review.is_empty() {
+            true => format!("{}/5", self.score),
+            false => format!("{}/5 - {}", self.score, self.review),
+        };
+
+        format!("[{}]({}) ({})\n\n{}", self.title, self.uri, year, score)
+    }
+
+    fn to_micro_post(&self) -> MicroPost {
+        MicroPost::new(
+            self.slug(),
+            self.date,
+            self.content(),
+            None,
+            vec![],
+            vec![Tag::from_string(MOVIE_REVIEW_TAG)],
+        )
+    }
+
+    fn to_movie_review(&self) -> MovieReview {
+        MovieReview {
+            title: self.title.clone(),
+            year: self.year,
+            score: self.score,
+            review: self.review.clone(),
+            rewatch: self.rewatch,
+            ids: ReviewIds::default(),
+        }
+    }
+
+    fn to_imported_review(&self) -> ImportedReview {
+        ImportedReview::Movie {
+            review: self.to_movie_review(),
+            post: self.to_micro_post(),
+        }
+    }
+}
+
+// Letterboxd rates in half stars, the site only has whole points out of 5
+fn rating_to_score(rating: f32) -> u8 {
+    (rating.round() as u8).min(5)
+}
+
+fn row_to_entry(row: DiaryRow) -> Result<Option<LetterboxdEntry>> {
+    // Without a rating or year there's nothing to review or look up
+    let (Some(rating), Some(year)) = (row.rating, row.year) else {
+        return Ok(None);
+    };
+
+    let id = row
+        .uri
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(Some(LetterboxdEntry {
+        id,
+        date: parse_date(row.watched_date())?,
+        title: row.name,
+        year,
+        score: rating_to_score(rating),
+        rewatch: row
+            .rewatch
+            .is_some_and(|rewatch| rewatch.eq_ignore_ascii_case("yes")),
+        review: row
+            .review
+            .map(|review| review.trim().replace("<br>", "\n"))
+            .unwrap_or_default(),
+        uri: row.uri,
+    }))
+}
+
+// Reviews are diary entries with text, so they replace the diary row for the same viewing
+fn merge_rows(diary: Vec<DiaryRow>, reviews: Vec<DiaryRow>) -> Vec<DiaryRow> {
+    let mut rows = diary
+        .into_iter()
+        .map(|row| (row.key(), row))
+        .collect::<HashMap<_, _>>();
+
+    for review in reviews {
+        rows.insert(review.key(), review);
+    }
+
+    rows.into_values().collect()
+}
+
+fn read_rows(file_name: &str) -> Result<Vec<DiaryRow>> {
+    let file = FileService::content(format!("{}/{}", EXPORT_DIR, file_name).into());
+
+    match file.exists()? {
+        true => file.read_csv(),
+        false => Ok(vec![]),
+    }
+}
+
+pub fn load_letterboxd_reviews() -> Result<Vec<ImportedReview>> {
+    let rows = merge_rows(read_rows(DIARY_FILE)?, read_rows(REVIEWS_FILE)?);
+
+    let entries = rows
+        .into_iter()
+        .filter_map(|row| row_to_entry(row).transpose())
+        .collect::<Result<Vec<LetterboxdEntry>>>()?;
+
+    info!("Letterboxd entries {}", entries.len());
+
+    Ok(entries
+        .iter()
+        .map(|entry| entry.to_imported_review())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(uri: &str, rating: Option<f32>, review: Option<&str>) -> DiaryRow {
+        DiaryRow {
+            date: "2023-05-14".to_string(),
+            name: "Dune".to_string(),
+            year: Some(2021),
+            uri: uri.to_string(),
+            rating,
+            rewatch: Some("Yes".to_string()),
+            watched_date: Some("2023-05-12".to_string()),
+            review: review.map(|review| review.to_string()),
+        }
+    }
+
+    #[test]
+    fn it_should_merge_reviews_into_the_diary_and_post_them_as_movie_reviews() {
+        let rows = merge_rows(
+            vec![row("https://boxd.it/abc1", Some(3.5), None)],
+            vec![row(
+                "https://boxd.it/abc1",
+                Some(3.5),
+                Some("Even better.<br><br>The sound is incredible - 5/5 for that alone"),
+            )],
+        );
+
+        let entry = row_to_entry(rows[0].clone()).unwrap().unwrap();
+
+        assert_eq!(entry.score, 4);
+        assert!(entry.rewatch);
+        assert_eq!(entry.date, parse_date("2023-05-12").unwrap());
+
+        let post = entry.to_micro_post();
+
+        assert_eq!(post.slug, Slug::new("/letterboxd/2023/05/12/abc1"));
+
+        let review = entry.to_movie_review();
+
+        assert_eq!(review.title, "Dune");
+        assert_eq!(review.year, 2021);
+        assert_eq!(review.score, 4);
+        assert!(review.rewatch);
+        assert_eq!(
+            review.review,
+            "Even better.\n\nThe sound is incredible - 5/5 for that alone"
+        );
+
+        assert!(
+            row_to_entry(row("https://boxd.it/abc2", None, None))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,16 +1,27 @@
-use std::{path::Path, thread::sleep, time::Duration};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    path::Path,
+    thread::sleep,
+    time::Duration,
+};
 
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Deserialize;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 use url::Url;
 
 use crate::{
     config::CONFIG,
     domain::models::{
-        mastodon_post::{MastodonPost, MastodonPostNonSpoiler, MastodonPostSpoiler, MastodonPosts},
+        mastodon_post::{
+            MastodonAnnouncement, MastodonPost, MastodonPostEngagement, MastodonPostNonSpoiler,
+            MastodonPostSpoiler, MastodonPosts, MastodonReply, MastodonReplyAccount,
+        },
+        media::Media,
+        slug::Slug,
         tag::Tag,
     },
     prelude::*,
@@ -23,9 +34,11 @@ use crate::{
     },
 };
 
-const FILE_NAME: &str = "mastodon_posts.json";
+pub const FILE_NAME: &str = "mastodon_posts.json";
+const ALT_TEXT_OVERRIDES_FILE_NAME: &str = "mastodon_alt_text.yml";
+const MISSING_ALT_TEXT_REPORT_FILE_NAME: &str = "reports/mastodon_missing_alt_text.json";
 const QUERY: &str = "mastodon";
-const SELF_URL: &str = "zoeaubert.me/blog";
+pub const SELF_URL: &str = "zoeaubert.me/blog";
 const APPLICATIONS_TO_IGNORE: [&str; 2] = ["Micro.blog", "status.lol"];
 
 static TAGS_REGEX: Lazy<Regex> =
@@ -33,11 +46,15 @@ static TAGS_REGEX: Lazy<Regex> =
 
 static EMPTY_P_TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<p>\s*</p>"#).unwrap());
 
+static SELF_BLOG_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"https?://zoeaubert\.me(/blog/[^"'\s<>?#]+)"#).unwrap());
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 enum MastodonStatusMedia {
     #[serde(rename = "image")]
     Image {
+        id: String,
         url: Url,
         description: Option<String>,
     },
@@ -55,11 +72,8 @@ struct MastodonStatus {
     created_at: DateTime<Utc>,
     content: String,
     media_attachments: Vec<MastodonStatusMedia>,
-    #[allow(unused)]
     reblogs_count: u32,
-    #[allow(unused)]
     favourites_count: u32,
-    #[allow(unused)]
     replies_count: u32,
     application: Option<MastodonStatusApplication>,
     #[allow(unused)]
@@ -68,12 +82,38 @@ struct MastodonStatus {
     edited_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MastodonContextAccount {
+    display_name: String,
+    acct: String,
+    url: Url,
+}
+
+#[derive(Debug, Deserialize)]
+struct MastodonContextStatus {
+    id: String,
+    in_reply_to_id: Option<String>,
+    uri: Url,
+    url: Option<Url>,
+    created_at: DateTime<Utc>,
+    content: String,
+    visibility: String,
+    account: MastodonContextAccount,
+}
+
+#[derive(Debug, Deserialize)]
+struct MastodonContext {
+    descendants: Vec<MastodonContextStatus>,
+}
+
+const MASTODON_API_URL: &str = "https://social.lol/api/v1";
 const MASTODON_PAGINATION_LIMIT: u32 = 40;
+const PUBLIC_VISIBILITY: &str = "public";
 
 static API_BASE_URL: Lazy<String> = Lazy::new(|| {
     format!(
-        "https://social.lol/api/v1/accounts/{}/statuses?exclude_reblogs=true&exclude_replies=true&limit={}",
-        CONFIG.mastodon.account_id, MASTODON_PAGINATION_LIMIT,
+        "{}/accounts/{}/statuses?exclude_reblogs=true&exclude_replies=true&limit={}",
+        MASTODON_API_URL, CONFIG.mastodon.account_id, MASTODON_PAGINATION_LIMIT,
     )
 });
 
@@ -87,53 +127,173 @@ fn fetch_page(ctx: &ServiceContext, oldest: Option<&String>) -> Result<Vec<Masto
     ctx.network.download_json(&url)
 }
 
-fn fetch_most_recent(ctx: &ServiceContext) -> Result<Vec<MastodonStatus>> {
-    fetch_page(ctx, None)
+fn fetch_replies(ctx: &ServiceContext, id: &str) -> Result<Vec<MastodonReply>> {
+    let url: Url = format!("{}/statuses/{}/context", MASTODON_API_URL, id)
+        .parse()
+        .unwrap();
+
+    let context: MastodonContext = ctx.network.download_json(&url)?;
+
+    let replies = context
+        .descendants
+        .into_iter()
+        .filter(|status| status.visibility == PUBLIC_VISIBILITY)
+        .map(|status| MastodonReply {
+            id: status.id,
+            in_reply_to_id: status.in_reply_to_id,
+            url: status.url.unwrap_or(status.uri),
+            created_at: status.created_at,
+            content: status.content,
+            account: MastodonReplyAccount {
+                display_name: status.account.display_name,
+                acct: status.account.acct,
+                url: status.account.url,
+            },
+        })
+        .collect();
+
+    Ok(replies)
 }
 
-fn fetch_all(ctx: &ServiceContext) -> Result<Vec<MastodonStatus>> {
+struct MastodonSync {
+    statuses: Vec<MastodonStatus>,
+    deleted: Vec<String>,
+}
+
+// Status ids are snowflakes, so they sort in the same order the API pages through them
+fn status_id_order(id: &str) -> u64 {
+    id.parse().unwrap_or(0)
+}
+
+fn is_unchanged(post: &MastodonPost, status: &MastodonStatus) -> bool {
+    *post.updated_at() == status.edited_at.unwrap_or(status.created_at)
+}
+
+fn is_seen_and_unchanged(posts: &MastodonPosts, status: &MastodonStatus) -> bool {
+    match posts.find_by_id(&status.id) {
+        Some(post) => is_unchanged(post, status),
+        None => posts.contains_announcement(&status.id),
+    }
+}
+
+// Pages back through statuses until we reach ones we've already got and haven't been edited.
+// Anything we hold that falls within the fetched window but wasn't returned has been deleted.
+// Imported posts older than anything the API returned are out of its reach, not deleted
+fn sync_statuses<F>(
+    posts: &MastodonPosts,
+    full_resync: bool,
+    page_size: usize,
+    fetch_page: F,
+) -> Result<MastodonSync>
+where
+    F: Fn(Option<&String>) -> Result<Vec<MastodonStatus>>,
+{
     let mut statuses: Vec<MastodonStatus> = Vec::new();
 
-    loop {
+    let reached_end = loop {
         let oldest = statuses.last().map(|s| &s.id);
 
-        let page = fetch_page(ctx, oldest)?;
+        let page = fetch_page(oldest)?;
 
-        if page.is_empty() {
-            break;
-        }
+        let page_len = page.len();
+
+        let reached_seen = !full_resync && page.iter().any(|s| is_seen_and_unchanged(posts, s));
 
         statuses.extend(page);
 
-        if statuses.len() < MASTODON_PAGINATION_LIMIT as usize {
-            break;
+        if page_len < page_size {
+            break true;
         }
 
-        sleep(Duration::from_secs(5));
-    }
+        if reached_seen {
+            break false;
+        }
+    };
 
-    Ok(statuses)
+    let window_start = match reached_end {
+        true => 0,
+        false => statuses
+            .last()
+            .map(|s| status_id_order(&s.id))
+            .unwrap_or(u64::MAX),
+    };
+
+    let oldest_fetched = statuses
+        .last()
+        .map(|s| status_id_order(&s.id))
+        .unwrap_or(u64::MAX);
+
+    let fetched = statuses
+        .iter()
+        .map(|s| s.id.as_str())
+        .collect::<HashSet<&str>>();
+
+    let deleted = posts
+        .ids()
+        .filter(|id| status_id_order(id) >= window_start && !fetched.contains(id))
+        .filter(|id| !(posts.is_imported(id) && status_id_order(id) < oldest_fetched))
+        .map(|id| id.to_string())
+        .collect();
+
+    Ok(MastodonSync { statuses, deleted })
 }
 
-fn extract_tags(content: &str) -> Vec<String> {
+pub fn extract_tags(content: &str) -> Vec<String> {
     TAGS_REGEX
         .captures_iter(content)
         .map(|capture| capture.get(1).unwrap().as_str().to_string())
         .collect()
 }
 
-fn strip_tags(content: &str) -> String {
+pub fn strip_tags(content: &str) -> String {
     let content = TAGS_REGEX.replace_all(content, "").to_string();
 
     EMPTY_P_TAGS.replace_all(&content, "").to_string()
 }
 
+pub fn extract_self_blog_slug(content: &str) -> Option<Slug> {
+    SELF_BLOG_LINK_REGEX
+        .captures(content)
+        .and_then(|capture| capture.get(1))
+        .map(|path| Slug::new(path.as_str()))
+}
+
+enum ProcessedStatus {
+    Post(MastodonPost),
+    Announcement(Slug, MastodonAnnouncement),
+}
+
 struct ProcessStatus {
     status: MastodonStatus,
+    existing_post: Option<MastodonPost>,
+    existing_replies: Vec<MastodonReply>,
+}
+
+impl ProcessStatus {
+    fn engagement(&self, ctx: &ServiceContext) -> MastodonPostEngagement {
+        let engagement = MastodonPostEngagement::new(
+            self.status.reblogs_count,
+            self.status.favourites_count,
+            self.status.replies_count,
+        );
+
+        let replies = match self.status.replies_count {
+            0 => vec![],
+            _ => match fetch_replies(ctx, &self.status.id) {
+                Ok(replies) => replies,
+                Err(e) => {
+                    warn!("Unable to fetch replies for [{}] {}", self.status.id, e);
+                    self.existing_replies.clone()
+                }
+            },
+        };
+
+        engagement.with_replies(replies)
+    }
 }
 
 impl ProcessorTask for ProcessStatus {
-    type Output = Option<MastodonPost>;
+    type Output = Option<ProcessedStatus>;
 
     fn run(self, ctx: &ServiceContext) -> Result<Self::Output> {
         if let Some(application) = &self.status.application
@@ -143,7 +303,29 @@ impl ProcessorTask for ProcessStatus {
         }
 
         if self.status.content.contains(SELF_URL) {
-            return Ok(None);
+            return match extract_self_blog_slug(&self.status.content) {
+                Some(slug) => Ok(Some(ProcessedStatus::Announcement(
+                    slug,
+                    MastodonAnnouncement {
+                        id: self.status.id.clone(),
+                        original_uri: self.status.uri.clone(),
+                        engagement: self.engagement(ctx),
+                    },
+                ))),
+                None => Ok(None),
+            };
+        }
+
+        let engagement = self.engagement(ctx);
+
+        // Nothing about the content has changed, so don't bother reprocessing its media.
+        // Older archives dropped undescribed images and didn't record attachment ids, so those get redone
+        if let Some(mut post) = self.existing_post
+            && is_unchanged(&post, &self.status)
+            && post.attachment_ids().len() == self.status.media_attachments.len()
+        {
+            post.set_engagement(engagement);
+            return Ok(Some(ProcessedStatus::Post(post)));
         }
 
         info!("Processing Mastodon post {:?}", &self.status.created_at);
@@ -175,67 +357,341 @@ impl ProcessorTask for ProcessStatus {
             )),
         };
 
+        post.set_engagement(engagement);
+
         for attachment in self.status.media_attachments.iter() {
             match attachment {
-                MastodonStatusMedia::Image { url, description } => {
-                    if let Some(description) = description {
-                        let url_path = Path::new(url.path());
-
-                        let file_name = url_path.file_name().unwrap().to_str().unwrap();
-
-                        let cdn_file = CdnFile::from_date_and_file_name(
-                            &self.status.created_at,
-                            file_name,
-                            None,
-                        );
-
-                        let image = MediaService::image_from_url(
-                            ctx,
-                            url,
-                            &cdn_file,
-                            description,
-                            Some(&post.slug().relative_string()),
-                            Some(self.status.created_at),
-                        )?;
-
-                        post.add_media(image.into());
+                MastodonStatusMedia::Image {
+                    id,
+                    url,
+                    description,
+                } => {
+                    let url_path = Path::new(url.path());
+
+                    let file_name = url_path.file_name().unwrap().to_str().unwrap();
+
+                    let cdn_file =
+                        CdnFile::from_date_and_file_name(&self.status.created_at, file_name, None);
+
+                    if description.is_none() {
+                        warn!("Mastodon image has no description [{}] [{}]", post.id(), id);
                     }
+
+                    let image = MediaService::image_from_url(
+                        ctx,
+                        url,
+                        &cdn_file,
+                        description.as_deref().unwrap_or_default(),
+                        Some(&post.slug().relative_string()),
+                        Some(self.status.created_at),
+                    )?;
+
+                    post.add_attachment(id.clone(), image.into());
                 }
             }
         }
 
-        Ok(Some(post))
+        Ok(Some(ProcessedStatus::Post(post)))
     }
 }
 
-pub fn load_mastodon_posts(ctx: &ServiceContext) -> Result<MastodonPosts> {
-    let file = FileService::archive(FILE_NAME.into());
+fn sync_posts(ctx: &ServiceContext, posts: &mut MastodonPosts, full_resync: bool) -> Result<()> {
+    info!(
+        "Fetching mastodon posts data | Full resync: {}",
+        full_resync
+    );
+
+    let sync = sync_statuses(
+        posts,
+        full_resync,
+        MASTODON_PAGINATION_LIMIT as usize,
+        |oldest| {
+            if oldest.is_some() {
+                sleep(Duration::from_secs(5));
+            }
 
-    let mut posts: MastodonPosts = file.read_json_or_default()?;
+            fetch_page(ctx, oldest)
+        },
+    )?;
 
-    if !ctx.query_limiter.can_query_within_hour(QUERY)? {
-        return Ok(posts);
+    let tasks = sync
+        .statuses
+        .into_iter()
+        .map(|status| {
+            let existing_post = posts.find_by_id(&status.id).cloned();
+
+            let existing_replies = posts
+                .find_replies_by_id(&status.id)
+                .cloned()
+                .unwrap_or_default();
+
+            ProcessStatus {
+                status,
+                existing_post,
+                existing_replies,
+            }
+        })
+        .collect();
+
+    let results = run_processor_tasks(tasks, ctx)?;
+
+    for processed in results.into_iter().flatten() {
+        match processed {
+            ProcessedStatus::Post(post) => posts.add(post),
+            ProcessedStatus::Announcement(slug, announcement) => {
+                posts.add_announcement(slug, announcement)
+            }
+        }
     }
 
-    info!("Fetching mastodon posts data");
+    let deleted_at = Utc::now();
 
-    let statuses = match posts.count() {
-        0 => fetch_all(ctx)?,
-        _ => fetch_most_recent(ctx)?,
-    };
+    for id in sync.deleted {
+        info!("Mastodon post deleted [{}]", id);
+        posts.tombstone(&id, deleted_at);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct MissingAltTextReportEntry {
+    attachment_id: String,
+    status_id: String,
+    status_uri: Url,
+    created_at: DateTime<Utc>,
+    media_url: Url,
+}
 
-    let tasks = statuses
+fn write_missing_alt_text_report(posts: &MastodonPosts) -> Result<()> {
+    let mut entries = posts
+        .posts()
         .into_iter()
-        .map(|status| ProcessStatus { status })
-        .collect();
+        .flat_map(|post| {
+            post.attachments()
+                .filter_map(move |(attachment_id, media)| match media {
+                    Media::Image(image) if !image.has_description() => {
+                        Some(MissingAltTextReportEntry {
+                            attachment_id: attachment_id.clone(),
+                            status_id: post.id().to_string(),
+                            status_uri: post.original_uri().clone(),
+                            created_at: *post.created_at(),
+                            media_url: media.original_cdn_url(),
+                        })
+                    }
+                    _ => None,
+                })
+        })
+        .collect::<Vec<MissingAltTextReportEntry>>();
+
+    entries.sort_by_key(|entry| Reverse(entry.created_at));
+
+    if !entries.is_empty() {
+        warn!(
+            "Mastodon images without descriptions: {} [{}]",
+            entries.len(),
+            MISSING_ALT_TEXT_REPORT_FILE_NAME
+        );
+    }
 
-    let results = run_processor_tasks(tasks, ctx)?;
+    FileService::cache(MISSING_ALT_TEXT_REPORT_FILE_NAME.into()).write_json(&entries)
+}
+
+// Lets me fix up alt text locally without having to edit the post on Mastodon
+fn read_alt_text_overrides() -> Result<HashMap<String, String>> {
+    let file = FileService::content(ALT_TEXT_OVERRIDES_FILE_NAME.into());
 
-    for post in results.into_iter().flatten() {
-        posts.add(post);
+    match file.exists()? {
+        true => file.read_yaml(),
+        false => Ok(HashMap::new()),
     }
+}
 
-    file.write_json(&posts)?;
+pub fn load_mastodon_posts(ctx: &ServiceContext, full_resync: bool) -> Result<MastodonPosts> {
+    let file = FileService::archive(FILE_NAME.into());
+
+    let mut posts: MastodonPosts = file.read_json_or_default()?;
+
+    // A full resync ignores the limit but still counts as the latest sync
+    if full_resync {
+        ctx.query_limiter.record_query(QUERY)?;
+    }
+
+    if full_resync || ctx.query_limiter.can_query_within_hour(QUERY)? {
+        sync_posts(ctx, &mut posts, full_resync)?;
+
+        file.write_json(&posts)?;
+    }
+
+    posts.apply_alt_text_overrides(&read_alt_text_overrides()?);
+
+    write_missing_alt_text_report(&posts)?;
 
     Ok(posts)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A full statuses response, with snowflake ids, edits, cross-posts and nulls as the API sends
+    // them, so the sync is tested against real payloads rather than just the fields it reads
+    const STATUSES: &str = include_str!("fixtures/mastodon_statuses.json");
+
+    // Mimics the API's max_id pagination over the statuses
+    fn statuses_page(oldest: Option<&String>, page_size: usize) -> Result<Vec<MastodonStatus>> {
+        let statuses: Vec<MastodonStatus> = serde_json::from_str(STATUSES).unwrap();
+
+        Ok(statuses
+            .into_iter()
+            .filter(|status| match oldest {
+                Some(max_id) => status_id_order(&status.id) < status_id_order(max_id),
+                None => true,
+            })
+            .take(page_size)
+            .collect())
+    }
+
+    fn archived_post(id: &str, created_at: &str, updated_at: &str) -> MastodonPost {
+        MastodonPost::NonSpoiler(MastodonPostNonSpoiler::new(
+            id.to_string(),
+            format!("https://social.lol/users/geekyaubergine/statuses/{}", id)
+                .parse()
+                .unwrap(),
+            created_at.parse().unwrap(),
+            "<p>Archived</p>".to_string(),
+            vec![],
+            updated_at.parse().unwrap(),
+        ))
+    }
+
+    fn fetched_ids(sync: &MastodonSync) -> Vec<&str> {
+        sync.statuses.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn it_should_fetch_everything_when_archive_is_empty() {
+        let posts = MastodonPosts::default();
+
+        let sync = sync_statuses(&posts, false, 2, |oldest| statuses_page(oldest, 2)).unwrap();
+
+        assert_eq!(sync.statuses.len(), 6);
+        assert!(sync.deleted.is_empty());
+    }
+
+    #[test]
+    fn it_should_stop_paging_at_seen_unchanged_status_and_tombstone_missing_ones() {
+        let mut posts = MastodonPosts::default();
+        posts.add(archived_post(
+            "112971588894743824",
+            "2024-08-16T12:00:45Z",
+            "2024-08-16T12:00:45Z",
+        ));
+        posts.add(archived_post(
+            "112976776396828162",
+            "2024-08-17T10:00:00Z",
+            "2024-08-17T10:00:00Z",
+        ));
+        posts.add(archived_post(
+            "112962000453681668",
+            "2024-08-14T19:22:17Z",
+            "2024-08-14T19:22:17Z",
+        ));
+
+        let sync = sync_statuses(&posts, false, 2, |oldest| statuses_page(oldest, 2)).unwrap();
+
+        assert_eq!(
+            fetched_ids(&sync),
+            vec![
+                "112995659350030913",
+                "112988029780005314",
+                "112985085181955047",
+                "112971588894743824",
+            ]
+        );
+        assert_eq!(sync.deleted, vec!["112976776396828162".to_string()]);
+    }
+
+    #[test]
+    fn it_should_keep_paging_past_edited_statuses() {
+        let mut posts = MastodonPosts::default();
+        posts.add(archived_post(
+            "112985085181955047",
+            "2024-08-18T21:13:02Z",
+            "2024-08-18T21:13:02Z",
+        ));
+        posts.add(archived_post(
+            "112971588894743824",
+            "2024-08-16T12:00:45Z",
+            "2024-08-16T12:00:45Z",
+        ));
+
+        let sync = sync_statuses(&posts, false, 1, |oldest| statuses_page(oldest, 1)).unwrap();
+
+        assert_eq!(
+            fetched_ids(&sync),
+            vec![
+                "112995659350030913",
+                "112988029780005314",
+                "112985085181955047",
+                "112971588894743824",
+            ]
+        );
+
+        let edited = &sync.statuses[2];
+        let archived = posts.find_by_id(&edited.id).unwrap();
+
+        assert!(!is_unchanged(archived, edited));
+        assert!(is_unchanged(
+            posts.find_by_id("112971588894743824").unwrap(),
+            &sync.statuses[3]
+        ));
+    }
+
+    #[test]
+    fn it_should_tombstone_everything_missing_on_full_resync() {
+        let mut posts = MastodonPosts::default();
+        posts.add(archived_post(
+            "112971588894743824",
+            "2024-08-16T12:00:45Z",
+            "2024-08-16T12:00:45Z",
+        ));
+        posts.add(archived_post(
+            "111677748019207031",
+            "2024-01-01T00:00:00Z",
+            "2024-01-01T00:00:00Z",
+        ));
+
+        let sync = sync_statuses(&posts, true, 2, |oldest| statuses_page(oldest, 2)).unwrap();
+
+        assert_eq!(sync.statuses.len(), 6);
+        assert_eq!(sync.deleted, vec!["111677748019207031".to_string()]);
+
+        for id in &sync.deleted {
+            posts.tombstone(id, Utc::now());
+        }
+
+        assert!(posts.is_tombstoned("111677748019207031"));
+        assert!(posts.find_by_id("111677748019207031").is_none());
+        assert!(posts.find_by_id("112971588894743824").is_some());
+    }
+
+    #[test]
+    fn it_should_not_tombstone_imported_posts_older_than_the_api_reaches() {
+        let mut posts = MastodonPosts::default();
+        posts.add_imported(archived_post(
+            "111677748019207031",
+            "2024-01-01T00:00:00Z",
+            "2024-01-01T00:00:00Z",
+        ));
+        posts.add_imported(archived_post(
+            "112976776396828162",
+            "2024-08-17T10:00:00Z",
+            "2024-08-17T10:00:00Z",
+        ));
+
+        let sync = sync_statuses(&posts, true, 2, |oldest| statuses_page(oldest, 2)).unwrap();
+
+        assert_eq!(sync.deleted, vec!["112976776396828162".to_string()]);
+    }
+}
@@ -0,0 +1,569 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::{
+    domain::models::mastodon_post::{
+        MastodonAnnouncement, MastodonPost, MastodonPostEngagement, MastodonPostNonSpoiler,
+        MastodonPostSpoiler, MastodonPosts,
+    },
+    domain::models::{micro_post::MicroPost, slug::Slug, tag::Tag},
+    prelude::*,
+    processors::{
+        mastodon::{FILE_NAME, SELF_URL, extract_self_blog_slug, extract_tags, strip_tags},
+        micro_posts::load_micro_posts,
+        social_archives::{ArchiveItem, load_social_archive_items},
+        tasks::{ProcessorTask, run_processor_tasks},
+    },
+    services::{
+        ServiceContext,
+        cdn_service::CdnFile,
+        file_service::{FileService, ReadableFile, WritableFile},
+        media_service::MediaService,
+    },
+};
+
+const OUTBOX_FILE_NAME: &str = "outbox.json";
+const PUBLIC_AUDIENCE: &str = "https://www.w3.org/ns/activitystreams#Public";
+// Enough of the text to tell posts apart, Micro.blog shortens long posts when cross-posting
+const CROSS_POST_PREFIX_LENGTH: usize = 80;
+
+// Html tags, markdown images and links, which cross-posting adds or removes
+static MARKUP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<[^>]*>|!\[[^\]]*\]\([^)]*\)|\(https?://[^)]*\)|https?://\S+").unwrap()
+});
+static HREF_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"href="([^"]+)""#).unwrap());
+
+#[derive(Debug, Deserialize)]
+struct OutboxAttachment {
+    #[serde(rename = "mediaType")]
+    media_type: Option<String>,
+    url: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutboxNote {
+    id: Url,
+    published: DateTime<Utc>,
+    updated: Option<DateTime<Utc>>,
+    summary: Option<String>,
+    content: Option<String>,
+    #[serde(rename = "inReplyTo")]
+    in_reply_to: Option<String>,
+    #[serde(default)]
+    to: Vec<String>,
+    #[serde(default)]
+    attachment: Vec<OutboxAttachment>,
+}
+
+impl OutboxNote {
+    fn status_id(&self) -> Option<String> {
+        self.id
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .map(|id| id.to_string())
+    }
+
+    fn is_public(&self) -> bool {
+        self.to.iter().any(|to| to == PUBLIC_AUDIENCE)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum OutboxActivity {
+    Create {
+        object: Box<OutboxNote>,
+    },
+    // Boosts and anything else we don't publish
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Outbox {
+    #[serde(rename = "orderedItems")]
+    ordered_items: Vec<OutboxActivity>,
+}
+
+// Attachments are stored under their id split into groups of three digits,
+// e.g. /media_attachments/files/112/345/678/901/234/567/original/abc.jpg
+fn attachment_id_from_path(path: &str) -> Option<String> {
+    let segments = path.split('/').collect::<Vec<&str>>();
+
+    let start = segments.iter().position(|segment| *segment == "files")? + 1;
+    let end = segments.iter().position(|segment| *segment == "original")?;
+
+    let id = segments
+        .get(start..end)?
+        .iter()
+        .map(|segment| segment.to_string())
+        .collect::<String>();
+
+    match id.trim_start_matches('0') {
+        "" => None,
+        id => Some(id.to_string()),
+    }
+}
+
+fn plain_text(content: &str) -> String {
+    MARKUP_REGEX
+        .replace_all(content, " ")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// A micro post, or an archived one, that a status could be a cross-post of
+struct CrossPostSource {
+    date: DateTime<Utc>,
+    // The last part of its url, which is what a cross-post links back to
+    id: String,
+    text: String,
+}
+
+impl CrossPostSource {
+    fn from_micro_post(post: &MicroPost) -> Self {
+        Self {
+            date: post.date,
+            id: last_path_segment(&post.slug.relative_string()),
+            text: plain_text(&post.content),
+        }
+    }
+
+    fn from_archive_item(item: &ArchiveItem) -> Self {
+        Self {
+            date: item.date,
+            id: item.id.clone(),
+            text: plain_text(&item.content),
+        }
+    }
+}
+
+// e.g. /2023/01/29/new-post-saving.html -> new-post-saving
+fn last_path_segment(path: &str) -> String {
+    let segment = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+
+    segment.trim_end_matches(".html").to_string()
+}
+
+// Links to anywhere other than the Mastodon server itself, so hashtags and mentions are ignored
+fn external_links(note: &OutboxNote) -> Vec<Url> {
+    HREF_REGEX
+        .captures_iter(note.content.as_deref().unwrap_or_default())
+        .filter_map(|cap| cap[1].parse::<Url>().ok())
+        .filter(|url| url.host_str() != note.id.host_str())
+        .collect()
+}
+
+fn links_to_cross_post_source(url: &Url, sources: &[CrossPostSource]) -> bool {
+    if url
+        .host_str()
+        .is_some_and(|host| host.ends_with("micro.blog"))
+    {
+        return true;
+    }
+
+    let id = last_path_segment(url.path());
+
+    !id.is_empty() && sources.iter().any(|source| source.id == id)
+}
+
+// Micro post dates have no timezone, so a day either side is allowed
+fn starts_like_cross_post_source(note: &OutboxNote, sources: &[CrossPostSource]) -> bool {
+    let text = plain_text(note.content.as_deref().unwrap_or_default());
+
+    if text.is_empty() {
+        return false;
+    }
+
+    let prefix = text
+        .chars()
+        .take(CROSS_POST_PREFIX_LENGTH)
+        .collect::<String>();
+
+    sources.iter().any(|source| {
+        let source_prefix = source
+            .text
+            .chars()
+            .take(CROSS_POST_PREFIX_LENGTH)
+            .collect::<String>();
+
+        (note.published - source.date).num_days().abs() <= 1
+            && !source_prefix.is_empty()
+            && (prefix.starts_with(&source_prefix) || source_prefix.starts_with(&prefix))
+    })
+}
+
+// The export doesn't say which app posted a status, so the Micro.blog and status.lol cross-posts
+// the API skips are found by the link back to the post they came from. Short posts are
+// cross-posted without one, so those are matched on their text
+fn is_cross_post(note: &OutboxNote, sources: &[CrossPostSource]) -> bool {
+    let links = external_links(note);
+
+    if let Some(link) = links
+        .iter()
+        .find(|link| links_to_cross_post_source(link, sources))
+    {
+        info!("Skipping cross-post [{}] linking to [{}]", note.id, link);
+        return true;
+    }
+
+    if links.is_empty() && starts_like_cross_post_source(note, sources) {
+        info!("Skipping cross-post [{}] matched on its text", note.id);
+        return true;
+    }
+
+    false
+}
+
+// Only public, top level statuses that we don't already have (or have deliberately deleted) and
+// that aren't cross-posts of micro posts
+fn notes_to_import(
+    outbox: Outbox,
+    posts: &MastodonPosts,
+    sources: &[CrossPostSource],
+) -> Vec<(String, OutboxNote)> {
+    outbox
+        .ordered_items
+        .into_iter()
+        .filter_map(|activity| match activity {
+            OutboxActivity::Create { object } => Some(*object),
+            OutboxActivity::Other => None,
+        })
+        .filter(|note| note.is_public() && note.in_reply_to.is_none())
+        .filter_map(|note| note.status_id().map(|id| (id, note)))
+        .filter(|(id, _)| !posts.contains(id) && !posts.is_tombstoned(id))
+        .filter(|(_, note)| !is_cross_post(note, sources))
+        .collect()
+}
+
+enum ImportedNote {
+    Post(MastodonPost),
+    Announcement(Slug, MastodonAnnouncement),
+}
+
+struct ImportNote {
+    export_dir: PathBuf,
+    id: String,
+    note: OutboxNote,
+}
+
+impl ProcessorTask for ImportNote {
+    type Output = Option<ImportedNote>;
+
+    fn run(self, ctx: &ServiceContext) -> Result<Self::Output> {
+        let content = self.note.content.unwrap_or_default();
+
+        if content.contains(SELF_URL) {
+            return Ok(extract_self_blog_slug(&content).map(|slug| {
+                ImportedNote::Announcement(
+                    slug,
+                    MastodonAnnouncement {
+                        id: self.id,
+                        original_uri: self.note.id,
+                        engagement: MastodonPostEngagement::default(),
+                    },
+                )
+            }));
+        }
+
+        info!("Importing Mastodon post {:?}", &self.note.published);
+
+        let tags = extract_tags(&content)
+            .iter()
+            .map(|t| Tag::from_string(t))
+            .collect();
+
+        let updated_at = self.note.updated.unwrap_or(self.note.published);
+
+        let mut post = match self.note.summary.filter(|summary| !summary.is_empty()) {
+            None => MastodonPost::NonSpoiler(MastodonPostNonSpoiler::new(
+                self.id,
+                self.note.id,
+                self.note.published,
+                strip_tags(&content),
+                tags,
+                updated_at,
+            )),
+            Some(spoiler_text) => MastodonPost::Spoiler(MastodonPostSpoiler::new(
+                self.id,
+                self.note.id,
+                self.note.published,
+                strip_tags(&content),
+                spoiler_text,
+                tags,
+                updated_at,
+            )),
+        };
+
+        for attachment in self.note.attachment.iter() {
+            if !attachment
+                .media_type
+                .as_deref()
+                .unwrap_or_default()
+                .starts_with("image/")
+            {
+                continue;
+            }
+
+            let Some(file_name) = Path::new(&attachment.url)
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+            else {
+                warn!("Unable to find attachment file name [{}]", attachment.url);
+                continue;
+            };
+
+            let Some(attachment_id) = attachment_id_from_path(&attachment.url) else {
+                warn!("Unable to find attachment id [{}]", attachment.url);
+                continue;
+            };
+
+            let cdn_file = CdnFile::from_date_and_file_name(post.created_at(), file_name, None);
+
+            let source =
+                FileService::archive(self.export_dir.join(attachment.url.trim_start_matches('/')));
+
+            if attachment.name.is_none() {
+                warn!(
+                    "Mastodon image has no description [{}] [{}]",
+                    post.id(),
+                    attachment_id
+                );
+            }
+
+            let image = MediaService::image_from_file(
+                ctx,
+                &source,
+                &cdn_file,
+                attachment.name.as_deref().unwrap_or_default(),
+                Some(&post.slug().relative_string()),
+                Some(*post.created_at()),
+            )?;
+
+            post.add_attachment(attachment_id, image.into());
+        }
+
+        Ok(Some(ImportedNote::Post(post)))
+    }
+}
+
+// The export dir is relative to the archive dir
+pub fn import_mastodon_export(ctx: &ServiceContext, export_dir: &Path) -> Result<()> {
+    info!("Importing Mastodon export [{:?}]", export_dir);
+
+    let file = FileService::archive(FILE_NAME.into());
+
+    let mut posts: MastodonPosts = file.read_json_or_default()?;
+
+    let outbox: Outbox = FileService::archive(export_dir.join(OUTBOX_FILE_NAME)).read_json()?;
+
+    // Only the text is needed, so archived media isn't processed
+    let sources = load_micro_posts(ctx)?
+        .iter()
+        .map(CrossPostSource::from_micro_post)
+        .chain(
+            load_social_archive_items()?
+                .iter()
+                .map(CrossPostSource::from_archive_item),
+        )
+        .collect::<Vec<CrossPostSource>>();
+
+    let tasks = notes_to_import(outbox, &posts, &sources)
+        .into_iter()
+        .map(|(id, note)| ImportNote {
+            export_dir: export_dir.to_path_buf(),
+            id,
+            note,
+        })
+        .collect();
+
+    let imported = run_processor_tasks(tasks, ctx)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<ImportedNote>>();
+
+    info!("Imported {} Mastodon statuses", imported.len());
+
+    for imported in imported {
+        match imported {
+            ImportedNote::Post(post) => posts.add_imported(post),
+            ImportedNote::Announcement(slug, announcement) => {
+                // Don't replace an announcement the API has given us engagement for
+                if posts.announcement_for(&slug).is_none() {
+                    posts.add_announcement(slug, announcement)
+                }
+            }
+        }
+    }
+
+    file.write_json(&posts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDED_OUTBOX: &str = include_str!("fixtures/mastodon_outbox.json");
+
+    #[test]
+    fn it_should_find_attachment_id_from_path() {
+        assert_eq!(
+            attachment_id_from_path(
+                "/media_attachments/files/113/000/000/000/000/103/original/5d1f2a.jpg"
+            ),
+            Some("113000000000000103".to_string())
+        );
+        assert_eq!(
+            attachment_id_from_path("/media_attachments/files/000/004/217/original/a.png"),
+            Some("4217".to_string())
+        );
+        assert_eq!(attachment_id_from_path("/system/a.png"), None);
+    }
+
+    #[test]
+    fn it_should_only_import_public_top_level_statuses_not_already_archived() {
+        let outbox: Outbox = serde_json::from_str(RECORDED_OUTBOX).unwrap();
+
+        let mut posts = MastodonPosts::default();
+        posts.add(MastodonPost::NonSpoiler(MastodonPostNonSpoiler::new(
+            "113000000000000003".to_string(),
+            "https://social.lol/users/geekyaubergine/statuses/113000000000000003"
+                .parse()
+                .unwrap(),
+            "2024-08-16T12:00:45Z".parse().unwrap(),
+            "<p>Archived</p>".to_string(),
+            vec![],
+            "2024-08-16T12:00:45Z".parse().unwrap(),
+        )));
+        posts.add(MastodonPost::NonSpoiler(MastodonPostNonSpoiler::new(
+            "109000000000000004".to_string(),
+            "https://social.lol/users/geekyaubergine/statuses/109000000000000004"
+                .parse()
+                .unwrap(),
+            "2022-11-21T08:00:00Z".parse().unwrap(),
+            "<p>Deleted</p>".to_string(),
+            vec![],
+            "2022-11-21T08:00:00Z".parse().unwrap(),
+        )));
+        posts.tombstone("109000000000000004", Utc::now());
+
+        let ids = notes_to_import(outbox, &posts, &[])
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            ids,
+            vec![
+                "109000000000000001".to_string(),
+                "109000000000000002".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_clean_up_content_like_the_api() {
+        let outbox: Outbox = serde_json::from_str(RECORDED_OUTBOX).unwrap();
+
+        let (_, note) = notes_to_import(outbox, &MastodonPosts::default(), &[])
+            .into_iter()
+            .find(|(id, _)| id == "109000000000000001")
+            .unwrap();
+
+        let content = note.content.unwrap();
+
+        assert_eq!(extract_tags(&content), vec!["TTRPG".to_string()]);
+        assert_eq!(
+            strip_tags(&content),
+            "<p>Finally finished painting the party for Friday</p>"
+        );
+    }
+
+    #[test]
+    fn it_should_skip_cross_posts_of_micro_posts() {
+        let outbox: Outbox = serde_json::from_str(RECORDED_OUTBOX).unwrap();
+
+        let micro_posts = [
+            MicroPost::new(
+                Slug::new("micros/2022-11-19/gyoza"),
+                "2022-11-19T12:29:00Z".parse().unwrap(),
+                "Tried making [gyoza](https://example.com/gyoza) from scratch, they were not pretty\n\n![Some gyoza](https://cdn.geekyaubergine.com/gyoza.jpg)".to_string(),
+                None,
+                vec![],
+                vec![],
+            ),
+            MicroPost::new(
+                Slug::new("micros/2022-11-01/painting"),
+                "2022-11-01T10:00:00Z".parse().unwrap(),
+                "Finally finished painting the party for Friday".to_string(),
+                None,
+                vec![],
+                vec![],
+            ),
+        ];
+
+        let sources = micro_posts
+            .iter()
+            .map(CrossPostSource::from_micro_post)
+            .collect::<Vec<CrossPostSource>>();
+
+        let ids = notes_to_import(outbox, &MastodonPosts::default(), &sources)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<String>>();
+
+        assert!(!ids.contains(&"109000000000000002".to_string()));
+        assert!(ids.contains(&"109000000000000001".to_string()));
+    }
+
+    #[test]
+    fn it_should_match_cross_posts_on_their_link_before_their_text() {
+        let note = |content: &str| -> OutboxNote {
+            serde_json::from_value(serde_json::json!({
+                "id": "https://social.lol/users/geekyaubergine/statuses/110000000000000001",
+                "published": "2023-01-29T10:00:00Z",
+                "content": content,
+            }))
+            .unwrap()
+        };
+
+        let sources = vec![CrossPostSource {
+            date: "2023-01-29T09:59:00Z".parse().unwrap(),
+            id: "new-post-saving".to_string(),
+            text: "started saving for a new pc".to_string(),
+        }];
+
+        assert!(is_cross_post(
+            &note(
+                r#"<p>Started saving <a href="https://geekyaubergine.com/2023/01/29/new-post-saving.html">geekyaubergine.com/2023/01…</a></p>"#
+            ),
+            &sources
+        ));
+        assert!(is_cross_post(
+            &note("<p>Started saving for a new PC</p>"),
+            &sources
+        ));
+        assert!(!is_cross_post(
+            &note(
+                r#"<p>Started saving for a new PC, <a href="https://example.com/deals">these deals</a> helped</p>"#
+            ),
+            &sources
+        ));
+    }
+}
@@ -4,7 +4,9 @@ use serde::Deserialize;
 use tracing::info;
 
 use crate::{
-    domain::models::{media::Media, micro_post::MicroPost, slug::Slug, tag::Tag},
+    domain::models::{
+        media::Media, micro_post::MicroPost, post_status::PostStatus, slug::Slug, tag::Tag,
+    },
     error::MicroPostError,
     prelude::*,
     processors::tasks::{ProcessorTask, run_processor_tasks},
@@ -24,6 +26,8 @@ pub static MARKDOWN_LINK_REGEX: Lazy<Regex> =
 pub struct MicroPostFrontMatter {
     date: String,
     tags: Vec<String>,
+    #[serde(default)]
+    status: PostStatus,
 }
 
 fn description_from_string(s: &str) -> Option<String> {
@@ -110,7 +114,8 @@ impl ProcessorTask for ProcessFile {
 
         let description = description_from_string(&content);
 
-        let micro_post = MicroPost::new(slug, date, content.to_string(), description, media, tags);
+        let micro_post = MicroPost::new(slug, date, content.to_string(), description, media, tags)
+            .with_status(front_matter.status);
 
         Ok(micro_post)
     }
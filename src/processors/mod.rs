@@ -1,15 +1,40 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use tracing::{info, instrument};
 
 use crate::{
-    domain::models::data::Data,
+    domain::models::{
+        blog_post::BlogPost,
+        content_page::{ContentPage, ContentPages},
+        data::Data,
+        micro_post::MicroPost,
+        post_status::PostStatus,
+        review::imported_review::ImportedReview,
+        shortcode::Shortcodes,
+        site_config::SiteLinks,
+    },
     processors::{
-        about_text::load_about_text, albums::load_albums, blog_posts::load_blog_posts,
-        credits::load_credits, faq::load_faq, games::load_games, lego::load_lego,
-        mastodon::load_mastodon_posts, micro_blog_archive::load_micro_blog_archive,
-        micro_posts::load_micro_posts, now_text::load_now_text, projects::load_projects,
-        referrals::load_referrals, silly_names::load_silly_names,
+        about_text::load_about_text,
+        albums::load_albums,
+        blog_posts::load_blog_posts,
+        content_pages::load_content_pages,
+        credits::load_credits,
+        faq::load_faq,
+        games::load_games,
+        lego::load_lego,
+        letterboxd::load_letterboxd_reviews,
+        mastodon::load_mastodon_posts,
+        micro_posts::load_micro_posts,
+        now_text::load_now_text,
+        projects::load_projects,
+        reading_log::load_reading_log,
+        referrals::load_referrals,
+        scrobbles::load_listening,
+        shortcodes::ShortcodeResolver,
+        silly_names::load_silly_names,
+        social_archives::load_social_archives,
         timeline_events::process_timeline_events,
+        wiki_links::{WikiLinkTargets, find_backlinks},
     },
     services::ServiceContext,
 };
@@ -19,28 +44,62 @@ use crate::prelude::*;
 pub mod about_text;
 pub mod albums;
 pub mod blog_posts;
+pub mod content_pages;
 pub mod credits;
 pub mod faq;
 pub mod games;
 pub mod lego;
+pub mod letterboxd;
 pub mod mastodon;
-pub mod micro_blog_archive;
+pub mod mastodon_export;
 pub mod micro_posts;
 pub mod now_text;
 pub mod projects;
+pub mod reading_log;
 pub mod referrals;
+pub mod scrobbles;
+pub mod shortcodes;
 pub mod silly_names;
+pub mod social_archives;
 pub mod timeline_events;
+pub mod wiki_links;
 
 pub mod tasks;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ResyncTarget {
+    Mastodon,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessOptions {
+    pub full_resync: Vec<ResyncTarget>,
+    pub include_drafts: bool,
+    pub build_time: DateTime<Utc>,
+}
+
+impl ProcessOptions {
+    pub fn should_full_resync(&self, target: ResyncTarget) -> bool {
+        self.full_resync.contains(&target)
+    }
+
+    // Drafts only build when asked for, whatever their date, scheduled posts wait until their
+    // date has passed
+    pub fn should_publish(&self, status: PostStatus, date: &DateTime<Utc>) -> bool {
+        match status {
+            PostStatus::Draft => self.include_drafts,
+            _ => *date <= self.build_time,
+        }
+    }
+}
+
 #[instrument(skip_all)]
-pub fn process_data(ctx: &ServiceContext) -> Result<Data> {
+pub fn process_data(ctx: &ServiceContext, options: &ProcessOptions) -> Result<Data> {
     info!("Processing data | Start");
 
     let start = Utc::now();
 
-    let mastodon = load_mastodon_posts(ctx)?;
+    let mastodon = load_mastodon_posts(ctx, options.should_full_resync(ResyncTarget::Mastodon))?;
 
     let games = load_games(ctx)?;
 
@@ -52,13 +111,50 @@ pub fn process_data(ctx: &ServiceContext) -> Result<Data> {
     let silly_names = load_silly_names()?;
     let blog_posts = load_blog_posts(ctx)?;
     let micro_posts = load_micro_posts(ctx)?;
-    let micro_blog_archive = load_micro_blog_archive(ctx)?;
+
+    let blog_posts = blog_posts
+        .into_iter()
+        .filter(|post| options.should_publish(post.status, &post.date))
+        .collect::<Vec<BlogPost>>();
+    let mut micro_posts = micro_posts
+        .into_iter()
+        .filter(|post| options.should_publish(post.status, &post.date))
+        .collect::<Vec<MicroPost>>();
+    let social_archives = load_social_archives(ctx)?;
+    let letterboxd = load_letterboxd_reviews()?;
+    let reading_log = load_reading_log()?;
+    let listening = load_listening()?;
     let lego = load_lego(ctx)?;
     let albums = load_albums(ctx)?;
     let credits = load_credits()?;
+    let content_pages = load_content_pages()?;
+
+    let resolver = ShortcodeResolver::new(ctx, &albums, &mastodon);
+    let mut shortcodes = Shortcodes::default();
+
+    for post in &micro_posts {
+        resolver.resolve(&post.content, &post.slug, &mut shortcodes)?;
+    }
+    for post in &blog_posts {
+        resolver.resolve(&post.content, &post.slug, &mut shortcodes)?;
+    }
+    for page in content_pages.all() {
+        resolver.resolve(&page.content, &page.slug, &mut shortcodes)?;
+    }
+    let content_pages = content_pages.all().to_vec();
+
+    micro_posts.extend(social_archives);
 
-    let mut micro_posts = micro_posts;
-    micro_posts.extend(micro_blog_archive);
+    let blog_posts = blog_posts
+        .into_iter()
+        .map(|post| match mastodon.announcement_for(&post.slug) {
+            Some(announcement) => {
+                let announcement = announcement.clone();
+                post.with_mastodon_announcement(announcement)
+            }
+            None => post,
+        })
+        .collect::<Vec<BlogPost>>();
 
     info!(
         "Processing data | Load | Done [{}ms]",
@@ -67,8 +163,44 @@ pub fn process_data(ctx: &ServiceContext) -> Result<Data> {
 
     let start = Utc::now();
 
-    let timeline_events =
-        process_timeline_events(ctx, blog_posts, micro_posts, mastodon, &games, &albums);
+    let book_shelves = reading_log.shelves(ctx);
+
+    let mut imported_reviews = letterboxd;
+    imported_reviews.extend(
+        reading_log
+            .review_posts()
+            .into_iter()
+            .map(ImportedReview::Post),
+    );
+
+    let timeline_events = process_timeline_events(
+        ctx,
+        blog_posts,
+        micro_posts,
+        mastodon,
+        imported_reviews,
+        &games,
+        &albums,
+    );
+
+    let wiki_links = WikiLinkTargets::new(&timeline_events, &content_pages);
+
+    let timeline_events = timeline_events
+        .try_map_markdown_content(|slug, content| wiki_links.resolve(content, slug))?;
+
+    let content_pages = ContentPages::from_pages(
+        content_pages
+            .into_iter()
+            .map(|page| {
+                let content = wiki_links.resolve(&page.content, &page.slug)?;
+                Ok(ContentPage { content, ..page })
+            })
+            .collect::<Result<Vec<ContentPage>>>()?,
+    );
+
+    let site_links = SiteLinks::new(content_pages.header_links(), content_pages.footer_links());
+
+    let backlinks = find_backlinks(&timeline_events, &wiki_links);
 
     info!(
         "Processing data | Process Timeline | Events: {} [{}ms]",
@@ -87,6 +219,43 @@ pub fn process_data(ctx: &ServiceContext) -> Result<Data> {
         albums,
         projects,
         timeline_events,
+        backlinks,
         credits,
+        content_pages,
+        book_shelves,
+        listening,
+        shortcodes,
+        site_links,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_publish_drafts_when_asked_and_scheduled_posts_once_due() {
+        let build_time = "2024-06-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let past = "2024-05-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let future = "2024-07-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let options = ProcessOptions {
+            full_resync: vec![],
+            include_drafts: false,
+            build_time,
+        };
+
+        assert!(options.should_publish(PostStatus::Published, &past));
+        assert!(options.should_publish(PostStatus::Unlisted, &past));
+        assert!(!options.should_publish(PostStatus::Published, &future));
+        assert!(!options.should_publish(PostStatus::Draft, &past));
+
+        let options = ProcessOptions {
+            include_drafts: true,
+            ..options
+        };
+
+        assert!(options.should_publish(PostStatus::Draft, &past));
+        assert!(options.should_publish(PostStatus::Draft, &future));
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{
+    domain::models::{
+        book_shelves::{BookShelves, ShelvedBook},
+        micro_post::MicroPost,
+        review::review_ids::ReviewIds,
+        slug::Slug,
+        tag::Tag,
+    },
+    prelude::*,
+    services::{
+        ServiceContext,
+        file_service::{FileService, ReadableFile},
+    },
+    utils::date::parse_date,
+};
+
+const GOODREADS_FILE: &str = "goodreads_library_export.csv";
+const STORYGRAPH_FILE: &str = "storygraph_export.csv";
+
+const BOOK_REVIEW_TAG: &str = "Books";
+
+// Goodreads adds the series to the title, e.g. "Horus Rising (The Horus Heresy, #1)"
+static SERIES_SUFFIX_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\s*\([^()]*#[^()]*\)$").unwrap());
+static LINE_BREAK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shelf {
+    Read,
+    CurrentlyReading,
+    WantToRead,
+    // StoryGraph's did-not-finish, and any custom Goodreads shelves
+    Other,
+}
+
+impl Shelf {
+    fn from_export(shelf: &str) -> Self {
+        match shelf {
+            "read" => Self::Read,
+            "currently-reading" => Self::CurrentlyReading,
+            "to-read" => Self::WantToRead,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GoodreadsRow {
+    #[serde(rename = "Book Id")]
+    id: String,
+    #[serde(rename = "Title")]
+    title: String,
+    #[serde(rename = "Author")]
+    author: String,
+    #[serde(rename = "ISBN")]
+    isbn: Option<String>,
+    #[serde(rename = "ISBN13")]
+    isbn13: Option<String>,
+    // 0 when unrated
+    #[serde(rename = "My Rating")]
+    rating: Option<f32>,
+    #[serde(rename = "Date Read")]
+    date_read: Option<String>,
+    #[serde(rename = "Date Added")]
+    date_added: String,
+    #[serde(rename = "Exclusive Shelf")]
+    shelf: String,
+    #[serde(rename = "My Review")]
+    review: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StoryGraphRow {
+    #[serde(rename = "Title")]
+    title: String,
+    #[serde(rename = "Authors")]
+    authors: String,
+    #[serde(rename = "ISBN/UID")]
+    isbn: String,
+    #[serde(rename = "Read Status")]
+    shelf: String,
+    #[serde(rename = "Date Added")]
+    date_added: String,
+    #[serde(rename = "Last Date Read")]
+    date_read: Option<String>,
+    // Quarter stars
+    #[serde(rename = "Star Rating")]
+    rating: Option<f32>,
+    #[serde(rename = "Review")]
+    review: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ReadingEntry {
+    source: &'static str,
+    id: String,
+    title: String,
+    author: String,
+    isbn: Option<String>,
+    shelf: Shelf,
+    date_added: DateTime<Utc>,
+    date_read: Option<DateTime<Utc>>,
+    score: Option<u8>,
+    review: String,
+}
+
+impl ReadingEntry {
+    fn ids(&self) -> ReviewIds {
+        ReviewIds {
+            isbn: self.isbn.clone(),
+            ..Default::default()
+        }
+    }
+
+    // Written the way book reviews are posted, the ISBN link lets the book service skip searching
+    fn content(&self, score: u8) -> String {
+        let title = match &self.isbn {
+            Some(isbn) => format!("[{}](https://openlibrary.org/isbn/{})", self.title, isbn),
+            None => self.title.clone(),
+        };
+
+        let score = match self.review.is_empty() {
+            true => format!("{}/5", score),
+            false => format!("{}/5 - {}", score, self.review),
+        };
+
+        format!("{} by {}\n\n{}", title, self.author, score)
+    }
+
+    fn to_micro_post(&self) -> Option<MicroPost> {
+        let (Shelf::Read, Some(score)) = (self.shelf, self.score) else {
+            return None;
+        };
+
+        let date = self.date_read.unwrap_or(self.date_added);
+
+        Some(MicroPost::new(
+            Slug::new(&format!(
+                "{}/{}/{}",
+                self.source,
+                date.format("%Y/%m/%d"),
+                self.id
+            )),
+            date,
+            self.content(score),
+            None,
+            vec![],
+            vec![Tag::from_string(BOOK_REVIEW_TAG)],
+        ))
+    }
+}
+
+// Both exports write dates as 2024/05/12
+fn parse_export_date(date: &str) -> Result<DateTime<Utc>> {
+    parse_date(&date.replace('/', "-"))
+}
+
+fn parse_optional_export_date(date: Option<String>) -> Result<Option<DateTime<Utc>>> {
+    date.filter(|date| !date.trim().is_empty())
+        .map(|date| parse_export_date(date.trim()))
+        .transpose()
+}
+
+// Goodreads wraps ISBNs as ="0575084243" so spreadsheets keep the leading zeros
+fn clean_isbn(isbn: Option<String>) -> Option<String> {
+    isbn.map(|isbn| {
+        isbn.chars()
+            .filter(|c| c.is_ascii_digit() || *c == 'X')
+            .collect::<String>()
+    })
+    .filter(|isbn| isbn.len() == 10 || isbn.len() == 13)
+}
+
+fn rating_to_score(rating: Option<f32>) -> Option<u8> {
+    rating
+        .filter(|rating| *rating > 0.0)
+        .map(|rating| (rating.round() as u8).clamp(1, 5))
+}
+
+fn clean_review(review: Option<String>) -> String {
+    review
+        .map(|review| {
+            LINE_BREAK_REGEX
+                .replace_all(review.trim(), "\n")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn goodreads_entry(row: GoodreadsRow) -> Result<ReadingEntry> {
+    Ok(ReadingEntry {
+        source: "goodreads",
+        id: row.id,
+        title: SERIES_SUFFIX_REGEX
+            .replace(row.title.trim(), "")
+            .to_string(),
+        author: row.author.trim().to_string(),
+        isbn: clean_isbn(row.isbn13).or(clean_isbn(row.isbn)),
+        shelf: Shelf::from_export(&row.shelf),
+        date_added: parse_export_date(&row.date_added)?,
+        date_read: parse_optional_export_date(row.date_read)?,
+        score: rating_to_score(row.rating),
+        review: clean_review(row.review),
+    })
+}
+
+fn storygraph_entry(row: StoryGraphRow) -> Result<ReadingEntry> {
+    let author = row.authors.split(',').next().unwrap_or_default();
+
+    Ok(ReadingEntry {
+        source: "storygraph",
+        id: row.isbn.clone(),
+        title: row.title.trim().to_string(),
+        author: author.trim().to_string(),
+        isbn: clean_isbn(Some(row.isbn)),
+        shelf: Shelf::from_export(&row.shelf),
+        date_added: parse_export_date(&row.date_added)?,
+        date_read: parse_optional_export_date(row.date_read)?,
+        score: rating_to_score(row.rating),
+        review: clean_review(row.review),
+    })
+}
+
+fn read_rows<D: for<'de> Deserialize<'de>>(file_name: &str) -> Result<Vec<D>> {
+    let file = FileService::content(file_name.into());
+
+    match file.exists()? {
+        true => file.read_csv(),
+        false => Ok(vec![]),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReadingLog {
+    entries: Vec<ReadingEntry>,
+}
+
+impl ReadingLog {
+    pub fn review_posts(&self) -> Vec<MicroPost> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.to_micro_post())
+            .collect()
+    }
+
+    pub fn shelves(&self, ctx: &ServiceContext) -> BookShelves {
+        let mut shelves = BookShelves::default();
+        // Books moved from Goodreads to StoryGraph are in both exports
+        let mut seen = HashSet::new();
+
+        for entry in &self.entries {
+            let shelf = match entry.shelf {
+                Shelf::CurrentlyReading => &mut shelves.currently_reading,
+                Shelf::WantToRead => &mut shelves.want_to_read,
+                Shelf::Read | Shelf::Other => continue,
+            };
+
+            // Shelves are a nice to have, a failed lookup shouldn't stop the build
+            let book = ctx
+                .books
+                .find_book(ctx, &entry.title, &entry.author, &[], &entry.ids());
+
+            match book {
+                Ok(Some(book)) if seen.insert(book.id) => shelf.push(ShelvedBook {
+                    book,
+                    author: entry.author.clone(),
+                    date_added: entry.date_added,
+                }),
+                Ok(Some(_)) => {}
+                Ok(None) => warn!("Unable to find shelved book [{}]", entry.title),
+                Err(e) => warn!("Unable to look up shelved book [{}]: {e}", entry.title),
+            }
+        }
+
+        shelves
+            .currently_reading
+            .sort_by_key(|shelved| std::cmp::Reverse(shelved.date_added));
+        shelves
+            .want_to_read
+            .sort_by_key(|shelved| std::cmp::Reverse(shelved.date_added));
+
+        shelves
+    }
+}
+
+pub fn load_reading_log() -> Result<ReadingLog> {
+    let mut entries = read_rows(GOODREADS_FILE)?
+        .into_iter()
+        .map(goodreads_entry)
+        .collect::<Result<Vec<ReadingEntry>>>()?;
+
+    entries.extend(
+        read_rows(STORYGRAPH_FILE)?
+            .into_iter()
+            .map(storygraph_entry)
+            .collect::<Result<Vec<ReadingEntry>>>()?,
+    );
+
+    info!("Reading log entries {}", entries.len());
+
+    Ok(ReadingLog { entries })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::models::review::book_review::BookReview;
+
+    use super::*;
+
+    #[test]
+    fn it_should_post_rated_goodreads_books_as_book_reviews() {
+        let entry = goodreads_entry(GoodreadsRow {
+            id: "1234".to_string(),
+            title: "Horus Rising (The Horus Heresy, #1)".to_string(),
+            author: "Dan Abnett".to_string(),
+            isbn: Some("=\"1844162945\"".to_string()),
+            isbn13: Some("=\"9781844162949\"".to_string()),
+            rating: Some(5.0),
+            date_read: Some("2024/05/12".to_string()),
+            date_added: "2024/04/01".to_string(),
+            shelf: "read".to_string(),
+            review: Some("Where it all starts<br/>Again".to_string()),
+        })
+        .unwrap();
+
+        let post = entry.to_micro_post().unwrap();
+
+        assert_eq!(post.slug, Slug::new("/goodreads/2024/05/12/1234"));
+        assert_eq!(post.date, parse_date("2024-05-12").unwrap());
+
+        let review = BookReview::from_content(&post.content).unwrap();
+
+        assert_eq!(review.title, "Horus Rising");
+        assert_eq!(review.author, "Dan Abnett");
+        assert_eq!(review.score, 5);
+        assert_eq!(review.ids.isbn, Some("9781844162949".to_string()));
+
+        let unrated = ReadingEntry {
+            score: rating_to_score(Some(0.0)),
+            ..entry.clone()
+        };
+        let unread = ReadingEntry {
+            shelf: Shelf::from_export("to-read"),
+            ..entry
+        };
+
+        assert!(unrated.to_micro_post().is_none());
+        assert!(unread.to_micro_post().is_none());
+    }
+}
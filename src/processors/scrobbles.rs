@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    domain::models::listening::{ListenCount, Listening, ListeningMonth},
+    error::MusicError,
+    prelude::*,
+    services::file_service::{FileService, ReadableFile},
+};
+
+const LISTENBRAINZ_FILE: &str = "scrobbles/listenbrainz.json";
+const LASTFM_FILE: &str = "scrobbles/lastfm.json";
+
+const TOP_COUNT: usize = 10;
+
+#[derive(Debug, Clone, Deserialize)]
+struct ListenBrainzTrackMetadata {
+    artist_name: String,
+    release_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ListenBrainzListen {
+    listened_at: i64,
+    track_metadata: ListenBrainzTrackMetadata,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LastFmText {
+    #[serde(rename = "#text")]
+    text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LastFmDate {
+    uts: String,
+}
+
+// As returned by user.getRecentTracks, the track playing at export time has no date
+#[derive(Debug, Clone, Deserialize)]
+struct LastFmTrack {
+    artist: LastFmText,
+    album: Option<LastFmText>,
+    date: Option<LastFmDate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LastFmRecentTracks {
+    track: Vec<LastFmTrack>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LastFmPage {
+    recenttracks: LastFmRecentTracks,
+}
+
+// Accepts the bare track list, a single user.getRecentTracks response or an array of its pages
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum LastFmExport {
+    Tracks(Vec<LastFmTrack>),
+    Pages(Vec<LastFmPage>),
+    Page(LastFmPage),
+}
+
+impl LastFmExport {
+    fn into_tracks(self) -> Vec<LastFmTrack> {
+        match self {
+            Self::Tracks(tracks) => tracks,
+            Self::Pages(pages) => pages
+                .into_iter()
+                .flat_map(|page| page.recenttracks.track)
+                .collect(),
+            Self::Page(page) => page.recenttracks.track,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Scrobble {
+    date: DateTime<Utc>,
+    artist: String,
+    album: Option<String>,
+}
+
+trait IntoScrobble {
+    fn into_scrobble(self) -> Option<Scrobble>;
+}
+
+impl IntoScrobble for ListenBrainzListen {
+    fn into_scrobble(self) -> Option<Scrobble> {
+        Some(Scrobble {
+            date: DateTime::from_timestamp(self.listened_at, 0)?,
+            artist: self.track_metadata.artist_name,
+            album: self.track_metadata.release_name,
+        })
+    }
+}
+
+impl IntoScrobble for LastFmTrack {
+    fn into_scrobble(self) -> Option<Scrobble> {
+        let timestamp = self.date?.uts.parse().ok()?;
+
+        Some(Scrobble {
+            date: DateTime::from_timestamp(timestamp, 0)?,
+            artist: self.artist.text,
+            album: self.album.map(|album| album.text),
+        })
+    }
+}
+
+// ListenBrainz exports either a JSON array or one listen per line
+fn parse_listenbrainz(text: &str) -> Result<Vec<ListenBrainzListen>> {
+    let parsed = match text.trim_start().starts_with('[') {
+        true => serde_json::from_str(text),
+        false => text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect(),
+    };
+
+    parsed.map_err(|error| MusicError::unable_to_read_scrobbles(error.to_string()))
+}
+
+fn parse_lastfm(text: &str) -> Result<Vec<LastFmTrack>> {
+    serde_json::from_str::<LastFmExport>(text)
+        .map(LastFmExport::into_tracks)
+        .map_err(|error| MusicError::unable_to_read_scrobbles(error.to_string()))
+}
+
+fn read_scrobbles<D: IntoScrobble>(
+    file_name: &str,
+    parse: fn(&str) -> Result<Vec<D>>,
+) -> Result<Vec<Scrobble>> {
+    let file = FileService::content(file_name.into());
+
+    if !file.exists()? {
+        return Ok(vec![]);
+    }
+
+    Ok(parse(&file.read_text()?)?
+        .into_iter()
+        .filter_map(IntoScrobble::into_scrobble)
+        .collect())
+}
+
+// The same listen is present in both exports when Last.fm scrobbles are imported into ListenBrainz
+fn dedupe(scrobbles: Vec<Scrobble>) -> Vec<Scrobble> {
+    let mut seen = HashSet::new();
+
+    scrobbles
+        .into_iter()
+        .filter(|scrobble| seen.insert((scrobble.date, scrobble.artist.to_lowercase())))
+        .collect()
+}
+
+fn top(counts: HashMap<(String, String), usize>) -> Vec<ListenCount> {
+    let mut counts = counts
+        .into_iter()
+        .map(|((name, artist), listens)| ListenCount {
+            name,
+            artist,
+            listens,
+        })
+        .collect::<Vec<ListenCount>>();
+
+    counts.sort_by(|a, b| b.listens.cmp(&a.listens).then_with(|| a.name.cmp(&b.name)));
+    counts.truncate(TOP_COUNT);
+
+    counts
+}
+
+fn group_by_month(scrobbles: Vec<Scrobble>) -> Listening {
+    let mut by_month: BTreeMap<NaiveDate, Vec<Scrobble>> = BTreeMap::new();
+
+    for scrobble in scrobbles {
+        let date = scrobble.date.date_naive();
+        let month = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap();
+
+        by_month.entry(month).or_default().push(scrobble);
+    }
+
+    let months = by_month
+        .into_iter()
+        .rev()
+        .map(|(month, scrobbles)| {
+            let mut artists = HashMap::new();
+            let mut albums = HashMap::new();
+
+            for scrobble in &scrobbles {
+                *artists
+                    .entry((scrobble.artist.clone(), String::new()))
+                    .or_default() += 1;
+
+                if let Some(album) = scrobble.album.as_ref().filter(|album| !album.is_empty()) {
+                    *albums
+                        .entry((album.clone(), scrobble.artist.clone()))
+                        .or_default() += 1;
+                }
+            }
+
+            ListeningMonth {
+                month,
+                listens: scrobbles.len(),
+                top_artists: top(artists),
+                top_albums: top(albums),
+            }
+        })
+        .collect();
+
+    Listening { months }
+}
+
+pub fn load_listening() -> Result<Listening> {
+    let mut scrobbles = read_scrobbles(LISTENBRAINZ_FILE, parse_listenbrainz)?;
+    scrobbles.extend(read_scrobbles(LASTFM_FILE, parse_lastfm)?);
+
+    let scrobbles = dedupe(scrobbles);
+
+    info!("Scrobbles {}", scrobbles.len());
+
+    Ok(group_by_month(scrobbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_count_top_artists_and_albums_per_month() {
+        let listenbrainz = parse_listenbrainz(
+            r#"{"listened_at": 1714521600, "track_metadata": {"artist_name": "David Bowie", "release_name": "Blackstar"}}
+{"listened_at": 1714608000, "track_metadata": {"artist_name": "David Bowie", "release_name": "Low"}}"#,
+        )
+        .unwrap();
+        let lastfm = parse_lastfm(
+            r##"[
+                {"artist": {"#text": "David Bowie"}, "album": {"#text": "Blackstar"}, "date": {"uts": "1714694400"}},
+                {"artist": {"#text": "Kate Bush"}, "album": {"#text": "Hounds of Love"}, "date": {"uts": "1717200000"}},
+                {"artist": {"#text": "Kate Bush"}, "album": {"#text": "Hounds of Love"}}
+            ]"##,
+        )
+        .unwrap();
+
+        let scrobbles = listenbrainz
+            .into_iter()
+            .filter_map(IntoScrobble::into_scrobble)
+            .chain(lastfm.into_iter().filter_map(IntoScrobble::into_scrobble))
+            .collect();
+
+        let listening = group_by_month(scrobbles);
+
+        assert_eq!(listening.months.len(), 2);
+
+        let may = &listening.months[1];
+
+        assert_eq!(may.month, NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+        assert_eq!(may.listens, 3);
+        assert_eq!(may.top_artists[0].name, "David Bowie");
+        assert_eq!(may.top_artists[0].listens, 3);
+        assert_eq!(may.top_albums[0].name, "Blackstar");
+        assert_eq!(may.top_albums[0].listens, 2);
+    }
+
+    #[test]
+    fn it_should_read_paged_lastfm_exports() {
+        let single = parse_lastfm(
+            r##"{"recenttracks": {"track": [
+                {"artist": {"#text": "Kate Bush"}, "album": {"#text": "Hounds of Love"}, "date": {"uts": "1717200000"}}
+            ], "@attr": {"page": "1"}}}"##,
+        )
+        .unwrap();
+        let pages = parse_lastfm(
+            r##"[
+                {"recenttracks": {"track": [{"artist": {"#text": "Kate Bush"}, "date": {"uts": "1717200000"}}]}},
+                {"recenttracks": {"track": [{"artist": {"#text": "David Bowie"}, "date": {"uts": "1717100000"}}]}}
+            ]"##,
+        )
+        .unwrap();
+
+        assert_eq!(single.len(), 1);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1].artist.text, "David Bowie");
+    }
+
+    #[test]
+    fn it_should_count_a_listen_in_both_exports_once() {
+        let listenbrainz = parse_listenbrainz(
+            r#"{"listened_at": 1717200000, "track_metadata": {"artist_name": "Kate Bush"}}"#,
+        )
+        .unwrap();
+        let lastfm = parse_lastfm(
+            r##"[{"artist": {"#text": "Kate Bush"}, "date": {"uts": "1717200000"}}]"##,
+        )
+        .unwrap();
+
+        let scrobbles = listenbrainz
+            .into_iter()
+            .filter_map(IntoScrobble::into_scrobble)
+            .chain(lastfm.into_iter().filter_map(IntoScrobble::into_scrobble))
+            .collect();
+
+        assert_eq!(dedupe(scrobbles).len(), 1);
+    }
+}
@@ -0,0 +1,191 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+use crate::{
+    domain::models::{
+        albums::Albums,
+        mastodon_post::MastodonPosts,
+        review::review_ids::ReviewIds,
+        shortcode::{Shortcode, ShortcodeCall, Shortcodes, find_shortcodes},
+        slug::Slug,
+    },
+    error::ShortcodeError,
+    prelude::*,
+    services::ServiceContext,
+};
+
+static YOUTUBE_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^[A-Za-z0-9_-]{11}$"#).unwrap());
+
+// Albums and photos can be referenced with or without the leading /albums/
+fn album_slug(target: &str) -> Slug {
+    let target = target.trim_start_matches('/');
+
+    match target.starts_with("albums/") {
+        true => Slug::new(target),
+        false => Slug::new(&format!("albums/{}", target)),
+    }
+}
+
+// Everything that doesn't need looking up remotely
+fn resolve_local_shortcode(
+    call: &ShortcodeCall,
+    slug: &Slug,
+    albums: &Albums,
+    mastodon: &MastodonPosts,
+) -> Result<Shortcode> {
+    let name = call.name.as_str();
+    let arguments = call.arguments.as_slice();
+
+    let unknown_target = |target: &str| ShortcodeError::unknown_target(name, slug, target);
+
+    match (name, arguments) {
+        ("album", [target]) => {
+            let album = albums
+                .find_by_slug(&album_slug(target))
+                .ok_or_else(|| unknown_target(target))?;
+
+            Ok(Shortcode::Album(album.clone()))
+        }
+        ("photo", [target]) => {
+            let slug = album_slug(target);
+
+            let photo = albums
+                .find_all_by_date()
+                .into_iter()
+                .flat_map(|album| album.photos.iter())
+                .find(|photo| photo.slug == slug)
+                .ok_or_else(|| unknown_target(target))?;
+
+            Ok(Shortcode::Photo(photo.clone()))
+        }
+        ("youtube", [id]) if YOUTUBE_ID_REGEX.is_match(id) => Ok(Shortcode::YouTube(id.clone())),
+        ("toot", [target]) => {
+            let url: Url = target.parse().map_err(|_| unknown_target(target))?;
+
+            let own_post = url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .and_then(|id| mastodon.find_by_id(id))
+                .filter(|post| post.original_uri().host_str() == url.host_str());
+
+            Ok(match own_post {
+                Some(post) => Shortcode::OwnToot(post.clone()),
+                None => Shortcode::RemoteToot(url),
+            })
+        }
+        ("album" | "photo" | "youtube" | "toot" | "movie" | "book", _) => {
+            Err(ShortcodeError::invalid_arguments(name, slug, arguments))
+        }
+        _ => Err(ShortcodeError::unknown_shortcode(name, slug)),
+    }
+}
+
+pub struct ShortcodeResolver<'l> {
+    ctx: &'l ServiceContext,
+    albums: &'l Albums,
+    mastodon: &'l MastodonPosts,
+}
+
+impl<'l> ShortcodeResolver<'l> {
+    pub fn new(ctx: &'l ServiceContext, albums: &'l Albums, mastodon: &'l MastodonPosts) -> Self {
+        Self {
+            ctx,
+            albums,
+            mastodon,
+        }
+    }
+
+    fn resolve_shortcode(&self, call: &ShortcodeCall, slug: &Slug) -> Result<Shortcode> {
+        let name = call.name.as_str();
+        let arguments = call.arguments.as_slice();
+
+        match (name, arguments) {
+            ("movie", [title, year]) => {
+                let year = year
+                    .parse::<u16>()
+                    .map_err(|_| ShortcodeError::invalid_arguments(name, slug, arguments))?;
+
+                let movie = self
+                    .ctx
+                    .movies
+                    .find_movie(self.ctx, title, year, &ReviewIds::default())?
+                    .ok_or_else(|| {
+                        ShortcodeError::unknown_target(name, slug, &format!("{} ({})", title, year))
+                    })?;
+
+                Ok(Shortcode::Movie(movie))
+            }
+            ("book", [title, author]) => {
+                let book = self
+                    .ctx
+                    .books
+                    .find_book(self.ctx, title, author, &[], &ReviewIds::default())?
+                    .ok_or_else(|| {
+                        ShortcodeError::unknown_target(
+                            name,
+                            slug,
+                            &format!("{} by {}", title, author),
+                        )
+                    })?;
+
+                Ok(Shortcode::Book(book))
+            }
+            _ => resolve_local_shortcode(call, slug, self.albums, self.mastodon),
+        }
+    }
+
+    // The content is left as it is, the renderer swaps each shortcode for what it resolved to
+    pub fn resolve(&self, content: &str, slug: &Slug, shortcodes: &mut Shortcodes) -> Result<()> {
+        for call in find_shortcodes(content)? {
+            if shortcodes.find(&call).is_none() {
+                let shortcode = self.resolve_shortcode(&call, slug)?;
+                shortcodes.insert(&call, shortcode);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    fn resolve(content: &str) -> Result<Shortcode> {
+        let call = find_shortcodes(content)?.remove(0);
+
+        resolve_local_shortcode(
+            &call,
+            &Slug::new("/blog/shortcodes"),
+            &Albums::default(),
+            &MastodonPosts::default(),
+        )
+    }
+
+    #[test]
+    fn it_should_resolve_shortcodes_and_fail_on_unknown_ones() {
+        assert!(matches!(
+            resolve("{{< youtube dQw4w9WgXcQ >}}"),
+            Ok(Shortcode::YouTube(id)) if id == "dQw4w9WgXcQ"
+        ));
+        assert!(matches!(
+            resolve("{{< toot https://mastodon.social/@someone/112971588894743824 >}}"),
+            Ok(Shortcode::RemoteToot(..))
+        ));
+
+        assert!(matches!(
+            resolve("{{< youtube not-an-id >}}"),
+            Err(Error::ShortcodeError(ShortcodeError::InvalidArguments(..)))
+        ));
+        assert!(matches!(
+            resolve("{{< gallery everything >}}"),
+            Err(Error::ShortcodeError(ShortcodeError::UnknownShortcode(..)))
+        ));
+        assert!(matches!(
+            resolve(r#"{{< album "2019/08/haven-falconry" >}}"#),
+            Err(Error::ShortcodeError(ShortcodeError::UnknownTarget(..)))
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{
+    error::DateParseError,
+    prelude::*,
+    processors::social_archives::{
+        ArchiveItem, ArchiveMedia, SocialArchive, TagRules, is_image_path,
+    },
+    services::file_service::{FileService, ReadableFile},
+};
+
+const ARCHIVE_DIR: &str = "instagram-archive";
+const POSTS_FILE: &str = "your_instagram_activity/content/posts_1.json";
+
+const TAG_RULES: TagRules = TagRules {
+    ignore: &[],
+    rename: &[("warhammer40000", "Warhammer40k")],
+};
+
+// Instagram has no tags of its own, only hashtags in the caption
+static HASHTAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"#(\w+)"#).unwrap());
+
+#[derive(Debug, Clone, Deserialize)]
+struct InstagramMedia {
+    uri: String,
+    creation_timestamp: i64,
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct InstagramPost {
+    media: Vec<InstagramMedia>,
+    // Single image posts only have these on the media
+    title: Option<String>,
+    creation_timestamp: Option<i64>,
+}
+
+// Instagram writes its exports as UTF-8 bytes escaped as if they were Latin-1 characters
+fn fix_encoding(text: &str) -> String {
+    if text.chars().any(|c| c as u32 > 0xFF) {
+        return text.to_string();
+    }
+
+    let bytes = text.chars().map(|c| c as u8).collect::<Vec<u8>>();
+
+    String::from_utf8(bytes).unwrap_or_else(|_| text.to_string())
+}
+
+fn post_to_item(post: InstagramPost) -> Result<Option<ArchiveItem>> {
+    let Some(first) = post.media.first() else {
+        return Ok(None);
+    };
+
+    let timestamp = post.creation_timestamp.unwrap_or(first.creation_timestamp);
+
+    let date: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0)
+        .ok_or_else(|| DateParseError::unable_to_parse_date(timestamp.to_string()))?;
+
+    let caption = fix_encoding(post.title.as_deref().unwrap_or(&first.title));
+
+    let tags = HASHTAG_REGEX
+        .captures_iter(&caption)
+        .map(|capture| capture.get(1).unwrap().as_str().to_string())
+        .collect();
+
+    let media = post
+        .media
+        .iter()
+        .filter(|media| is_image_path(&media.uri))
+        .map(|media| ArchiveMedia::Local {
+            file: FileService::content(format!("{}/{}", ARCHIVE_DIR, media.uri).into()),
+            // Instagram never had alt text worth keeping
+            alt: String::new(),
+        })
+        .collect::<Vec<ArchiveMedia>>();
+
+    if media.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(ArchiveItem {
+        id: timestamp.to_string(),
+        date,
+        content: caption,
+        media,
+        tags,
+    }))
+}
+
+pub struct InstagramArchive;
+
+impl SocialArchive for InstagramArchive {
+    fn name(&self) -> &'static str {
+        "Instagram"
+    }
+
+    fn tag_rules(&self) -> TagRules {
+        TAG_RULES
+    }
+
+    fn load_items(&self) -> Result<Vec<ArchiveItem>> {
+        let file = FileService::content(format!("{}/{}", ARCHIVE_DIR, POSTS_FILE).into());
+
+        if !file.exists()? {
+            return Ok(vec![]);
+        }
+
+        let posts: Vec<InstagramPost> = file.read_json()?;
+
+        let mut items = vec![];
+
+        for post in posts {
+            if let Some(item) = post_to_item(post)? {
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_posts_and_fix_caption_encoding() {
+        let posts: Vec<InstagramPost> = serde_json::from_str(
+            r#"[
+                {
+                    "media": [
+                        {
+                            "uri": "media/posts/202103/158742_n.jpg",
+                            "creation_timestamp": 1614898800,
+                            "title": "Finished the Knight ð\u009f\u008e\u0089 #warhammer40000 #minipainting"
+                        },
+                        {
+                            "uri": "media/posts/202103/158743_n.mp4",
+                            "creation_timestamp": 1614898800,
+                            "title": ""
+                        }
+                    ]
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let item = post_to_item(posts.into_iter().next().unwrap())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(item.id, "1614898800");
+        assert_eq!(
+            item.content,
+            "Finished the Knight 🎉 #warhammer40000 #minipainting"
+        );
+        assert_eq!(
+            item.tags,
+            vec!["warhammer40000".to_string(), "minipainting".to_string()]
+        );
+        assert_eq!(item.media.len(), 1);
+    }
+}
@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use url::Url;
+
+use crate::{
+    config::CONFIG,
+    prelude::*,
+    processors::social_archives::{ArchiveItem, ArchiveMedia, SocialArchive, TagRules},
+    services::file_service::{FileService, ReadableFile},
+};
+
+pub static HTML_IMAGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)<img(((src="(?<src>([^"]+))")|(alt="(?<alt>([^"]+))")|(width="(?<width>([^"]+))")|(height="(?<height>([^"]+))"))|[^>])*>"#).unwrap()
+});
+
+const MICRO_BLOG_FEED_FILE: &str = "micro-blog-archive/feed.json";
+
+const MICRO_BLOG_TAG_RULES: TagRules = TagRules {
+    ignore: &["status", "photography"],
+    rename: &[],
+};
+
+#[derive(Debug, Clone, Deserialize, Hash)]
+struct FeedItem {
+    id: String,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    date_published: DateTime<Utc>,
+    url: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Feed {
+    // version: String,
+    // title: String,
+    items: Vec<FeedItem>,
+}
+
+// Uses the last part of the item's url as its id, e.g. 2022/11/18/some-post.html -> some-post
+fn item_id(item: &FeedItem) -> String {
+    let url = item.url.as_ref().unwrap_or(&item.id);
+
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(url)
+        .replace(".html", "")
+}
+
+fn extract_images_from_html(markup: &str) -> Vec<ArchiveMedia> {
+    HTML_IMAGE_REGEX
+        .captures_iter(markup)
+        .filter_map(|cap| {
+            let src = cap.name("src").map_or("", |m| m.as_str());
+            let alt = cap.name("alt").map_or("", |m| m.as_str());
+
+            let url: Url = src.parse().ok()?;
+
+            Some(ArchiveMedia::Remote {
+                url,
+                alt: alt.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub struct JsonFeedArchive {
+    name: &'static str,
+    feed_file: &'static str,
+    tag_rules: TagRules,
+    // Relative upload paths in the feed, and where they're hosted now
+    uploads_path: Option<(&'static str, String)>,
+}
+
+impl JsonFeedArchive {
+    pub fn micro_blog() -> Self {
+        Self {
+            name: "Micro.blog",
+            feed_file: MICRO_BLOG_FEED_FILE,
+            tag_rules: MICRO_BLOG_TAG_RULES,
+            uploads_path: Some(("uploads/", format!("{}/", CONFIG.cdn_url))),
+        }
+    }
+
+    fn parse_items(&self, feed: Feed) -> Vec<ArchiveItem> {
+        feed.items
+            .into_iter()
+            .map(|item| {
+                let content = item
+                    .content_text
+                    .clone()
+                    .or(item.content_html.clone())
+                    .unwrap_or_default();
+
+                let content = match &self.uploads_path {
+                    Some((from, to)) => content.replace(from, to),
+                    None => content,
+                };
+
+                ArchiveItem {
+                    id: item_id(&item),
+                    date: item.date_published,
+                    media: extract_images_from_html(&content),
+                    content,
+                    tags: item.tags.unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl SocialArchive for JsonFeedArchive {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn tag_rules(&self) -> TagRules {
+        self.tag_rules
+    }
+
+    fn load_items(&self) -> Result<Vec<ArchiveItem>> {
+        let file = FileService::content(self.feed_file.into());
+
+        if !file.exists()? {
+            return Ok(vec![]);
+        }
+
+        Ok(self.parse_items(file.read_json()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_extract_media_from_html() {
+        let markup = r#"Movie friend
+
+<img src="https://cdn.example.com/2022/ced7ff5352.jpg" width="600" height="450" alt="Picture of my tabby cat called Muffin">
+"#;
+
+        let media = extract_images_from_html(markup);
+
+        assert_eq!(media.len(), 1);
+
+        match &media[0] {
+            ArchiveMedia::Remote { url, alt } => {
+                assert_eq!(url.path(), "/2022/ced7ff5352.jpg");
+                assert_eq!(alt, "Picture of my tabby cat called Muffin");
+            }
+            ArchiveMedia::Local { .. } => panic!("Expected remote media"),
+        }
+    }
+
+    #[test]
+    fn it_should_use_last_part_of_url_as_id() {
+        let item = FeedItem {
+            id: "http://geekyaubergine.micro.blog/2023/01/29/new-post-saving.html".to_string(),
+            content_html: None,
+            content_text: Some("New Post".to_string()),
+            date_published: "2023-01-29T10:00:00Z".parse().unwrap(),
+            url: None,
+            tags: None,
+        };
+
+        assert_eq!(item_id(&item), "new-post-saving");
+    }
+}
@@ -0,0 +1,294 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tracing::info;
+use url::Url;
+
+use crate::{
+    domain::models::{media::Media, micro_post::MicroPost, slug::Slug, tag::Tag},
+    prelude::*,
+    processors::{
+        social_archives::{
+            instagram::InstagramArchive, json_feed::JsonFeedArchive, twitter::TwitterArchive,
+        },
+        tasks::{ProcessorTask, run_processor_tasks},
+    },
+    services::{
+        ServiceContext, cdn_service::CdnFile, file_service::ContentFile,
+        media_service::MediaService,
+    },
+};
+
+pub mod instagram;
+pub mod json_feed;
+pub mod twitter;
+
+static MARKDOWN_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\(https?://[^\s]+\)"#).unwrap());
+
+static HTML_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<[^>]*>"#).unwrap());
+
+// Along with the space before it, so removing it doesn't leave a gap
+static HASHTAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"[ \t]*#(\w+)"#).unwrap());
+
+// Posts that link back to the site were only ever announcing something that's already here
+const SELF_URL: &str = "zoeaubert.me";
+
+const IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "webp", "gif"];
+
+#[derive(Debug, Clone)]
+pub enum ArchiveMedia {
+    Remote { url: Url, alt: String },
+    Local { file: ContentFile, alt: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveItem {
+    pub id: String,
+    pub date: DateTime<Utc>,
+    pub content: String,
+    pub media: Vec<ArchiveMedia>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TagRules {
+    // Anything tagged with one of these is skipped entirely
+    pub ignore: &'static [&'static str],
+    pub rename: &'static [(&'static str, &'static str)],
+}
+
+impl TagRules {
+    fn apply(&self, tags: &[String]) -> Option<Vec<Tag>> {
+        if tags
+            .iter()
+            .any(|tag| self.ignore.contains(&tag.to_lowercase().as_str()))
+        {
+            return None;
+        }
+
+        let tags = tags
+            .iter()
+            .map(|tag| {
+                let renamed = self
+                    .rename
+                    .iter()
+                    .find(|(from, _)| from.eq_ignore_ascii_case(tag))
+                    .map(|(_, to)| *to);
+
+                Tag::from_string(renamed.unwrap_or(tag))
+            })
+            .collect();
+
+        Some(tags)
+    }
+
+    // Hashtags that became tags are shown as tags, so every archive takes them out of the text
+    fn remove_hashtags(content: &str, tags: &[String]) -> String {
+        HASHTAG_REGEX
+            .replace_all(content, |capture: &regex::Captures| {
+                match tags.iter().any(|tag| tag.eq_ignore_ascii_case(&capture[1])) {
+                    true => String::new(),
+                    false => capture[0].to_string(),
+                }
+            })
+            .trim()
+            .to_string()
+    }
+}
+
+pub trait SocialArchive {
+    fn name(&self) -> &'static str;
+
+    fn tag_rules(&self) -> TagRules;
+
+    // Archives are optional, so an importer returns nothing if its export isn't there
+    fn load_items(&self) -> Result<Vec<ArchiveItem>>;
+}
+
+pub fn is_image_path(path: &str) -> bool {
+    path.rsplit('.')
+        .next()
+        .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+// Find first line or sentence. Remove markdown links and html tags.
+fn extract_description(content: &str) -> Option<String> {
+    let first_line = content.lines().find(|line| !line.trim().is_empty())?;
+
+    let first_line = first_line.replace("[", "").replace("]", "");
+
+    let first_line = MARKDOWN_LINK_REGEX.replace_all(&first_line, "");
+
+    let first_line = HTML_TAG_REGEX.replace_all(&first_line, "");
+
+    let first_sentence = first_line.split('.').next()?.trim();
+
+    match first_sentence.is_empty() {
+        true => None,
+        false => Some(first_sentence.to_string()),
+    }
+}
+
+fn slug_for_item(item: &ArchiveItem) -> Slug {
+    Slug::new(&format!(
+        "micros/{}/{}",
+        item.date.format("%Y/%m/%d"),
+        item.id
+    ))
+}
+
+struct ProcessItem {
+    item: ArchiveItem,
+    tags: Vec<Tag>,
+}
+
+impl ProcessorTask for ProcessItem {
+    type Output = MicroPost;
+
+    fn run(self, ctx: &ServiceContext) -> Result<Self::Output> {
+        let slug = slug_for_item(&self.item);
+
+        let mut media = vec![];
+
+        for archive_media in self.item.media.iter() {
+            let image = match archive_media {
+                ArchiveMedia::Remote { url, alt } => MediaService::image_from_url(
+                    ctx,
+                    url,
+                    &CdnFile::from_path(url.path()),
+                    alt,
+                    Some(&slug.relative_string()),
+                    Some(self.item.date),
+                )?,
+                ArchiveMedia::Local { file, alt } => {
+                    let file_name = file.as_path_buff();
+                    let file_name = file_name.file_name().unwrap().to_str().unwrap();
+
+                    MediaService::image_from_file(
+                        ctx,
+                        file,
+                        &CdnFile::from_date_and_file_name(&self.item.date, file_name, None),
+                        alt,
+                        Some(&slug.relative_string()),
+                        Some(self.item.date),
+                    )?
+                }
+            };
+
+            media.push(Media::from(&image));
+        }
+
+        let description = extract_description(&self.item.content);
+
+        Ok(MicroPost::new(
+            slug,
+            self.item.date,
+            self.item.content,
+            description,
+            media,
+            self.tags,
+        ))
+    }
+}
+
+fn archives() -> Vec<Box<dyn SocialArchive>> {
+    vec![
+        Box::new(JsonFeedArchive::micro_blog()),
+        Box::new(TwitterArchive),
+        Box::new(InstagramArchive),
+    ]
+}
+
+// Every item that would become a micro post, before any of its media is processed
+fn importable_items() -> Result<Vec<ProcessItem>> {
+    let mut items = vec![];
+
+    for archive in archives() {
+        info!("Processing {} archive", archive.name());
+
+        let tag_rules = archive.tag_rules();
+
+        for item in archive.load_items()? {
+            if item.content.contains(SELF_URL) {
+                continue;
+            }
+
+            if let Some(tags) = tag_rules.apply(&item.tags) {
+                let content = TagRules::remove_hashtags(&item.content, &item.tags);
+
+                items.push(ProcessItem {
+                    item: ArchiveItem { content, ..item },
+                    tags,
+                });
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+pub fn load_social_archive_items() -> Result<Vec<ArchiveItem>> {
+    Ok(importable_items()?
+        .into_iter()
+        .map(|process_item| process_item.item)
+        .collect())
+}
+
+pub fn load_social_archives(ctx: &ServiceContext) -> Result<Vec<MicroPost>> {
+    run_processor_tasks(importable_items()?, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAG_RULES: TagRules = TagRules {
+        ignore: &["status"],
+        rename: &[("wip", "WIPWednesday")],
+    };
+
+    #[test]
+    fn it_should_skip_items_with_ignored_tags() {
+        let tags = vec!["Lego".to_string(), "Status".to_string()];
+
+        assert!(TAG_RULES.apply(&tags).is_none());
+    }
+
+    #[test]
+    fn it_should_rename_mapped_tags() {
+        let tags = vec!["Lego".to_string(), "WIP".to_string()];
+
+        assert_eq!(
+            TAG_RULES.apply(&tags),
+            Some(vec![
+                Tag::from_string("Lego"),
+                Tag::from_string("WIPWednesday")
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_remove_hashtags_that_became_tags() {
+        let tags = vec!["WIPWednesday".to_string(), "minipainting".to_string()];
+
+        assert_eq!(
+            TagRules::remove_hashtags(
+                "Primed #wipwednesday and ready #ToPaint\nhttps://example.com/minis #minipainting",
+                &tags
+            ),
+            "Primed and ready #ToPaint\nhttps://example.com/minis"
+        );
+    }
+
+    #[test]
+    fn it_should_extract_description_from_first_sentence() {
+        let content = "Finished my [Goff Rocker](https://www.games-workshop.com/en-GB/ork-goff-rocker). Pretty pleased with the result\n\n<img src=\"uploads/2022/e09fcfa66a.jpg\">";
+
+        assert_eq!(
+            extract_description(content),
+            Some("Finished my Goff Rocker".to_string())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    error::{DateParseError, JsonError},
+    prelude::*,
+    processors::social_archives::{
+        ArchiveItem, ArchiveMedia, SocialArchive, TagRules, is_image_path,
+    },
+    services::file_service::{FileService, ReadableFile},
+};
+
+const ARCHIVE_DIR: &str = "twitter-archive";
+const TWEETS_FILE: &str = "data/tweets.js";
+const TWEETS_MEDIA_DIR: &str = "data/tweets_media";
+const TWITTER_DATE_FORMAT: &str = "%a %b %d %H:%M:%S %z %Y";
+
+const TAG_RULES: TagRules = TagRules {
+    ignore: &["nowplaying"],
+    rename: &[("wipwednesday", "WIPWednesday")],
+};
+
+#[derive(Debug, Clone, Deserialize)]
+struct TweetHashtag {
+    text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TweetUrl {
+    url: String,
+    expanded_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TweetMedia {
+    url: String,
+    media_url_https: String,
+    #[serde(rename = "type")]
+    media_type: String,
+    ext_alt_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct TweetEntities {
+    #[serde(default)]
+    hashtags: Vec<TweetHashtag>,
+    #[serde(default)]
+    urls: Vec<TweetUrl>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct TweetExtendedEntities {
+    #[serde(default)]
+    media: Vec<TweetMedia>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Tweet {
+    id_str: String,
+    created_at: String,
+    full_text: String,
+    #[serde(default)]
+    entities: TweetEntities,
+    #[serde(default)]
+    extended_entities: TweetExtendedEntities,
+    in_reply_to_status_id_str: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TweetsFileItem {
+    tweet: Tweet,
+}
+
+// tweets.js is a script assigning the array to a global, so strip that back to the JSON
+fn parse_tweets_file(script: &str) -> Result<Vec<Tweet>> {
+    let json = match script.find('[') {
+        Some(start) => &script[start..],
+        None => script,
+    };
+
+    let items: Vec<TweetsFileItem> = serde_json::from_str(json).map_err(JsonError::parse_error)?;
+
+    Ok(items.into_iter().map(|item| item.tweet).collect())
+}
+
+fn tweet_content(tweet: &Tweet) -> String {
+    let mut content = tweet.full_text.clone();
+
+    for url in tweet.entities.urls.iter() {
+        content = content.replace(&url.url, &url.expanded_url);
+    }
+
+    // Media gets its own link in the text, which we render separately
+    for media in tweet.extended_entities.media.iter() {
+        content = content.replace(&media.url, "");
+    }
+
+    html_escape::decode_html_entities(content.trim()).to_string()
+}
+
+fn tweet_to_item(tweet: Tweet) -> Result<ArchiveItem> {
+    let date: DateTime<Utc> = DateTime::parse_from_str(&tweet.created_at, TWITTER_DATE_FORMAT)
+        .map_err(|_| DateParseError::unable_to_parse_date(tweet.created_at.clone()))?
+        .into();
+
+    let media = tweet
+        .extended_entities
+        .media
+        .iter()
+        .filter(|media| media.media_type == "photo" && is_image_path(&media.media_url_https))
+        .map(|media| {
+            let file_name = media.media_url_https.rsplit('/').next().unwrap_or_default();
+
+            ArchiveMedia::Local {
+                file: FileService::content(
+                    format!(
+                        "{}/{}/{}-{}",
+                        ARCHIVE_DIR, TWEETS_MEDIA_DIR, tweet.id_str, file_name
+                    )
+                    .into(),
+                ),
+                alt: media.ext_alt_text.clone().unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(ArchiveItem {
+        id: tweet.id_str.clone(),
+        date,
+        content: tweet_content(&tweet),
+        media,
+        tags: tweet
+            .entities
+            .hashtags
+            .iter()
+            .map(|hashtag| hashtag.text.clone())
+            .collect(),
+    })
+}
+
+pub struct TwitterArchive;
+
+impl SocialArchive for TwitterArchive {
+    fn name(&self) -> &'static str {
+        "Twitter"
+    }
+
+    fn tag_rules(&self) -> TagRules {
+        TAG_RULES
+    }
+
+    fn load_items(&self) -> Result<Vec<ArchiveItem>> {
+        let file = FileService::content(format!("{}/{}", ARCHIVE_DIR, TWEETS_FILE).into());
+
+        if !file.exists()? {
+            return Ok(vec![]);
+        }
+
+        // Replies and retweets only make sense in the context of someone else's post
+        parse_tweets_file(&file.read_text()?)?
+            .into_iter()
+            .filter(|tweet| tweet.in_reply_to_status_id_str.is_none())
+            .filter(|tweet| !tweet.full_text.starts_with("RT @"))
+            .map(tweet_to_item)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWEETS_FILE_CONTENT: &str = r#"window.YTD.tweets.part0 = [
+  {
+    "tweet" : {
+      "id_str" : "1590000000000000001",
+      "created_at" : "Tue Nov 08 18:30:12 +0000 2022",
+      "full_text" : "Primed &amp; ready to paint https://t.co/abc #WIPWednesday https://t.co/pic",
+      "entities" : {
+        "hashtags" : [ { "text" : "WIPWednesday" } ],
+        "urls" : [ { "url" : "https://t.co/abc", "expanded_url" : "https://example.com/minis" } ]
+      },
+      "extended_entities" : {
+        "media" : [
+          {
+            "url" : "https://t.co/pic",
+            "media_url_https" : "https://pbs.twimg.com/media/FhAbCdE.jpg",
+            "type" : "photo",
+            "ext_alt_text" : "Three grey primed miniatures"
+          }
+        ]
+      }
+    }
+  }
+]"#;
+
+    #[test]
+    fn it_should_parse_tweets_into_archive_items() {
+        let tweets = parse_tweets_file(TWEETS_FILE_CONTENT).unwrap();
+
+        let item = tweet_to_item(tweets.into_iter().next().unwrap()).unwrap();
+
+        assert_eq!(item.id, "1590000000000000001");
+        assert_eq!(
+            item.date,
+            "2022-11-08T18:30:12Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            item.content,
+            "Primed & ready to paint https://example.com/minis #WIPWednesday"
+        );
+        assert_eq!(item.tags, vec!["WIPWednesday".to_string()]);
+
+        match &item.media[0] {
+            ArchiveMedia::Local { file, alt } => {
+                assert!(file.to_string().ends_with(
+                    "twitter-archive/data/tweets_media/1590000000000000001-FhAbCdE.jpg"
+                ));
+                assert_eq!(alt, "Three grey primed miniatures");
+            }
+            ArchiveMedia::Remote { .. } => panic!("Expected local media"),
+        }
+    }
+}
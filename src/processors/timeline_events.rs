@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
 use once_cell::sync::Lazy;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tracing::{error, info, instrument};
@@ -10,9 +13,10 @@ use crate::{
         mastodon_post::MastodonPosts,
         micro_post::MicroPost,
         review::{
-            book_review::BookReview, movie_review::MovieReview, review_source::ReviewSource,
-            tv_show_review::TvShowReview,
+            album_review::AlbumReview, book_review::BookReview, imported_review::ImportedReview,
+            movie_review::MovieReview, review_source::ReviewSource, tv_show_review::TvShowReview,
         },
+        slug::Slug,
         tag::Tag,
         timeline_event::{
             TimelineEvent, TimelineEventGameAchievementUnlock, TimelineEventPost,
@@ -25,15 +29,20 @@ use crate::{
 static MOVIE_REVIEW_POST_TAG: Lazy<Tag> = Lazy::new(|| Tag::from_string("Movies"));
 static TV_SHOW_REVIEW_POST_TAG: Lazy<Tag> = Lazy::new(|| Tag::from_string("TV"));
 static BOOK_REVIEW_POST_TAG: Lazy<Tag> = Lazy::new(|| Tag::from_string("Books"));
+static ALBUM_REVIEW_POST_TAG: Lazy<Tag> = Lazy::new(|| Tag::from_string("Music"));
 
 #[instrument(skip_all, fields(source.slug=%source.slug()))]
 fn process_review_source(ctx: &ServiceContext, source: ReviewSource) -> TimelineEvent {
     if source.tags().contains(&BOOK_REVIEW_POST_TAG)
         && let Ok(review) = BookReview::from_content(source.content())
     {
-        let book = ctx
-            .books
-            .find_book(ctx, &review.title, &review.author, source.tags());
+        let book = ctx.books.find_book(
+            ctx,
+            &review.title,
+            &review.author,
+            source.tags(),
+            &review.ids,
+        );
 
         return match book {
             Ok(Some(book)) => TimelineEvent::Review(TimelineEventReview::BookReview {
@@ -54,41 +63,51 @@ fn process_review_source(ctx: &ServiceContext, source: ReviewSource) -> Timeline
     if source.tags().contains(&MOVIE_REVIEW_POST_TAG)
         && let Ok(review) = MovieReview::from_content(source.content())
     {
-        let movie = ctx.movies.find_movie(ctx, &review.title, review.year);
+        return process_movie_review(ctx, review, source);
+    }
+
+    if source.tags().contains(&TV_SHOW_REVIEW_POST_TAG)
+        && let Ok(review) = TvShowReview::from_content(source.content())
+    {
+        let tv_show = ctx.tv_shows.find_tv_show(ctx, &review.title, &review.ids);
 
-        return match movie {
-            Ok(Some(movie)) => TimelineEvent::Review(TimelineEventReview::MovieReview {
+        return match tv_show {
+            Ok(Some(tv_show)) => TimelineEvent::Review(TimelineEventReview::TvShowReview {
                 review,
-                movie,
+                tv_show,
                 source,
             }),
             Ok(None) => source.into(),
             Err(_) => {
                 let slug = source.slug();
                 let title = review.title;
-                let year = review.year;
-                error!("Unable to process movie post [{slug}] [{title} - {year}]");
+                error!("Unable to process movie post [{slug}] [{title}]");
                 source.into()
             }
         };
     }
 
-    if source.tags().contains(&TV_SHOW_REVIEW_POST_TAG)
-        && let Ok(review) = TvShowReview::from_content(source.content())
+    if source.tags().contains(&ALBUM_REVIEW_POST_TAG)
+        && let Ok(review) = AlbumReview::from_content(source.content())
     {
-        let tv_show = ctx.tv_shows.find_tv_show(ctx, &review.title);
+        let album = ctx.music.find_album(
+            ctx,
+            &review.title,
+            &review.artist,
+            review.musicbrainz.as_deref(),
+        );
 
-        return match tv_show {
-            Ok(Some(tv_show)) => TimelineEvent::Review(TimelineEventReview::TvShowReview {
+        return match album {
+            Ok(Some(album)) => TimelineEvent::Review(TimelineEventReview::AlbumReview {
                 review,
-                tv_show,
+                album: Box::new(album),
                 source,
             }),
             Ok(None) => source.into(),
             Err(_) => {
                 let slug = source.slug();
                 let title = review.title;
-                error!("Unable to process movie post [{slug}] [{title}]");
+                error!("Unable to process album post [{slug}] [{title}]");
                 source.into()
             }
         };
@@ -97,6 +116,32 @@ fn process_review_source(ctx: &ServiceContext, source: ReviewSource) -> Timeline
     source.into()
 }
 
+fn process_movie_review(
+    ctx: &ServiceContext,
+    review: MovieReview,
+    source: ReviewSource,
+) -> TimelineEvent {
+    let movie = ctx
+        .movies
+        .find_movie(ctx, &review.title, review.year, &review.ids);
+
+    match movie {
+        Ok(Some(movie)) => TimelineEvent::Review(TimelineEventReview::MovieReview {
+            review,
+            movie,
+            source,
+        }),
+        Ok(None) => source.into(),
+        Err(_) => {
+            let slug = source.slug();
+            let title = review.title;
+            let year = review.year;
+            error!("Unable to process movie post [{slug}] [{title} - {year}]");
+            source.into()
+        }
+    }
+}
+
 fn extract_events_from_blog_posts(
     blog_posts: Vec<BlogPost>,
 ) -> impl Iterator<Item = TimelineEvent> {
@@ -128,6 +173,49 @@ fn extract_events_from_mastodon(
         .collect()
 }
 
+fn review_key(event: &TimelineEvent) -> Option<(Slug, NaiveDate)> {
+    let TimelineEvent::Review(review) = event else {
+        return None;
+    };
+
+    let (work, source) = match review {
+        TimelineEventReview::MovieReview { movie, source, .. } => (movie.slug(), source),
+        TimelineEventReview::TvShowReview {
+            tv_show, source, ..
+        } => (tv_show.slug(), source),
+        TimelineEventReview::BookReview { book, source, .. } => (book.slug(), source),
+        TimelineEventReview::AlbumReview { album, source, .. } => (album.slug(), source),
+    };
+
+    Some((work, source.date().date_naive()))
+}
+
+// Reviews from exports of other sites. Anything already reviewed the same day is the same
+// viewing or reading, and the post on the site wins
+fn extract_events_from_imports(
+    ctx: &ServiceContext,
+    imported_reviews: Vec<ImportedReview>,
+    events: &[TimelineEvent],
+) -> Vec<TimelineEvent> {
+    let mut reviewed = events.iter().filter_map(review_key).collect::<HashSet<_>>();
+
+    let imported = imported_reviews
+        .into_par_iter()
+        .map(|imported| match imported {
+            ImportedReview::Post(post) => process_review_source(ctx, ReviewSource::MicroPost(post)),
+            ImportedReview::Movie { review, post } => {
+                process_movie_review(ctx, review, ReviewSource::MicroPost(post))
+            }
+        })
+        .collect::<Vec<TimelineEvent>>();
+
+    imported
+        .into_iter()
+        // Unresolved works would otherwise show up as bare micro posts
+        .filter(|event| review_key(event).is_some_and(|key| reviewed.insert(key)))
+        .collect()
+}
+
 fn extract_events_from_games(games: &Games) -> Vec<TimelineEvent> {
     let mut events = vec![];
 
@@ -177,6 +265,7 @@ pub fn process_timeline_events(
     blog_posts: Vec<BlogPost>,
     micro_posts: Vec<MicroPost>,
     mastodon_posts: MastodonPosts,
+    imported_reviews: Vec<ImportedReview>,
     games: &Games,
     albums: &Albums,
 ) -> TimelineEvents {
@@ -185,6 +274,10 @@ pub fn process_timeline_events(
     events.extend(extract_events_from_blog_posts(blog_posts));
     events.extend(extract_events_from_micro_posts(ctx, micro_posts));
     events.extend(extract_events_from_mastodon(ctx, mastodon_posts));
+
+    let imported_events = extract_events_from_imports(ctx, imported_reviews, &events);
+    events.extend(imported_events);
+
     events.extend(extract_events_from_games(games));
     events.extend(extract_events_from_albums(albums));
 
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::{
+    domain::models::{
+        backlinks::{Backlink, Backlinks},
+        content_page::ContentPage,
+        review::review_source::ReviewSource,
+        site_config::SITE_CONFIG,
+        slug::Slug,
+        timeline_event::{TimelineEvent, TimelineEventPost, TimelineEventReview, TimelineEvents},
+    },
+    error::WikiLinkError,
+    prelude::*,
+    renderer::formatters::format_markdown::find_links,
+    utils::code_blocks::map_lines_outside_code_blocks,
+};
+
+// [[slug]] or [[slug|label]]
+static WIKI_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\[\[(?P<target>[^\[\]|]+)(?:\|(?P<label>[^\[\]]+))?\]\]"#).unwrap());
+
+fn escape_label(label: &str) -> String {
+    label.replace('[', "\\[").replace(']', "\\]")
+}
+
+// Links to our own pages, either relative or against the site url
+fn internal_slug(url: &str) -> Option<Slug> {
+    let path = url.strip_prefix(&SITE_CONFIG.url).unwrap_or(url);
+
+    if !path.starts_with('/') || path.starts_with("//") {
+        return None;
+    }
+
+    let path = path.split(['#', '?']).next()?;
+
+    Some(Slug::new(path))
+}
+
+fn markdown_content(event: &TimelineEvent) -> Option<&str> {
+    match event {
+        TimelineEvent::Post(TimelineEventPost::BlogPost(post)) => Some(&post.content),
+        TimelineEvent::Post(TimelineEventPost::MicroPost(post)) => Some(&post.content),
+        TimelineEvent::Review(
+            TimelineEventReview::BookReview { source, .. }
+            | TimelineEventReview::MovieReview { source, .. }
+            | TimelineEventReview::TvShowReview { source, .. }
+            | TimelineEventReview::AlbumReview { source, .. },
+        ) => match source {
+            ReviewSource::MicroPost(post) => Some(&post.content),
+            ReviewSource::MastodonPost(_) => None,
+        },
+        _ => None,
+    }
+}
+
+pub struct WikiLinkTargets {
+    titles: HashMap<Slug, String>,
+}
+
+impl WikiLinkTargets {
+    // Static pages are whatever the site config links to, plus content pages
+    pub fn new(events: &TimelineEvents, pages: &[ContentPage]) -> Self {
+        let mut titles = HashMap::new();
+
+        let static_links = SITE_CONFIG
+            .header_links
+            .iter()
+            .map(|link| (&link.url, &link.name))
+            .chain(
+                SITE_CONFIG
+                    .page_links
+                    .iter()
+                    .flat_map(|group| group.links.iter())
+                    .map(|link| (&link.url, &link.name)),
+            )
+            .filter(|(url, _)| url.starts_with('/'));
+
+        for (url, name) in static_links {
+            titles.insert(Slug::new(url), name.clone());
+        }
+
+        for page in pages {
+            titles.insert(page.slug.clone(), page.title.clone());
+        }
+
+        for event in events.all_including_unlisted() {
+            titles
+                .entry(Slug::new(&event.key()))
+                .or_insert_with(|| event.title());
+        }
+
+        Self { titles }
+    }
+
+    pub fn contains(&self, slug: &Slug) -> bool {
+        self.titles.contains_key(slug)
+    }
+
+    // Bare names are assumed to be blog posts
+    fn find(&self, target: &str) -> Option<(&Slug, &String)> {
+        let target = target.trim();
+
+        [Slug::new(target), Slug::new(&format!("blog/{}", target))]
+            .iter()
+            .find_map(|slug| self.titles.get_key_value(slug))
+    }
+
+    pub fn resolve(&self, content: &str, slug: &Slug) -> Result<String> {
+        let mut unresolved = vec![];
+
+        let content = map_lines_outside_code_blocks(content, |line| {
+            let line = WIKI_LINK_REGEX.replace_all(line, |cap: &Captures| {
+                match self.find(&cap["target"]) {
+                    Some((target, title)) => {
+                        let label = cap.name("label").map_or(title.as_str(), |m| m.as_str());
+
+                        format!(
+                            "[{}]({})",
+                            escape_label(label.trim()),
+                            target.relative_string()
+                        )
+                    }
+                    None => {
+                        unresolved.push(cap["target"].trim().to_string());
+                        cap[0].to_string()
+                    }
+                }
+            });
+
+            Ok(line.to_string())
+        })?;
+
+        if !unresolved.is_empty() {
+            return Err(WikiLinkError::unresolved_links(slug, unresolved));
+        }
+
+        Ok(content)
+    }
+}
+
+pub fn find_backlinks(events: &TimelineEvents, targets: &WikiLinkTargets) -> Backlinks {
+    let mut backlinks = Backlinks::default();
+
+    for event in events.all_by_date() {
+        let Some(content) = markdown_content(event) else {
+            continue;
+        };
+
+        let source = Slug::new(&event.key());
+
+        for target in find_links(content)
+            .iter()
+            .filter_map(|url| internal_slug(url))
+        {
+            if target == source || !targets.contains(&target) {
+                continue;
+            }
+
+            backlinks.add(
+                target,
+                Backlink {
+                    slug: source.clone(),
+                    title: event.title(),
+                    date: *event.date(),
+                },
+            );
+        }
+    }
+
+    backlinks
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::domain::models::{blog_post::BlogPost, micro_post::MicroPost};
+
+    fn events() -> TimelineEvents {
+        let post = BlogPost::new(
+            Slug::new("/blog/haven-falconry"),
+            "2019-08-10T10:00:00Z".parse().unwrap(),
+            "A day of falconry".to_string(),
+            String::new(),
+            vec![],
+            String::new(),
+        );
+
+        let micro = MicroPost::new(
+            Slug::new("/micros/2019/08/12/owls"),
+            Utc::now(),
+            "More owls, see [[haven-falconry|the blog post]] and [[/faq]]".to_string(),
+            Some("More owls".to_string()),
+            vec![],
+            vec![],
+        );
+
+        TimelineEvents::from_events(vec![
+            TimelineEvent::Post(TimelineEventPost::BlogPost(Box::new(post))),
+            TimelineEvent::Post(TimelineEventPost::MicroPost(Box::new(micro))),
+        ])
+    }
+
+    #[test]
+    fn it_should_resolve_wiki_links_and_build_backlinks() {
+        let events = events();
+        let targets = WikiLinkTargets::new(&events, &[]);
+
+        let events = events
+            .try_map_markdown_content(|slug, content| targets.resolve(content, slug))
+            .unwrap();
+
+        let micro = events.micro_posts_by_date().next().unwrap();
+
+        assert_eq!(
+            micro.content,
+            "More owls, see [the blog post](/blog/haven-falconry/) and [Faq](/faq/)"
+        );
+
+        let backlinks = find_backlinks(&events, &targets);
+
+        assert_eq!(
+            backlinks.for_slug(&Slug::new("/blog/haven-falconry")),
+            &[Backlink {
+                slug: micro.slug.clone(),
+                title: "More owls".to_string(),
+                date: micro.date,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_should_fail_on_unresolved_wiki_links() {
+        let targets = WikiLinkTargets::new(&events(), &[]);
+
+        assert!(
+            targets
+                .resolve("See [[not-a-post]]", &Slug::new("/blog/test"))
+                .is_err()
+        );
+        assert_eq!(
+            targets
+                .resolve("```\n[[not-a-post]]\n```", &Slug::new("/blog/test"))
+                .unwrap(),
+            "```\n[[not-a-post]]\n```"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use hypertext::prelude::*;
+
+use crate::{
+    domain::models::{
+        content_page::{ContentPage, ContentPages},
+        data::Data,
+        review::reviewed_work::{ReviewedWorkKind, WorkId, reviewed_works},
+        shortcode::{Shortcode, Shortcodes, map_shortcodes},
+        slug::Slug,
+    },
+    prelude::*,
+    renderer::partials::shortcodes::{
+        render_album_shortcode, render_book_shortcode, render_movie_shortcode,
+        render_own_toot_shortcode, render_photo_shortcode, render_remote_toot_shortcode,
+        render_youtube_shortcode,
+    },
+};
+
+// Movies and books link to their page on the site when they've been reviewed
+fn render_shortcode(shortcode: &Shortcode, work_slugs: &HashMap<WorkId, Slug>) -> String {
+    let work_link = |id: WorkId| work_slugs.get(&id).map(|slug| slug.relative_string());
+
+    let html = match shortcode {
+        Shortcode::Album(album) => render_album_shortcode(album).render(),
+        Shortcode::Photo(photo) => render_photo_shortcode(photo).render(),
+        Shortcode::YouTube(id) => render_youtube_shortcode(id).render(),
+        Shortcode::OwnToot(post) => render_own_toot_shortcode(post).render(),
+        Shortcode::RemoteToot(url) => render_remote_toot_shortcode(url).render(),
+        Shortcode::Movie(movie) => {
+            let link = work_link(WorkId::Movie(movie.id)).unwrap_or_else(|| movie.link.to_string());
+
+            render_movie_shortcode(movie, &link).render()
+        }
+        Shortcode::Book(book) => {
+            let link = work_link(WorkId::Book(book.id)).or(book.cover.link_on_click.clone());
+
+            render_book_shortcode(book, link.as_deref()).render()
+        }
+    };
+
+    html.into_inner()
+}
+
+fn expand(
+    content: &str,
+    shortcodes: &Shortcodes,
+    work_slugs: &HashMap<WorkId, Slug>,
+) -> Result<String> {
+    // Anything not resolved while processing, e.g. in imported posts, is left as written
+    map_shortcodes(content, |call| {
+        Ok(match shortcodes.find(call) {
+            Some(shortcode) => render_shortcode(shortcode, work_slugs),
+            None => call.text.clone(),
+        })
+    })
+}
+
+pub fn expand_shortcodes(data: Data) -> Result<Data> {
+    let work_slugs = [ReviewedWorkKind::Movie, ReviewedWorkKind::Book]
+        .into_iter()
+        .flat_map(|kind| reviewed_works(kind, &data.timeline_events))
+        .map(|work| (work.id, work.slug))
+        .collect::<HashMap<WorkId, Slug>>();
+
+    let timeline_events = data
+        .timeline_events
+        .try_map_markdown_content(|_, content| expand(content, &data.shortcodes, &work_slugs))?;
+
+    let content_pages = ContentPages::from_pages(
+        data.content_pages
+            .all()
+            .iter()
+            .map(|page| {
+                let content = expand(&page.content, &data.shortcodes, &work_slugs)?;
+                Ok(ContentPage {
+                    content,
+                    ..page.clone()
+                })
+            })
+            .collect::<Result<Vec<ContentPage>>>()?,
+    );
+
+    Ok(Data {
+        timeline_events,
+        content_pages,
+        ..data
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        image::Image,
+        movie::{Movie, MovieId},
+        shortcode::find_shortcodes,
+    };
+
+    fn movie() -> Movie {
+        Movie {
+            title: "Dune".to_string(),
+            year: 2021,
+            poster: Image {
+                description: "Dune poster".to_string(),
+                ..Image::test_fixture("/movies/dune.jpg", 400, 600)
+            },
+            id: MovieId::tmdb(438631),
+            link: "https://www.themoviedb.org/movie/438631".parse().unwrap(),
+            genres: vec![],
+            runtime: None,
+            directors: vec![],
+            cast: vec![],
+            metadata_version: 0,
+        }
+    }
+
+    #[test]
+    fn it_should_link_movies_to_their_page_and_leave_unresolved_shortcodes() {
+        let content = r#"Watched {{< movie "Dune" 2021 >}} and {{< movie "Dune" 1984 >}}"#;
+
+        let mut shortcodes = Shortcodes::default();
+        shortcodes.insert(
+            &find_shortcodes(content).unwrap()[0],
+            Shortcode::Movie(movie()),
+        );
+
+        let html = expand(content, &shortcodes, &HashMap::new()).unwrap();
+
+        assert!(html.contains(r#"href="https://www.themoviedb.org/movie/438631""#));
+        assert!(html.ends_with(r#"and {{< movie "Dune" 1984 >}}"#));
+
+        let work_slugs = HashMap::from([(
+            WorkId::Movie(MovieId::tmdb(438631)),
+            Slug::new("/interests/movies/dune-2021"),
+        )]);
+
+        let html = expand(content, &shortcodes, &work_slugs).unwrap();
+
+        assert!(html.contains(r#"href="/interests/movies/dune-2021/""#));
+    }
+}
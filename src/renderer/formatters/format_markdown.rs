@@ -1,17 +1,40 @@
-use crate::{error::MarkdownError, prelude::*};
-
-use comrak::{Options, options::ListStyleType};
+use std::{ops::RangeInclusive, path::Path};
+
+use crate::{
+    domain::models::{image::Image, media::Media},
+    error::MarkdownError,
+    prelude::*,
+    renderer::{
+        formatters::markdown_cache::cached_markdown_to_html, partials::media::render_markdown_image,
+    },
+    services::cdn_service::CdnFile,
+};
+
+use comrak::{
+    Anchorizer, Arena, Node, Options,
+    html::collect_text,
+    nodes::{NodeHtmlBlock, NodeValue},
+    options::ListStyleType,
+};
+use hypertext::prelude::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use syntect::{easy::HighlightLines, parsing::SyntaxSet};
+use syntect::{
+    highlighting::ThemeSet,
+    html::{ClassStyle, css_for_theme_with_class_style, line_tokens_to_classed_spans},
+    parsing::{ParseState, Scope, ScopeStack, ScopeStackOp, SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
 use tracing::{error, warn};
+use url::Url;
 
+static HTML_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\sid="([^"]+)""#).unwrap());
 static MEDIA_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\!\[.*?\]\(.*?\)"#).unwrap());
 static MEDIA_HTML_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"<img[^>]*src="(?P<src>[^"]+)"[^>]*alt="(?P<alt>[^"]+)"[^>]*>"#).unwrap()
 });
 
-static OPTIONS: Lazy<Options> = Lazy::new(|| {
+pub static OPTIONS: Lazy<Options> = Lazy::new(|| {
     let mut options = Options::default();
     // Extension
     options.extension.strikethrough = true;
@@ -46,7 +69,45 @@ static OPTIONS: Lazy<Options> = Lazy::new(|| {
     options
 });
 
-static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+// Heading and footnote ids would repeat on pages listing several posts, so they're only added
+// where a single post or page is shown
+pub static SINGLE_OPTIONS: Lazy<Options> = Lazy::new(|| {
+    let mut options = OPTIONS.clone();
+    options.extension.header_ids = Some(String::new());
+    options.extension.footnotes = true;
+
+    options
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkdownLayout {
+    Listed,
+    Single,
+}
+
+impl MarkdownLayout {
+    fn options(&self) -> &'static Options<'static> {
+        match self {
+            Self::Listed => &OPTIONS,
+            Self::Single => &SINGLE_OPTIONS,
+        }
+    }
+}
+
+static PRE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<pre(?P<attributes>[^>]*)>\s*?<code>(?P<body>(?s:(.*?)))(</code>\s*?</pre>)"#)
+        .unwrap()
+});
+static PRE_LANG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\slang="([^"]+)""#).unwrap());
+static PRE_META_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\sdata-meta="([^"]*)""#).unwrap());
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+const LIGHT_THEME: &str = "InspiredGitHub";
+const DARK_THEME: &str = "base16-ocean.dark";
+
+// The defaults don't cover everything we write about (TypeScript, TSX, TOML, Dockerfiles...), so
+// use bat's maintained set of syntaxes
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(two_face::syntax::extra_newlines);
 
 // static ref SYNTAX_HIGHLIGHTER: Highlighter = {
 //     let mut highlighter = Highlighter::new();
@@ -66,35 +127,124 @@ static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines
 
 fn correct_codeblock_language_name_to_extension(s: &str) -> &str {
     match s {
-        "typescript" => "js",
-        "tsx" => "js",
-        "ts" => "js",
+        "typescript" => "ts",
         "javascript" => "js",
+        "jsx" => "js",
         "bash" => "sh",
+        "shell" => "sh",
         "yaml" => "yml",
-        "toml" => "toml",
-        "json" => "json",
-        "html" => "html",
-        "css" => "css",
-        "scss" => "scss",
         "markdown" => "md",
         "rust" => "rs",
         "python" => "py",
         "ruby" => "rb",
-        "java" => "java",
-        "go" => "go",
-        "php" => "php",
-        "sql" => "sql",
-        "xml" => "xml",
-        "dockerfile" => "dockerfile",
-        "plaintext" => "plaintext",
+        "docker" => "dockerfile",
+        "plaintext" => "txt",
         _ => s,
     }
 }
 
+// Everything in the info string after the language, e.g. ```rust {3-5,8} linenos
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CodeBlockOptions {
+    line_numbers: bool,
+    highlighted_lines: Vec<RangeInclusive<usize>>,
+}
+
+impl CodeBlockOptions {
+    fn from_meta(meta: &str) -> Self {
+        let mut options = Self::default();
+
+        for part in meta.split_whitespace() {
+            if part == "linenos" {
+                options.line_numbers = true;
+                continue;
+            }
+
+            let Some(ranges) = part
+                .strip_prefix('{')
+                .and_then(|part| part.strip_suffix('}'))
+            else {
+                warn!("Unknown code block option [{}]", part);
+                continue;
+            };
+
+            for range in ranges.split(',').map(|range| range.trim()) {
+                let parsed = match range.split_once('-') {
+                    Some((start, end)) => start.parse().ok().zip(end.parse().ok()),
+                    None => range.parse().ok().map(|line| (line, line)),
+                };
+
+                match parsed {
+                    Some((start, end)) => options.highlighted_lines.push(start..=end),
+                    None => warn!("Invalid highlighted line range [{}]", range),
+                }
+            }
+        }
+
+        options
+    }
+
+    fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|range| range.contains(&line))
+    }
+}
+
+fn open_scopes(scopes: &[Scope]) -> Result<String> {
+    let ops = scopes
+        .iter()
+        .map(|scope| (0, ScopeStackOp::Push(*scope)))
+        .collect::<Vec<(usize, ScopeStackOp)>>();
+
+    let (html, _) = line_tokens_to_classed_spans("", &ops, CLASS_STYLE, &mut ScopeStack::new())
+        .map_err(MarkdownError::unable_to_highlight_code_block)?;
+
+    Ok(html)
+}
+
+// Each line is wrapped in its own span, so any scopes still open at the end of a line
+// are closed and reopened at the start of the next
+fn highlight_lines(code: &str, syntax: &SyntaxReference) -> Result<Vec<String>> {
+    let mut parse_state = ParseState::new(syntax);
+    let mut scope_stack = ScopeStack::new();
+
+    let mut lines = vec![];
+
+    for line in LinesWithEndings::from(code) {
+        let open = open_scopes(scope_stack.as_slice())?;
+
+        let ops = parse_state
+            .parse_line(line, &SYNTAX_SET)
+            .map_err(MarkdownError::unable_to_highlight_code_block)?;
+
+        let content = line.trim_end_matches(['\r', '\n']);
+
+        let ops = ops
+            .into_iter()
+            .map(|(index, op)| (index.min(content.len()), op))
+            .collect::<Vec<(usize, ScopeStackOp)>>();
+
+        let (html, _) = line_tokens_to_classed_spans(content, &ops, CLASS_STYLE, &mut scope_stack)
+            .map_err(MarkdownError::unable_to_highlight_code_block)?;
+
+        lines.push(format!(
+            "{}{}{}",
+            open,
+            html,
+            "</span>".repeat(scope_stack.len())
+        ));
+    }
+
+    Ok(lines)
+}
+
 fn highlight_code_block_capture(caps: &regex::Captures) -> Result<String> {
-    let original_lang = caps
-        .name("lang")
+    let attributes = caps.name("attributes").map_or("", |m| m.as_str());
+
+    let original_lang = PRE_LANG_REGEX
+        .captures(attributes)
+        .and_then(|caps| caps.get(1))
         .ok_or(MarkdownError::could_not_find_language_for_code_block())?
         .as_str();
     let code = caps
@@ -102,75 +252,272 @@ fn highlight_code_block_capture(caps: &regex::Captures) -> Result<String> {
         .ok_or(MarkdownError::could_not_find_body_for_code_block())?
         .as_str();
 
+    let options = PRE_META_REGEX
+        .captures(attributes)
+        .and_then(|caps| caps.get(1))
+        .map(|meta| CodeBlockOptions::from_meta(meta.as_str()))
+        .unwrap_or_default();
+
     let lang = correct_codeblock_language_name_to_extension(original_lang);
 
     let syntax = SYNTAX_SET
         .find_syntax_by_extension(lang)
+        .or_else(|| SYNTAX_SET.find_syntax_by_token(lang))
         .unwrap_or_else(|| {
             warn!("Syntax not found for {}", lang);
             SYNTAX_SET.find_syntax_plain_text()
         });
 
-    let theme_set = syntect::highlighting::ThemeSet::load_defaults();
+    let lines = highlight_lines(code, syntax)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let number = index + 1;
+
+            let class = match options.is_highlighted(number) {
+                true => "line highlighted",
+                false => "line",
+            };
+
+            format!(
+                "<span class=\"{}\" data-line=\"{}\">{}</span>",
+                class, number, line
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let class = match options.line_numbers {
+        true => "hl-code line-numbers",
+        false => "hl-code",
+    };
 
-    let theme = &theme_set.themes["base16-ocean.dark"];
+    Ok(format!(
+        "<pre class=\"{}\" lang=\"{}\"><code>{}</code></pre>",
+        class,
+        original_lang,
+        lines.join("\n")
+    ))
+}
 
-    let mut highlighter = HighlightLines::new(syntax, theme);
+fn highligh_codeblocks(markdown: &str) -> String {
+    let markdown = html_escape::decode_html_entities(markdown).to_string();
+    PRE_REGEX
+        .replace_all(
+            &markdown,
+            |caps: &regex::Captures| match highlight_code_block_capture(caps) {
+                Ok(highlighted) => highlighted,
+                Err(e) => {
+                    error!("Error highlighting code block: {}", e);
+                    caps[0].to_string()
+                }
+            },
+        )
+        .into_owned()
+}
 
-    let mut lines = vec![];
+fn scope_css(css: &str, scope: &str) -> String {
+    css.lines()
+        .map(|line| match line.starts_with('.') {
+            true => line
+                .split(", ")
+                .map(|selector| format!("{} {}", scope, selector))
+                .collect::<Vec<String>>()
+                .join(", "),
+            false => line.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
 
-    for line in code.lines() {
-        match highlighter.highlight_line(line, &SYNTAX_SET) {
-            Ok(ranges) => {
-                match syntect::html::styled_line_to_highlighted_html(
-                    &ranges,
-                    syntect::html::IncludeBackground::No,
-                ) {
-                    Ok(html) => {
-                        lines.push(html);
-                    }
-                    Err(e) => {
-                        error!("Error highlighting line: {}", e);
-                    }
-                }
+// Themes are switched with the same .dark class on <html> that Tailwind's dark variant uses
+pub fn syntax_highlighting_css() -> Result<String> {
+    let theme_set = ThemeSet::load_defaults();
+
+    let light = css_for_theme_with_class_style(&theme_set.themes[LIGHT_THEME], CLASS_STYLE)
+        .map_err(MarkdownError::unable_to_highlight_code_block)?;
+
+    let dark = css_for_theme_with_class_style(&theme_set.themes[DARK_THEME], CLASS_STYLE)
+        .map_err(MarkdownError::unable_to_highlight_code_block)?;
+
+    Ok(format!(
+        "{}\n{}\n",
+        scope_css(&light, "html:not(.dark)"),
+        scope_css(&dark, ".dark")
+    ))
+}
+
+// Processed images are keyed by the path of the url they were downloaded from
+fn find_image<'m>(media: &'m [Media], url: &str) -> Option<&'m Image> {
+    let url: Url = url.parse().ok()?;
+
+    Path::new(url.path()).extension()?;
+
+    let cdn_file = CdnFile::from_path(url.path());
+
+    media.iter().find_map(|media| match media {
+        Media::Image(image) if image.original.file == cdn_file => Some(image),
+        _ => None,
+    })
+}
+
+fn replace_images_with_media<'a>(root: Node<'a>, media: &[Media]) {
+    let images = root
+        .descendants()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::Image(_)))
+        .collect::<Vec<Node<'a>>>();
+
+    for node in images {
+        let (url, title) = match &node.data.borrow().value {
+            NodeValue::Image(link) => (link.url.clone(), link.title.clone()),
+            _ => continue,
+        };
+
+        let Some(image) = find_image(media, &url) else {
+            continue;
+        };
+
+        let caption = (!title.is_empty()).then_some(title.as_str());
+
+        let html = render_markdown_image(image, caption).render().into_inner();
+
+        // A figure can't live inside a paragraph, so a captioned image on its own replaces it
+        let parent = node
+            .parent()
+            .filter(|parent| matches!(parent.data.borrow().value, NodeValue::Paragraph))
+            .filter(|parent| parent.children().count() == 1);
+
+        match (caption, parent) {
+            (Some(_), Some(parent)) => {
+                node.detach();
+                parent.data.borrow_mut().value = NodeValue::HtmlBlock(NodeHtmlBlock {
+                    block_type: 6,
+                    literal: html,
+                });
             }
-            Err(e) => {
-                error!("Error highlighting line: {}", e);
+            _ => {
+                while let Some(child) = node.first_child() {
+                    child.detach();
+                }
+                node.data.borrow_mut().value = NodeValue::HtmlInline(html);
             }
         }
     }
+}
 
-    let highlighted = lines.join("\n");
+fn markdown_to_html(s: &str, media: &[Media], layout: MarkdownLayout) -> String {
+    cached_markdown_to_html(s, media, layout, || {
+        render_markdown_to_html(s, media, layout.options())
+    })
+}
 
-    Ok(format!(
-        "<pre lang=\"{}\">{}</pre>",
-        original_lang, highlighted
-    ))
+fn render_markdown_to_html(s: &str, media: &[Media], options: &Options) -> String {
+    let arena = Arena::new();
+
+    let root = comrak::parse_document(&arena, s, options);
+
+    replace_images_with_media(root, media);
+
+    let mut html = String::new();
+
+    if let Err(e) = comrak::format_html(root, options, &mut html) {
+        error!("Error rendering markdown: {}", e);
+    }
+
+    highligh_codeblocks(&html)
 }
 
-fn highligh_codeblocks(markdown: &str) -> String {
-    let markdown = html_escape::decode_html_entities(markdown).to_string();
-    let re = Regex::new(
-        r#"<pre lang="(?P<lang>\S+?)">\s*?<code>(?P<body>(?s:(.*?)))(</code>\s*?</pre>)"#,
-    )
-    .unwrap();
-    re.replace_all(
-        &markdown,
-        |caps: &regex::Captures| match highlight_code_block_capture(caps) {
-            Ok(highlighted) => highlighted,
-            Err(e) => {
-                error!("Error highlighting code block: {}", e);
-                markdown.to_string()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableOfContentsEntry {
+    pub level: u8,
+    pub title: String,
+    pub id: String,
+}
+
+// Anchorizes headings in document order, the same as comrak does when rendering, so ids match
+pub fn table_of_contents(markdown: &str) -> Vec<TableOfContentsEntry> {
+    let arena = Arena::new();
+
+    let root = comrak::parse_document(&arena, markdown, &SINGLE_OPTIONS);
+
+    let mut anchorizer = Anchorizer::new();
+
+    root.descendants()
+        .filter_map(|node| match &node.data.borrow().value {
+            NodeValue::Heading(heading) => Some((heading.level, node)),
+            _ => None,
+        })
+        .map(|(level, node)| {
+            let title = collect_text(node);
+
+            TableOfContentsEntry {
+                level,
+                id: anchorizer.anchorize(&title),
+                title,
             }
-        },
-    )
-    .into_owned()
+        })
+        .collect()
 }
 
-fn markdown_to_html(s: &str) -> String {
-    let html = comrak::markdown_to_html(s, &OPTIONS);
+// Returns any #anchor links that don't point at a heading, footnote or element in the document
+pub fn find_broken_anchor_links(markdown: &str) -> Vec<String> {
+    let arena = Arena::new();
 
-    highligh_codeblocks(&html)
+    let root = comrak::parse_document(&arena, markdown, &SINGLE_OPTIONS);
+
+    let mut targets = table_of_contents(markdown)
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect::<Vec<String>>();
+
+    let mut links = vec![];
+
+    for node in root.descendants() {
+        match &node.data.borrow().value {
+            NodeValue::FootnoteDefinition(footnote) => {
+                targets.push(format!("fn-{}", footnote.name));
+            }
+            NodeValue::FootnoteReference(footnote) => {
+                targets.push(format!("fnref-{}", footnote.name));
+            }
+            NodeValue::HtmlBlock(html) => {
+                targets.extend(html_ids(&html.literal));
+            }
+            NodeValue::HtmlInline(html) => {
+                targets.extend(html_ids(html));
+            }
+            NodeValue::Link(link) => {
+                if let Some(anchor) = link.url.strip_prefix('#') {
+                    links.push(anchor.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    links
+        .into_iter()
+        .filter(|anchor| !targets.contains(anchor))
+        .collect()
+}
+
+pub fn find_links(markdown: &str) -> Vec<String> {
+    let arena = Arena::new();
+
+    let root = comrak::parse_document(&arena, markdown, &OPTIONS);
+
+    root.descendants()
+        .filter_map(|node| match &node.data.borrow().value {
+            NodeValue::Link(link) => Some(link.url.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn html_ids(html: &str) -> Vec<String> {
+    HTML_ID_REGEX
+        .captures_iter(html)
+        .map(|capture| capture[1].to_string())
+        .collect()
 }
 
 fn remove_media_from_markdown(markdown: &str) -> String {
@@ -182,12 +529,25 @@ fn remove_media_from_markdown(markdown: &str) -> String {
 pub trait FormatMarkdown {
     fn to_html(&self) -> String;
 
+    fn to_html_with_media(&self, media: &[Media]) -> String;
+
+    // Only for pages showing nothing else, as headings and footnotes get ids
+    fn to_single_html(&self, media: &[Media]) -> String;
+
     fn remove_media(&self) -> String;
 }
 
 impl FormatMarkdown for String {
     fn to_html(&self) -> String {
-        markdown_to_html(self).to_string()
+        markdown_to_html(self, &[], MarkdownLayout::Listed)
+    }
+
+    fn to_html_with_media(&self, media: &[Media]) -> String {
+        markdown_to_html(self, media, MarkdownLayout::Listed)
+    }
+
+    fn to_single_html(&self, media: &[Media]) -> String {
+        markdown_to_html(self, media, MarkdownLayout::Single)
     }
 
     fn remove_media(&self) -> String {
@@ -197,10 +557,134 @@ impl FormatMarkdown for String {
 
 impl FormatMarkdown for &str {
     fn to_html(&self) -> String {
-        markdown_to_html(self).to_string()
+        markdown_to_html(self, &[], MarkdownLayout::Listed)
+    }
+
+    fn to_html_with_media(&self, media: &[Media]) -> String {
+        markdown_to_html(self, media, MarkdownLayout::Listed)
+    }
+
+    fn to_single_html(&self, media: &[Media]) -> String {
+        markdown_to_html(self, media, MarkdownLayout::Single)
     }
 
     fn remove_media(&self) -> String {
         remove_media_from_markdown(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processed_image(description: &str) -> Media {
+        Media::from_image(Image {
+            description: description.to_string(),
+            ..Image::test_fixture("/2024/05/knight.jpg", 4000, 3000)
+        })
+    }
+
+    #[test]
+    fn it_should_render_processed_images_in_place_of_originals() {
+        let media = vec![processed_image("A painted knight")];
+
+        let html = "Look at this ![A painted knight](https://cdn.example.com/2024/05/knight.jpg) I finished"
+            .to_html_with_media(&media);
+
+        assert!(html.contains("knight-large.jpg"));
+        assert!(html.contains(r#"width="2000""#));
+        assert!(html.contains(r#"loading="lazy""#));
+        assert!(html.contains(r#"href="#));
+        assert!(!html.contains("<figure"));
+    }
+
+    #[test]
+    fn it_should_render_titled_images_as_figures() {
+        let media = vec![processed_image("A painted knight")];
+
+        let html =
+            r#"![A painted knight](https://cdn.example.com/2024/05/knight.jpg "Finished at last")"#
+                .to_html_with_media(&media);
+
+        assert!(html.contains(r#"<figure class="markdown-image">"#));
+        assert!(html.contains("<figcaption>Finished at last</figcaption>"));
+        assert!(!html.contains("<p>"));
+    }
+
+    #[test]
+    fn it_should_build_table_of_contents_with_rendered_heading_ids() {
+        let markdown = "## Priming\n\n### The `base` coat\n\n## Priming\n";
+
+        let entries = table_of_contents(markdown);
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.level, entry.id.as_str()))
+                .collect::<Vec<(u8, &str)>>(),
+            vec![(2, "priming"), (3, "the-base-coat"), (2, "priming-1")]
+        );
+
+        let html = markdown.to_single_html(&[]);
+
+        for entry in entries {
+            assert!(html.contains(&format!(r#"id="{}""#, entry.id)));
+        }
+
+        assert!(!markdown.to_html().contains("id="));
+    }
+
+    #[test]
+    fn it_should_find_broken_anchor_links() {
+        let markdown = r#"See [priming](#priming), [the note](#fn-1) and [nowhere](#varnish)[^1]
+
+## Priming
+
+<div id="gallery"></div>
+
+[Gallery](#gallery)
+
+[^1]: Use a rattle can
+"#;
+
+        assert_eq!(
+            find_broken_anchor_links(markdown),
+            vec!["varnish".to_string()]
+        );
+    }
+
+    #[test]
+    fn it_should_leave_unprocessed_images_alone() {
+        let html = "![Elsewhere](https://example.com/other.png)".to_html_with_media(&[]);
+
+        assert!(html.contains(r#"src="https://example.com/other.png""#));
+    }
+
+    #[test]
+    fn it_should_parse_code_block_options_from_info_string() {
+        let options = CodeBlockOptions::from_meta("{3-5,8} linenos");
+
+        assert_eq!(
+            options,
+            CodeBlockOptions {
+                line_numbers: true,
+                highlighted_lines: vec![3..=5, 8..=8],
+            }
+        );
+        assert!(options.is_highlighted(4));
+        assert!(!options.is_highlighted(6));
+    }
+
+    #[test]
+    fn it_should_highlight_extra_syntaxes_with_classes_per_line() {
+        let html = "```ts {2}\n// A comment\nconst answer: number = 42;\n```".to_html();
+
+        assert!(html.contains(r#"<pre class="hl-code" lang="ts">"#));
+        assert!(html.contains(
+            r#"<span class="line" data-line="1"><span class="hl-source hl-ts"><span class="hl-comment"#
+        ));
+        assert!(html.contains(r#"<span class="line highlighted" data-line="2">"#));
+        assert!(html.contains(r#"<span class="hl-storage hl-type hl-ts">const</span>"#));
+        assert!(!html.contains("style="));
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    build_data::BUILD_DATE,
+    domain::models::media::Media,
+    prelude::*,
+    renderer::formatters::format_markdown::{MarkdownLayout, OPTIONS, SINGLE_OPTIONS},
+    services::file_service::{FileService, ReadableFile, WritableFile},
+};
+
+const CACHE_FILE: &str = "markdown_cache.json";
+
+// The output depends on the code as much as the markdown, so anything persisted by a different
+// build is thrown away. The build date changes whenever the site is recompiled
+static BUILD_ID: Lazy<String> = Lazy::new(|| {
+    format!(
+        "{}-{}-{:?}-{:?}",
+        env!("CARGO_PKG_VERSION"),
+        BUILD_DATE,
+        *OPTIONS,
+        *SINGLE_OPTIONS
+    )
+});
+
+// Shared by every render task, so the same post rendered on lists, tags and its own page is
+// only converted once
+static HTML_CACHE: Lazy<DashMap<u64, String>> = Lazy::new(DashMap::new);
+
+// Entries from a previous build, moved into HTML_CACHE when they're used again
+static PERSISTED_CACHE: Lazy<DashMap<u64, String>> = Lazy::new(DashMap::new);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedMarkdownCache {
+    build: String,
+    entries: HashMap<u64, String>,
+}
+
+// Media and layout are part of the key, so each MarkdownMediaOption and layout gets its own entry
+fn cache_key(markdown: &str, media: &[Media], layout: MarkdownLayout) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    BUILD_ID.hash(&mut hasher);
+    markdown.hash(&mut hasher);
+    layout.hash(&mut hasher);
+    media.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+pub fn cached_markdown_to_html<F>(
+    markdown: &str,
+    media: &[Media],
+    layout: MarkdownLayout,
+    to_html: F,
+) -> String
+where
+    F: FnOnce() -> String,
+{
+    let key = cache_key(markdown, media, layout);
+
+    if let Some(html) = HTML_CACHE.get(&key) {
+        return html.clone();
+    }
+
+    let html = match PERSISTED_CACHE.remove(&key) {
+        Some((_, html)) => html,
+        None => to_html(),
+    };
+
+    HTML_CACHE.insert(key, html.clone());
+
+    html
+}
+
+pub fn load_markdown_cache() -> Result<()> {
+    let cache: PersistedMarkdownCache =
+        FileService::cache(CACHE_FILE.into()).read_json_or_default()?;
+
+    if cache.build != *BUILD_ID {
+        return Ok(());
+    }
+
+    info!("Loaded {} cached markdown entries", cache.entries.len());
+
+    for (key, html) in cache.entries {
+        PERSISTED_CACHE.insert(key, html);
+    }
+
+    Ok(())
+}
+
+// Only what this build used, so entries for old versions of content are dropped
+pub fn save_markdown_cache() -> Result<()> {
+    let cache = PersistedMarkdownCache {
+        build: BUILD_ID.clone(),
+        entries: HTML_CACHE
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect(),
+    };
+
+    FileService::cache(CACHE_FILE.into()).write_json(&cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::image::Image;
+
+    #[test]
+    fn it_should_only_render_the_same_markdown_once() {
+        let markdown = "Some *cached* markdown";
+
+        let layout = MarkdownLayout::Listed;
+
+        let html = cached_markdown_to_html(markdown, &[], layout, || "<p>rendered</p>".to_string());
+        let cached = cached_markdown_to_html(markdown, &[], layout, || panic!("Should be cached"));
+
+        assert_eq!(html, cached);
+        assert_ne!(
+            cache_key(markdown, &[], layout),
+            cache_key("Other markdown", &[], layout)
+        );
+        assert_ne!(
+            cache_key(markdown, &[], layout),
+            cache_key(markdown, &[], MarkdownLayout::Single)
+        );
+
+        let media = [Media::from_image(Image::test_fixture(
+            "/2024/05/knight.jpg",
+            4000,
+            3000,
+        ))];
+
+        assert_ne!(
+            cache_key(markdown, &[], layout),
+            cache_key(markdown, &media, layout)
+        );
+    }
+}
@@ -1,4 +1,6 @@
+pub mod expand_shortcodes;
 pub mod format_date;
 pub mod format_markdown;
 pub mod format_number;
 pub mod format_relative_to_absolute_urls;
+pub mod markdown_cache;
@@ -4,9 +4,11 @@ use rayon::iter::{ParallelBridge, ParallelIterator};
 use crate::domain::models::data::Data;
 use crate::prelude::*;
 use crate::renderer::feeds::render_feeds;
+use crate::renderer::pages::album_review_pages_renderers::render_album_review_pages;
 use crate::renderer::pages::albums_pages_renderer::render_albums_pages;
 use crate::renderer::pages::blog_pages_renderers::render_blog_pages;
 use crate::renderer::pages::book_review_pages_renderers::render_book_review_pages;
+use crate::renderer::pages::content_pages_renderer::render_content_pages;
 use crate::renderer::pages::credits_renderer::render_credits_pages;
 use crate::renderer::pages::faq_page_renderer::render_faq_page;
 use crate::renderer::pages::feeds_page_renderer::render_feeds_page;
@@ -23,6 +25,8 @@ use crate::renderer::pages::now_page_renderer::render_now_page;
 use crate::renderer::pages::photo_pages_renderer::render_photo_pages;
 use crate::renderer::pages::project_pages_renderers::render_project_pages;
 use crate::renderer::pages::referrals_page_renderer::render_referrals_page;
+use crate::renderer::pages::review_stats_page_renderer::render_review_stats_page;
+use crate::renderer::pages::reviewed_works_pages_renderers::render_reviewed_works_pages;
 use crate::renderer::pages::support_page_renderer::render_support_page;
 use crate::renderer::pages::tag_pages_renderers::render_tags_pages;
 use crate::renderer::pages::timeline_pages_renderers::render_timeline_pages;
@@ -53,6 +57,9 @@ pub fn render_pages(data: &Data, renderer: &PageRenderer) -> Result<()> {
     render_book_review_pages(data, &mut queue);
     render_movie_review_pages(data, &mut queue);
     render_tv_review_pages(data, &mut queue);
+    render_album_review_pages(data, &mut queue);
+    render_reviewed_works_pages(data, &mut queue);
+    render_review_stats_page(data, &mut queue);
     render_games_pages(data, &mut queue);
     render_lego_pages(data, &mut queue);
     render_now_page(data, &mut queue);
@@ -62,6 +69,7 @@ pub fn render_pages(data: &Data, renderer: &PageRenderer) -> Result<()> {
     render_albums_pages(data, &mut queue);
     render_feeds_page(&mut queue);
     render_credits_pages(data, &mut queue);
+    render_content_pages(data, &mut queue);
     render_404_page(&mut queue);
     render_feeds(data, &mut queue);
 
@@ -0,0 +1,80 @@
+use hypertext::prelude::*;
+
+use crate::domain::models::data::Data;
+use crate::domain::models::music_album::MusicAlbum;
+use crate::domain::models::page::Page;
+use crate::domain::models::review::review_source::ReviewSource;
+use crate::domain::models::structured_data::StructuredData;
+use crate::domain::models::timeline_event::{TimelineEvent, TimelineEventReview};
+use crate::prelude::*;
+use crate::renderer::partials::md::{self, md_single};
+use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::partials::work_metadata::render_album_metadata;
+use crate::renderer::{RenderTask, RenderTasks};
+use crate::services::page_renderer::PageRenderer;
+
+pub fn render_album_review_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
+    data.timeline_events
+        .all_including_unlisted()
+        .filter_map(|event| match event {
+            TimelineEvent::Review(
+                event @ TimelineEventReview::AlbumReview { album, source, .. },
+            ) => Some((event, album, source)),
+            _ => None,
+        })
+        .for_each(|(event, album, source)| {
+            tasks.add(RenderAlbumReviewPageTask {
+                event,
+                album,
+                source,
+            })
+        });
+}
+
+struct RenderAlbumReviewPageTask<'l> {
+    event: &'l TimelineEventReview,
+    album: &'l MusicAlbum,
+    source: &'l ReviewSource,
+}
+
+impl<'l> RenderTask for RenderAlbumReviewPageTask<'l> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let source = self.source;
+
+        let album = self.album;
+
+        let content = maud! {
+            article {
+                (md_single(&source.content(), md::MarkdownMediaOption::NoMedia))
+            }
+            (render_album_metadata(album, source.status().is_listed()))
+            @if source.status().is_listed() {
+                p class="work-link" {
+                    a href=(album.slug().relative_string()) { "All reviews of " (album.title) }
+                }
+            }
+        };
+
+        let structured_data = StructuredData::from(self.event);
+
+        let options = PageOptions::new()
+            .with_main_class("album-review-post-page")
+            .use_date_as_title()
+            .with_image(&album.cover)
+            .with_structured_data(&structured_data);
+
+        let page = Page::new(source.slug().clone(), None, None)
+            .with_date(*source.date())
+            .with_tags(source.tags().clone())
+            .with_status(source.status());
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_post_page(
+            &source.slug(),
+            &rendered,
+            Some(*source.date()),
+            source.status(),
+        )
+    }
+}
@@ -1,5 +1,6 @@
 use crate::domain::models::albums::album_photo::AlbumPhoto;
 use crate::domain::models::data::Data;
+use crate::domain::models::structured_data::StructuredData;
 use crate::prelude::*;
 use crate::renderer::RenderTasks;
 use crate::renderer::partials::date::render_date;
@@ -72,7 +73,7 @@ impl<'l> RenderTask for RenderAlbumsListPageTask<'l> {
 
         let options = PageOptions::new().with_main_class("albums-list-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -102,9 +103,13 @@ impl<'l> RenderTask for RenderAlbumPageTask<'l> {
             (render_photo_grid(&album.photos))
         };
 
-        let options = PageOptions::new().with_main_class("album-page");
+        let structured_data = StructuredData::from(album);
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let options = PageOptions::new()
+            .with_main_class("album-page")
+            .with_structured_data(&structured_data);
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -177,15 +182,19 @@ impl<'l> RenderTask for RenderAlbumPhotoPageTask<'l> {
             }
         };
 
+        let structured_data = StructuredData::from(photo);
+
         let options = PageOptions::new()
             .with_body_class("album-photo-page")
             .with_main_class("album-photo-main")
             .hide_header()
-            .hide_footer();
+            .hide_footer()
+            .with_structured_data(&structured_data);
 
         let rendered = render_page(
             &page,
             &options,
+            renderer.site_links(),
             &content,
             album_photo_controls_scripts(previous, next),
         );
@@ -226,7 +235,7 @@ impl<'l> RenderTask for RenderAllAlbumsPageTask<'l> {
 
         let options = PageOptions::new().with_main_class("all-albums-photos-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
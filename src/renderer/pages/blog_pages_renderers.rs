@@ -1,39 +1,65 @@
 use hypertext::prelude::*;
 
+use crate::domain::models::backlinks::Backlink;
 use crate::domain::models::blog_post::BlogPost;
+use crate::domain::models::blog_series::BlogSeries;
 use crate::domain::models::data::Data;
 use crate::domain::models::page::Page;
+use crate::domain::models::site_config::SITE_CONFIG;
 use crate::domain::models::slug::Slug;
+use crate::domain::models::structured_data::StructuredData;
+use crate::error::BlogPostError;
 use crate::prelude::*;
+use crate::renderer::formatters::format_markdown::{find_broken_anchor_links, table_of_contents};
+use crate::renderer::partials::backlinks::render_backlinks;
 use crate::renderer::partials::date::render_date;
-use crate::renderer::partials::md::{self, md};
-use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::partials::mastodon::render_mastodon_engagement;
+use crate::renderer::partials::md::{self, md_single};
+use crate::renderer::partials::page::{PageOptions, render_page, render_reading};
+use crate::renderer::partials::series::render_series_navigation;
+use crate::renderer::partials::table_of_contents::render_table_of_contents;
 use crate::renderer::partials::tag::render_tags;
 use crate::renderer::{RenderTask, RenderTasks};
 use crate::services::page_renderer::PageRenderer;
 use crate::utils::paginator::{Paginator, PaginatorPage};
 
 const PAGINATION_SIZE: usize = 25;
-const NOTES_BLOG_POST_TO_IGNORE: &str = "MonthlyNotes";
+const HERO_IMAGE_SIZES: &str = "(max-width: 72rem) 100vw, 72rem";
 
 pub fn render_blog_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
+    let series = BlogSeries::from_posts(
+        data.timeline_events.blog_posts_by_date(),
+        &SITE_CONFIG.blog_series,
+    );
+
     data.timeline_events
-        .blog_posts_by_date()
-        .for_each(|post| tasks.add(RenderBlogPostPageTask { post }));
+        .blog_posts_including_unlisted()
+        .for_each(|post| {
+            let series = series.iter().find_map(|series| {
+                series
+                    .position(&post.slug)
+                    .map(|index| (series.clone(), index))
+            });
+
+            tasks.add(RenderBlogPostPageTask {
+                post,
+                backlinks: data.backlinks.for_slug(&post.slug),
+                series,
+            })
+        });
 
     // List pages do additional filtering
     data.timeline_events
         .blog_posts_by_date()
-        .filter(|post| {
-            !post
-                .tags
-                .iter()
-                .any(|t| t.tag().eq(NOTES_BLOG_POST_TO_IGNORE))
-        })
+        .filter(|post| !post.is_hidden_from_blog_list())
         .paginate(PAGINATION_SIZE)
         .for_each(|paginator_page| {
             tasks.add(RenderBlogPostListPaginatedPageTask { paginator_page })
         });
+
+    for series in series {
+        tasks.add(RenderBlogSeriesPageTask { series });
+    }
 }
 
 pub fn blog_post_list_item<'l>(post: &'l BlogPost) -> impl Renderable + 'l {
@@ -45,6 +71,9 @@ pub fn blog_post_list_item<'l>(post: &'l BlogPost) -> impl Renderable + 'l {
                 }
                 (render_date(&post.date))
             }
+            @if let Some(reading) = render_reading(Some(post.word_count), Some(&post.read_time())) {
+                p class="reading" { (reading) }
+            }
             p class="description prose" { (post.description )}
             (render_tags(&post.tags, Some(3)))
         }
@@ -73,38 +102,103 @@ impl<'p> RenderTask for RenderBlogPostListPaginatedPageTask<'p> {
 
         let options = PageOptions::new().with_main_class("blog-list-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
 }
 
+struct RenderBlogSeriesPageTask<'p> {
+    series: BlogSeries<'p>,
+}
+
+impl<'p> RenderTask for RenderBlogSeriesPageTask<'p> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let series = &self.series;
+
+        let page = Page::new(
+            series.slug(),
+            Some(series.config.title.clone()),
+            series.config.description.clone(),
+        );
+
+        let content = maud! {
+            @if let Some(description) = &series.config.description {
+                p class="series-description" { (description) }
+            }
+            ol class="blog-post-list" {
+                @for post in &series.posts {
+                    (blog_post_list_item(post))
+                }
+            }
+        };
+
+        let options = PageOptions::new().with_main_class("blog-list-page");
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        let last_modified = series.posts.iter().map(|post| post.date).max();
+
+        renderer.render_page(&series.slug(), &rendered, last_modified)
+    }
+}
+
 struct RenderBlogPostPageTask<'p> {
     post: &'p BlogPost,
+    backlinks: &'p [Backlink],
+    series: Option<(BlogSeries<'p>, usize)>,
 }
 
 impl<'p> RenderTask for RenderBlogPostPageTask<'p> {
     fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
         let post = self.post;
+
+        let broken_anchors = find_broken_anchor_links(&post.content);
+
+        if !broken_anchors.is_empty() {
+            return Err(BlogPostError::broken_anchor_links(
+                post.slug.to_string(),
+                broken_anchors,
+            ));
+        }
+
+        let table_of_contents =
+            match post.show_table_of_contents(SITE_CONFIG.table_of_contents_min_words) {
+                true => table_of_contents(&post.content),
+                false => vec![],
+            };
+
         let content = maud! {
+            @if let Some(hero) = &post.hero_image {
+                div class="hero" {
+                    (hero.render_responsive(HERO_IMAGE_SIZES))
+                }
+            }
+            @if let Some((series, index)) = &self.series {
+                (render_series_navigation(series, *index))
+            }
+            @if !table_of_contents.is_empty() {
+                (render_table_of_contents(&table_of_contents))
+            }
             article {
-                (md(&post.content, md::MarkdownMediaOption::WithMedia))
+                (md_single(&post.content, md::MarkdownMediaOption::WithProcessedMedia(&post.media)))
+            }
+            (render_backlinks(self.backlinks))
+            @if let Some(announcement) = &post.mastodon_announcement {
+                (render_mastodon_engagement(&announcement.engagement, &announcement.id, &announcement.original_uri))
             }
         };
 
-        let options = PageOptions::new().with_main_class("blog-post-page");
+        let structured_data = StructuredData::from(post);
 
-        let page = Page::new(
-            post.slug.clone(),
-            Some(post.title.clone()),
-            Some(post.description.clone()),
-        )
-        .with_date(post.date)
-        .with_tags(post.tags.clone());
-        // .with_image(post.hero_image); TODO
+        let options = PageOptions::new()
+            .with_main_class("blog-post-page")
+            .with_structured_data(&structured_data);
+
+        let page = post.page();
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
-        renderer.render_page(&post.slug, &rendered, Some(post.date))
+        renderer.render_post_page(&post.slug, &rendered, Some(post.date), post.status)
     }
 }
@@ -0,0 +1,47 @@
+use crate::domain::models::content_page::ContentPage;
+use crate::domain::models::data::Data;
+use crate::domain::models::page::Page;
+use crate::prelude::*;
+use crate::renderer::RenderTask;
+use crate::renderer::RenderTasks;
+use crate::services::page_renderer::PageRenderer;
+use hypertext::prelude::*;
+
+use crate::renderer::partials::md::MarkdownMediaOption;
+use crate::renderer::partials::md::md_single;
+use crate::renderer::partials::page::PageOptions;
+use crate::renderer::partials::page::render_page;
+
+pub fn render_content_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
+    for page in data.content_pages.all() {
+        tasks.add(RenderContentPageTask { page });
+    }
+}
+
+struct RenderContentPageTask<'l> {
+    page: &'l ContentPage,
+}
+
+impl<'l> RenderTask for RenderContentPageTask<'l> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let content_page = self.page;
+
+        let page = Page::new(
+            content_page.slug.clone(),
+            Some(content_page.title.clone()),
+            content_page.description.clone(),
+        );
+
+        let content = maud! {
+            article {
+                (md_single(&content_page.content, MarkdownMediaOption::WithMedia))
+            }
+        };
+
+        let options = PageOptions::new().with_main_class(content_page.layout.main_class());
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&content_page.slug, &rendered, None)
+    }
+}
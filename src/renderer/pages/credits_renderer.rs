@@ -49,7 +49,7 @@ impl<'l> RenderTask for RenderCreditsPageTask<'l> {
 
         let options = PageOptions::new().with_main_class("credits-page");
 
-        let render = render_page(&page, &options, &content, maud! {});
+        let render = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &render, None)
     }
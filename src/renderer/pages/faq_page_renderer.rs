@@ -9,7 +9,7 @@ use crate::services::page_renderer::PageRenderer;
 use hypertext::prelude::*;
 
 use crate::renderer::partials::md::MarkdownMediaOption;
-use crate::renderer::partials::md::md;
+use crate::renderer::partials::md::md_single;
 use crate::renderer::partials::page::PageOptions;
 use crate::renderer::partials::page::render_page;
 
@@ -28,13 +28,13 @@ impl<'l> RenderTask for RenderFaqPageTask<'l> {
 
         let content = maud! {
             article {
-                (md(&self.faq.faq, MarkdownMediaOption::WithMedia))
+                (md_single(&self.faq.faq, MarkdownMediaOption::WithMedia))
             }
         };
 
         let options = PageOptions::new().with_main_class("faq-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -34,7 +34,7 @@ impl RenderTask for RenderFeedsPageTask {
 
         let options = PageOptions::new().with_main_class("feeds-page");
 
-        let render = render_page(&page, &options, &content, maud! {});
+        let render = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &render, None)
     }
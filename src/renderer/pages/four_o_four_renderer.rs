@@ -28,7 +28,7 @@ impl RenderTask for Render404PageTask {
 
         let options = PageOptions::new().with_main_class("feeds-page");
 
-        let render = render_page(&page, &options, &content, maud! {});
+        let render = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &render, None)
     }
@@ -145,7 +145,7 @@ impl<'g> RenderTask for RenderGamesListPageTask<'g> {
 
         let slug = page.slug.clone();
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -212,7 +212,7 @@ impl<'g> RenderTask for RenderGamePageTask<'g> {
 
         let slug = page.slug.clone();
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
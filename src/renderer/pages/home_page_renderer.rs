@@ -5,6 +5,7 @@ use crate::domain::models::data::Data;
 use crate::domain::models::image::Image;
 use crate::domain::models::silly_names::SillyNames;
 use crate::domain::models::slug::Slug;
+use crate::domain::models::structured_data::{Person, StructuredData};
 use crate::domain::models::timeline_event::TimelineEvent;
 use crate::domain::models::timeline_event::TimelineEventPost;
 use crate::domain::models::{blog_post::BlogPost, page::Page};
@@ -175,11 +176,16 @@ impl<'l> RenderTask for RenderHomePageTask<'l> {
             }
         };
 
-        let options = PageOptions::new().with_main_class("home");
+        let structured_data = StructuredData::Person(Person::site_author());
+
+        let options = PageOptions::new()
+            .with_main_class("home")
+            .with_structured_data(&structured_data);
 
         let render = render_page(
             &page,
             &options,
+            renderer.site_links(),
             &content,
             home_page_scripts(&silly_names.silly_names),
         );
@@ -1,5 +1,6 @@
 use crate::domain::models::data::Data;
 use crate::domain::models::image::Image;
+use crate::domain::models::review::review_stats::ReviewStats;
 use crate::domain::models::timeline_event::{TimelineEvent, TimelineEventReview};
 use crate::prelude::*;
 use crate::renderer::{RenderTask, RenderTasks};
@@ -100,12 +101,36 @@ pub fn render_interests_page<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
         .take(ITEM_COUNT)
         .collect::<Vec<InterestElement<'d>>>();
 
+    let music = data
+        .timeline_events
+        .all_by_date()
+        .iter()
+        .filter_map(|event| match event {
+            TimelineEvent::Review(TimelineEventReview::AlbumReview {
+                review,
+                album,
+                source,
+            }) => Some(InterestElement {
+                sub_text: Some(format!("{}/5", review.score)),
+                image: &album.cover,
+                link: source.slug(),
+            }),
+            _ => None,
+        })
+        .take(ITEM_COUNT)
+        .collect::<Vec<InterestElement<'d>>>();
+
+    // The stats page is only rendered when there are reviews to chart
+    let has_stats = !ReviewStats::from_events(&data.timeline_events).is_empty();
+
     tasks.add(RenderInterestsPageTask {
         games,
         lego,
         books,
         movies,
         tv_shows,
+        music,
+        has_stats,
     });
 }
 
@@ -146,6 +171,8 @@ struct RenderInterestsPageTask<'l> {
     books: Vec<InterestElement<'l>>,
     movies: Vec<InterestElement<'l>>,
     tv_shows: Vec<InterestElement<'l>>,
+    music: Vec<InterestElement<'l>>,
+    has_stats: bool,
 }
 
 impl<'l> RenderTask for RenderInterestsPageTask<'l> {
@@ -160,14 +187,20 @@ impl<'l> RenderTask for RenderInterestsPageTask<'l> {
         let content = maud! {
             (render_interest_strip("Games", "All Games", "/interests/games/",  &self.games, "games"))
             (render_interest_strip("Lego", "All Lego", "/interests/lego/",  &self.lego, "lego"))
-            (render_interest_strip("Books", "All Book Reviews", "/tags/books/",  &self.books, "books"))
-            (render_interest_strip("Movies", "All Movie Reviews", "/tags/movies/",  &self.movies, "movies"))
-            (render_interest_strip("TV", "All TV Reviews", "/tags/tv/",  &self.tv_shows, "tv-shows"))
+            (render_interest_strip("Books", "All Book Reviews", "/interests/books/",  &self.books, "books"))
+            (render_interest_strip("Movies", "All Movie Reviews", "/interests/movies/",  &self.movies, "movies"))
+            (render_interest_strip("TV", "All TV Reviews", "/interests/tv/",  &self.tv_shows, "tv-shows"))
+            @if !self.music.is_empty() {
+                (render_interest_strip("Music", "All Album Reviews", "/interests/music/",  &self.music, "music"))
+            }
+            @if self.has_stats {
+                a class="stats-link" href="/interests/stats/" { "Review stats" }
+            }
         };
 
         let options = PageOptions::new().with_main_class("interests-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -86,7 +86,7 @@ impl<'l> RenderTask for RenderLegoPageTask<'l> {
 
         let slug = page.slug.clone();
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
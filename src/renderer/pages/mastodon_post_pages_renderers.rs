@@ -1,10 +1,13 @@
 use hypertext::prelude::*;
 
+use crate::domain::models::backlinks::Backlink;
 use crate::domain::models::data::Data;
 use crate::domain::models::mastodon_post::MastodonPost;
 use crate::domain::models::page::Page;
 use crate::prelude::*;
-use crate::renderer::partials::md::{self, md};
+use crate::renderer::partials::backlinks::render_backlinks;
+use crate::renderer::partials::mastodon::render_mastodon_engagement;
+use crate::renderer::partials::md::{self, md_single};
 use crate::renderer::partials::media::{MediaGripOptions, render_media_grid};
 use crate::renderer::partials::page::{PageOptions, render_page};
 use crate::renderer::{RenderTask, RenderTasks};
@@ -13,12 +16,16 @@ pub fn render_mastodon_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
     data.timeline_events
         .mastodon_posts_by_date()
         .for_each(|post| {
-            tasks.add(RenderMastodonPostPageTask { post });
+            tasks.add(RenderMastodonPostPageTask {
+                post,
+                backlinks: data.backlinks.for_slug(&post.slug()),
+            });
         });
 }
 
 struct RenderMastodonPostPageTask<'p> {
     post: &'p MastodonPost,
+    backlinks: &'p [Backlink],
 }
 
 impl<'p> RenderTask for RenderMastodonPostPageTask<'p> {
@@ -30,7 +37,7 @@ impl<'p> RenderTask for RenderMastodonPostPageTask<'p> {
 
         let content = maud! {
             article {
-                (md(&post.content(), md::MarkdownMediaOption::NoMedia))
+                (md_single(&post.content(), md::MarkdownMediaOption::NoMedia))
                 (render_media_grid(post.media(), &MediaGripOptions::for_post()))
                 p class="original-link" {
                     ("See Original: ")
@@ -38,7 +45,9 @@ impl<'p> RenderTask for RenderMastodonPostPageTask<'p> {
                         (post.original_uri().as_str())
                     }
                 }
+                (render_mastodon_engagement(post.engagement(), post.id(), post.original_uri()))
             }
+            (render_backlinks(self.backlinks))
         };
 
         let options = PageOptions::new()
@@ -49,7 +58,7 @@ impl<'p> RenderTask for RenderMastodonPostPageTask<'p> {
             .with_date(*post.created_at())
             .with_tags(post.tags().clone());
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&post.slug(), &rendered, Some(*post.created_at()))
     }
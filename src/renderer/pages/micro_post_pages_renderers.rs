@@ -1,23 +1,31 @@
 use hypertext::prelude::*;
 
+use crate::domain::models::backlinks::Backlink;
 use crate::domain::models::data::Data;
 use crate::domain::models::micro_post::MicroPost;
 use crate::domain::models::page::Page;
 use crate::prelude::*;
-use crate::renderer::partials::md::{self, md};
+use crate::renderer::partials::backlinks::render_backlinks;
+use crate::renderer::partials::md::{self, md_single};
 use crate::renderer::partials::media::{MediaGripOptions, render_media_grid};
 use crate::renderer::partials::page::{PageOptions, render_page};
 use crate::renderer::{RenderTask, RenderTasks};
 use crate::services::page_renderer::PageRenderer;
 
 pub fn render_micro_post_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
-    data.timeline_events.micro_posts_by_date().for_each(|post| {
-        tasks.add(RenderMicroPostTask { post });
-    })
+    data.timeline_events
+        .micro_posts_including_unlisted()
+        .for_each(|post| {
+            tasks.add(RenderMicroPostTask {
+                post,
+                backlinks: data.backlinks.for_slug(&post.slug),
+            });
+        })
 }
 
 struct RenderMicroPostTask<'p> {
     post: &'p MicroPost,
+    backlinks: &'p [Backlink],
 }
 
 impl<'p> RenderTask for RenderMicroPostTask<'p> {
@@ -26,9 +34,10 @@ impl<'p> RenderTask for RenderMicroPostTask<'p> {
 
         let content = maud! {
             article {
-                (md(&post.content, md::MarkdownMediaOption::NoMedia))
+                (md_single(&post.content, md::MarkdownMediaOption::NoMedia))
                 (render_media_grid(post.media(), &MediaGripOptions::for_post()))
             }
+            (render_backlinks(self.backlinks))
         };
 
         let options = PageOptions::new()
@@ -37,10 +46,11 @@ impl<'p> RenderTask for RenderMicroPostTask<'p> {
 
         let page = Page::new(post.slug.clone(), None, None)
             .with_date(post.date)
-            .with_tags(post.tags.clone());
+            .with_tags(post.tags.clone())
+            .with_status(post.status);
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
-        renderer.render_page(&post.slug, &rendered, Some(post.date))
+        renderer.render_post_page(&post.slug, &rendered, Some(post.date), post.status)
     }
 }
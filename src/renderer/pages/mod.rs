@@ -1,6 +1,8 @@
+pub mod album_review_pages_renderers;
 pub mod albums_pages_renderer;
 pub mod blog_pages_renderers;
 pub mod book_review_pages_renderers;
+pub mod content_pages_renderer;
 pub mod credits_renderer;
 pub mod faq_page_renderer;
 pub mod feeds_page_renderer;
@@ -17,6 +19,8 @@ pub mod now_page_renderer;
 pub mod photo_pages_renderer;
 pub mod project_pages_renderers;
 pub mod referrals_page_renderer;
+pub mod review_stats_page_renderer;
+pub mod reviewed_works_pages_renderers;
 pub mod support_page_renderer;
 pub mod tag_pages_renderers;
 pub mod timeline_pages_renderers;
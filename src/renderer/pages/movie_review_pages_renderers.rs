@@ -4,10 +4,12 @@ use crate::domain::models::data::Data;
 use crate::domain::models::movie::Movie;
 use crate::domain::models::page::Page;
 use crate::domain::models::review::review_source::ReviewSource;
+use crate::domain::models::structured_data::StructuredData;
 use crate::domain::models::timeline_event::{TimelineEvent, TimelineEventReview};
 use crate::prelude::*;
-use crate::renderer::partials::md::{self, md};
+use crate::renderer::partials::md::{self, md_single};
 use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::partials::work_metadata::render_movie_metadata;
 use crate::renderer::{RenderTask, RenderTasks};
 use crate::services::page_renderer::PageRenderer;
 
@@ -15,18 +17,24 @@ use crate::services::page_renderer::PageRenderer;
 
 pub fn render_movie_review_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
     data.timeline_events
-        .all_by_date()
-        .iter()
+        .all_including_unlisted()
         .filter_map(|event| match event {
-            TimelineEvent::Review(TimelineEventReview::MovieReview { movie, source, .. }) => {
-                Some((movie, source))
-            }
+            TimelineEvent::Review(
+                event @ TimelineEventReview::MovieReview { movie, source, .. },
+            ) => Some((event, movie, source)),
             _ => None,
         })
-        .for_each(|(movie, source)| tasks.add(RenderMovieReviewPageTask { movie, source }));
+        .for_each(|(event, movie, source)| {
+            tasks.add(RenderMovieReviewPageTask {
+                event,
+                movie,
+                source,
+            })
+        });
 }
 
 struct RenderMovieReviewPageTask<'l> {
+    event: &'l TimelineEventReview,
     movie: &'l Movie,
     source: &'l ReviewSource,
 }
@@ -35,23 +43,41 @@ impl<'l> RenderTask for RenderMovieReviewPageTask<'l> {
     fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
         let source = self.source;
 
+        let movie = self.movie;
+
+        // Unlisted reviews aren't part of the work's page
         let content = maud! {
             article {
-                (md(&source.content(), md::MarkdownMediaOption::NoMedia))
+                (md_single(&source.content(), md::MarkdownMediaOption::NoMedia))
+            }
+            (render_movie_metadata(movie, source.status().is_listed()))
+            @if source.status().is_listed() {
+                p class="work-link" {
+                    a href=(movie.slug().relative_string()) { "All reviews of " (movie.title) }
+                }
             }
         };
 
+        let structured_data = StructuredData::from(self.event);
+
         let options = PageOptions::new()
             .with_main_class("movie-review-post-page")
             .use_date_as_title()
-            .with_image(&self.movie.poster);
+            .with_image(&movie.poster)
+            .with_structured_data(&structured_data);
 
         let page = Page::new(source.slug().clone(), None, None)
             .with_date(*source.date())
-            .with_tags(source.tags().clone());
+            .with_tags(source.tags().clone())
+            .with_status(source.status());
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
-        renderer.render_page(&source.slug(), &rendered, Some(*source.date()))
+        renderer.render_post_page(
+            &source.slug(),
+            &rendered,
+            Some(*source.date()),
+            source.status(),
+        )
     }
 }
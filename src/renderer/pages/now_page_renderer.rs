@@ -9,7 +9,7 @@ use crate::services::page_renderer::PageRenderer;
 use hypertext::prelude::*;
 
 use crate::renderer::partials::md::MarkdownMediaOption;
-use crate::renderer::partials::md::md;
+use crate::renderer::partials::md::md_single;
 use crate::renderer::partials::page::PageOptions;
 use crate::renderer::partials::page::render_page;
 
@@ -32,13 +32,13 @@ impl<'l> RenderTask for RenderNowPageTask<'l> {
 
         let content = maud! {
             article {
-                (md(&now_text.now_text, MarkdownMediaOption::WithMedia))
+                (md_single(&now_text.now_text, MarkdownMediaOption::WithMedia))
             }
         };
 
         let options = PageOptions::new().with_main_class("now-page");
 
-        let rended = render_page(&page, &options, &content, maud! {});
+        let rended = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rended, None)
     }
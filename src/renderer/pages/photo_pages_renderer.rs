@@ -77,7 +77,7 @@ impl RenderTask for RenderPhotosListPageTask {
 
         let options = PageOptions::new().with_main_class("photos-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
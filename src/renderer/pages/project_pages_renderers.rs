@@ -45,7 +45,7 @@ impl<'p> RenderTask for RenderProjectsPageTask<'p> {
 
         let options = PageOptions::new().with_main_class("projects-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
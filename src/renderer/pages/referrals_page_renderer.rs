@@ -46,7 +46,7 @@ impl<'r> RenderTask for RenderReferralsPageTask<'r> {
 
         let options = PageOptions::new().with_main_class("referrals-page");
 
-        let render = render_page(&page, &options, &content, maud! {});
+        let render = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &render, None)
     }
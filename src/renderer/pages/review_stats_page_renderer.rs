@@ -0,0 +1,166 @@
+use hypertext::prelude::*;
+
+use crate::domain::models::data::Data;
+use crate::domain::models::page::Page;
+use crate::domain::models::review::review_stats::{AverageScore, ReviewStats};
+use crate::domain::models::slug::Slug;
+use crate::prelude::*;
+use crate::renderer::partials::bar_chart::{Bar, BarChart, BarChartLayout, render_bar_chart};
+use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::{RenderTask, RenderTasks};
+use crate::services::page_renderer::PageRenderer;
+
+pub fn render_review_stats_page<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
+    let stats = ReviewStats::from_events(&data.timeline_events);
+
+    if stats.is_empty() {
+        return;
+    }
+
+    tasks.add(RenderReviewStatsPageTask { stats });
+}
+
+fn average_bars(averages: &[AverageScore]) -> Vec<Bar> {
+    averages
+        .iter()
+        .map(|average| Bar {
+            label: average.label.clone(),
+            value: average.average,
+            value_text: format!("{:.1} ({})", average.average, average.reviews),
+        })
+        .collect()
+}
+
+fn charts(stats: &ReviewStats) -> Vec<BarChart> {
+    let mut charts = stats
+        .score_distributions
+        .iter()
+        .map(|distribution| {
+            let title = distribution.kind.title();
+
+            BarChart {
+                id: format!("scores-{}", title.to_lowercase().replace(' ', "-")),
+                title: format!("{} by score", title),
+                description: format!(
+                    "The number of reviews with each score out of 5, for {}",
+                    title
+                ),
+                label_heading: "Score".to_string(),
+                value_heading: "Reviews".to_string(),
+                layout: BarChartLayout::Columns,
+                bars: distribution
+                    .counts
+                    .iter()
+                    .enumerate()
+                    .map(|(score, count)| Bar::count(format!("{}/5", score), *count))
+                    .collect(),
+            }
+        })
+        .collect::<Vec<BarChart>>();
+
+    charts.push(BarChart {
+        id: "per-year".to_string(),
+        title: "Reviews per year".to_string(),
+        description: "How many reviews were written each year".to_string(),
+        label_heading: "Year".to_string(),
+        value_heading: "Reviews".to_string(),
+        layout: BarChartLayout::Columns,
+        bars: stats
+            .per_year
+            .iter()
+            .map(|(year, count)| Bar::count(year.to_string(), *count))
+            .collect(),
+    });
+
+    charts.push(BarChart {
+        id: "per-month".to_string(),
+        title: "Reviews per month".to_string(),
+        description: "How many reviews were written each month, over the last two years"
+            .to_string(),
+        label_heading: "Month".to_string(),
+        value_heading: "Reviews".to_string(),
+        layout: BarChartLayout::Columns,
+        bars: stats
+            .per_month
+            .iter()
+            .map(|(month, count)| Bar::count(month.format("%b %Y").to_string(), *count))
+            .collect(),
+    });
+
+    charts.push(BarChart {
+        id: "by-decade".to_string(),
+        title: "Average score by decade".to_string(),
+        description: "The average score out of 5 for works released in each decade, with the number of reviews".to_string(),
+        label_heading: "Decade".to_string(),
+        value_heading: "Average score (reviews)".to_string(),
+        layout: BarChartLayout::Rows,
+        bars: average_bars(&stats.by_decade),
+    });
+
+    charts.push(BarChart {
+        id: "by-genre".to_string(),
+        title: "Average score by genre".to_string(),
+        description:
+            "The average score out of 5 for the most reviewed genres, with the number of reviews"
+                .to_string(),
+        label_heading: "Genre".to_string(),
+        value_heading: "Average score (reviews)".to_string(),
+        layout: BarChartLayout::Rows,
+        bars: average_bars(&stats.by_genre),
+    });
+
+    charts
+}
+
+struct RenderReviewStatsPageTask {
+    stats: ReviewStats,
+}
+
+impl RenderTask for RenderReviewStatsPageTask {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let page = Page::new(
+            Slug::new("/interests/stats"),
+            Some("Review Stats".to_string()),
+            Some(format!(
+                "Charts from {} reviews of movies, TV, books and albums",
+                self.stats.total
+            )),
+        );
+        let slug = page.slug.clone();
+
+        let charts = charts(&self.stats);
+        let stats = &self.stats;
+
+        let content = maud! {
+            p { "From " (stats.total) " reviews." }
+            section class="review-streaks" {
+                h2 { "Longest streaks" }
+                ul {
+                    @if let Some(streak) = &stats.longest_streak {
+                        li {
+                            "Reviewing anything: " (streak.days()) " days, "
+                            (streak.start.format("%-d %B %Y").to_string()) " to "
+                            (streak.end.format("%-d %B %Y").to_string())
+                        }
+                    }
+                    @for (kind, streak) in &stats.longest_streaks {
+                        li {
+                            (kind.title()) ": " (streak.days()) " days, "
+                            (streak.start.format("%-d %B %Y").to_string()) " to "
+                            (streak.end.format("%-d %B %Y").to_string())
+                        }
+                    }
+                }
+            }
+            @for chart in &charts {
+                (render_bar_chart(chart))
+            }
+        };
+
+        let options = PageOptions::new().with_main_class("review-stats-page");
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&slug, &rendered, None)
+    }
+}
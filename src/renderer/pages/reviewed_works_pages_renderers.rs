@@ -0,0 +1,556 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use hypertext::prelude::*;
+use tracing::warn;
+
+use crate::domain::models::book_shelves::{BookShelves, ShelvedBook};
+use crate::domain::models::data::Data;
+use crate::domain::models::listening::{ListenCount, Listening};
+use crate::domain::models::page::Page;
+use crate::domain::models::review::reviewed_work::{
+    ReviewedWork, ReviewedWorkKind, reviewed_works,
+};
+use crate::domain::models::slug::Slug;
+use crate::prelude::*;
+use crate::renderer::formatters::format_number::FormatNumber;
+use crate::renderer::partials::date::render_date;
+use crate::renderer::partials::md::{MarkdownMediaOption, md};
+use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::{RenderTask, RenderTasks};
+use crate::services::page_renderer::PageRenderer;
+use crate::utils::paginator::{Paginator, PaginatorPage};
+
+const PAGINATION_SIZE: usize = 60;
+
+// Every value that has a sub-page, so each page can link to the others
+#[derive(Debug, Clone)]
+struct ReviewedWorksFilters {
+    kind: ReviewedWorkKind,
+    scores: BTreeSet<u8>,
+    years: BTreeSet<i32>,
+    decades: BTreeSet<u16>,
+    genres: BTreeSet<String>,
+    has_listening: bool,
+}
+
+impl ReviewedWorksFilters {
+    fn from_works(kind: ReviewedWorkKind, works: &[ReviewedWork], listening: &Listening) -> Self {
+        Self {
+            kind,
+            has_listening: kind.has_listening_page() && !listening.is_empty(),
+            scores: works.iter().map(|work| work.score()).collect(),
+            years: works
+                .iter()
+                .flat_map(|work| work.reviewed_years())
+                .collect(),
+            decades: works.iter().filter_map(|work| work.decade()).collect(),
+            genres: works
+                .iter()
+                .flat_map(|work| work.genres.iter().cloned())
+                .collect(),
+        }
+    }
+
+    fn score_slug(&self, score: u8) -> Slug {
+        self.kind.slug().append(&format!("score/{}", score))
+    }
+
+    fn year_slug(&self, year: i32) -> Slug {
+        self.kind
+            .slug()
+            .append(&format!("{}/{}", self.kind.verb(), year))
+    }
+
+    fn decade_slug(&self, decade: u16) -> Slug {
+        self.kind.slug().append(&format!("decade/{}s", decade))
+    }
+}
+
+// Different works can end up with the same slug, e.g. remakes released the same year. Only the
+// most recently reviewed one gets the page, rather than whichever renders last
+fn works_with_unique_slugs<'d>(works: &[ReviewedWork<'d>]) -> Vec<ReviewedWork<'d>> {
+    let mut titles_by_slug: HashMap<&Slug, &str> = HashMap::new();
+
+    works
+        .iter()
+        .filter(|work| match titles_by_slug.get(&work.slug) {
+            Some(title) => {
+                warn!(
+                    "{} and {} both have the slug {}, only rendering the page for {}",
+                    title, work.title, work.slug, title
+                );
+                false
+            }
+            None => {
+                titles_by_slug.insert(&work.slug, work.title);
+                true
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+pub fn render_reviewed_works_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
+    for kind in ReviewedWorkKind::ALL {
+        let works = reviewed_works(kind, &data.timeline_events);
+
+        if works.is_empty() {
+            continue;
+        }
+
+        let filters = ReviewedWorksFilters::from_works(kind, &works, &data.listening);
+
+        if filters.has_listening {
+            tasks.add(RenderListeningPageTask {
+                filters: filters.clone(),
+                listening: &data.listening,
+            });
+        }
+
+        // Only the first page of the books index has the shelves
+        let shelves = match kind {
+            ReviewedWorkKind::Book if !data.book_shelves.is_empty() => Some(&data.book_shelves),
+            _ => None,
+        };
+
+        for work in works_with_unique_slugs(&works) {
+            tasks.add(RenderReviewedWorkPageTask { work });
+        }
+
+        let mut add_list_pages = |slug: Slug, title: String, works: Vec<ReviewedWork<'d>>| {
+            works
+                .into_iter()
+                .paginate(PAGINATION_SIZE)
+                .for_each(|paginator_page| {
+                    let is_index = slug == kind.slug() && paginator_page.page_number == 1;
+
+                    tasks.add(RenderReviewedWorksListPageTask {
+                        slug: slug.clone(),
+                        title: title.clone(),
+                        filters: filters.clone(),
+                        shelves: shelves.filter(|_| is_index),
+                        paginator_page,
+                    })
+                });
+        };
+
+        add_list_pages(kind.slug(), kind.title().to_string(), works.clone());
+
+        for score in filters.scores.iter().copied() {
+            add_list_pages(
+                filters.score_slug(score),
+                format!("{} scored {}/5", kind.title(), score),
+                works
+                    .iter()
+                    .filter(|work| work.score() == score)
+                    .cloned()
+                    .collect(),
+            );
+        }
+
+        for year in filters.years.iter().copied() {
+            add_list_pages(
+                filters.year_slug(year),
+                format!("{} {} in {}", kind.title(), kind.verb(), year),
+                works
+                    .iter()
+                    .filter(|work| work.reviewed_years().contains(&year))
+                    .cloned()
+                    .collect(),
+            );
+        }
+
+        for decade in filters.decades.iter().copied() {
+            add_list_pages(
+                filters.decade_slug(decade),
+                format!("{} from the {}s", kind.title(), decade),
+                works
+                    .iter()
+                    .filter(|work| work.decade() == Some(decade))
+                    .cloned()
+                    .collect(),
+            );
+        }
+
+        for genre in filters.genres.iter() {
+            add_list_pages(
+                kind.genre_slug(genre),
+                format!("{} {}", genre, kind.title()),
+                works
+                    .iter()
+                    .filter(|work| work.genres.contains(genre))
+                    .cloned()
+                    .collect(),
+            );
+        }
+
+        if !kind.has_author_pages() {
+            continue;
+        }
+
+        let mut works_by_author: BTreeMap<&str, Vec<ReviewedWork<'d>>> = BTreeMap::new();
+
+        for work in &works {
+            for author in work.creators {
+                works_by_author
+                    .entry(author)
+                    .or_default()
+                    .push(work.clone());
+            }
+        }
+
+        let authors = works_by_author
+            .iter()
+            .map(|(author, works)| (author.to_string(), works.len()))
+            .collect();
+
+        for (author, works) in works_by_author {
+            add_list_pages(
+                kind.author_slug(author),
+                format!("{} by {}", kind.title(), author),
+                works,
+            );
+        }
+
+        tasks.add(RenderReviewedWorksAuthorsPageTask {
+            kind,
+            filters,
+            authors,
+        });
+    }
+}
+
+fn render_filters<'l>(
+    filters: &'l ReviewedWorksFilters,
+    current: &'l Slug,
+) -> impl Renderable + 'l {
+    let link = move |slug: Slug, text: String| {
+        let is_current = &slug == current;
+
+        maud! {
+            li {
+                a href=(slug.relative_string()) class="active"[is_current] aria-current="page"[is_current] {
+                    (text)
+                }
+            }
+        }
+    };
+
+    maud! {
+        nav class="reviewed-works-filters" aria-label="Filters" data-pagefind-ignore {
+            ul {
+                (link(filters.kind.slug(), "All".to_string()))
+                @if filters.kind.has_author_pages() {
+                    (link(filters.kind.authors_slug(), "Authors".to_string()))
+                }
+                @if filters.has_listening {
+                    (link(filters.kind.listening_slug(), "Listening".to_string()))
+                }
+            }
+            ul class="scores" {
+                @for score in filters.scores.iter().rev() {
+                    (link(filters.score_slug(*score), format!("{}/5", score)))
+                }
+            }
+            ul class="years" {
+                @for year in filters.years.iter().rev() {
+                    (link(filters.year_slug(*year), year.to_string()))
+                }
+            }
+            @if !filters.decades.is_empty() {
+                ul class="decades" {
+                    @for decade in filters.decades.iter().rev() {
+                        (link(filters.decade_slug(*decade), format!("{}s", decade)))
+                    }
+                }
+            }
+            @if !filters.genres.is_empty() {
+                ul class="genres" {
+                    @for genre in &filters.genres {
+                        (link(filters.kind.genre_slug(genre), genre.clone()))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_reviewed_work<'l>(work: &'l ReviewedWork<'l>) -> impl Renderable + 'l {
+    maud! {
+        li {
+            a href=(work.slug.relative_string()) {
+                (work.image.render_small())
+                p class="title" { (work.title) }
+                p class="sub-text" { (work.score()) "/5" }
+            }
+        }
+    }
+}
+
+fn render_shelf<'l>(title: &'l str, books: &'l [ShelvedBook]) -> impl Renderable + 'l {
+    maud! {
+        @if !books.is_empty() {
+            section class="book-shelf" {
+                h2 { (title) }
+                ul class="reviewed-works" {
+                    @for shelved in books {
+                        li {
+                            a href=[shelved.book.cover.link_on_click.as_deref()] {
+                                (shelved.book.cover.render_small())
+                                p class="title" { (shelved.book.title) }
+                                p class="sub-text" { (shelved.author) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct RenderReviewedWorksListPageTask<'d> {
+    slug: Slug,
+    title: String,
+    filters: ReviewedWorksFilters,
+    shelves: Option<&'d BookShelves>,
+    paginator_page: PaginatorPage<ReviewedWork<'d>>,
+}
+
+impl<'d> RenderTask for RenderReviewedWorksListPageTask<'d> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let page = Page::new(self.slug.clone(), Some(self.title.clone()), None);
+
+        let page = Page::from_page_and_pagination_page(&page, &self.paginator_page);
+
+        let content = maud! {
+            (render_filters(&self.filters, &self.slug))
+            @if let Some(shelves) = self.shelves {
+                (render_shelf("Currently reading", &shelves.currently_reading))
+                (render_shelf("Want to read", &shelves.want_to_read))
+                h2 { "Read" }
+            }
+            ul class="reviewed-works" {
+                @for work in &self.paginator_page.data {
+                    (render_reviewed_work(work))
+                }
+            }
+        };
+
+        let options = PageOptions::new().with_main_class("reviewed-works-page");
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&page.slug, &rendered, None)
+    }
+}
+
+struct RenderReviewedWorksAuthorsPageTask {
+    kind: ReviewedWorkKind,
+    filters: ReviewedWorksFilters,
+    // Sorted by name, with how many of their works were reviewed
+    authors: Vec<(String, usize)>,
+}
+
+impl RenderTask for RenderReviewedWorksAuthorsPageTask {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let slug = self.kind.authors_slug();
+
+        let page = Page::new(
+            slug.clone(),
+            Some(format!("{} by author", self.kind.title())),
+            None,
+        );
+
+        let content = maud! {
+            (render_filters(&self.filters, &slug))
+            ul class="reviewed-works-authors" {
+                @for (author, count) in &self.authors {
+                    li {
+                        a href=(self.kind.author_slug(author).relative_string()) { (author) }
+                        span class="count" { (count) }
+                    }
+                }
+            }
+        };
+
+        let options = PageOptions::new().with_main_class("reviewed-works-page");
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&page.slug, &rendered, None)
+    }
+}
+
+fn render_listen_counts<'l>(title: &'l str, counts: &'l [ListenCount]) -> impl Renderable + 'l {
+    maud! {
+        @if !counts.is_empty() {
+            div {
+                h3 { (title) }
+                ol {
+                    @for count in counts {
+                        li {
+                            (count.name)
+                            @if !count.artist.is_empty() {
+                                span class="artist" { " by " (count.artist) }
+                            }
+                            span class="count" { (count.listens.format(0, true)) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct RenderListeningPageTask<'d> {
+    filters: ReviewedWorksFilters,
+    listening: &'d Listening,
+}
+
+impl<'d> RenderTask for RenderListeningPageTask<'d> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let slug = self.filters.kind.listening_slug();
+
+        let page = Page::new(slug.clone(), Some("Listening".to_string()), None);
+
+        let content = maud! {
+            (render_filters(&self.filters, &slug))
+            @for month in &self.listening.months {
+                section class="listening-month" {
+                    h2 {
+                        (month.month.format("%B %Y").to_string())
+                        span class="count" { (month.listens.format(0, true)) " listens" }
+                    }
+                    div class="top-lists" {
+                        (render_listen_counts("Top artists", &month.top_artists))
+                        (render_listen_counts("Top albums", &month.top_albums))
+                    }
+                }
+            }
+        };
+
+        let options = PageOptions::new().with_main_class("reviewed-works-page");
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&page.slug, &rendered, None)
+    }
+}
+
+fn render_score_history<'l>(work: &'l ReviewedWork<'l>) -> impl Renderable + 'l {
+    maud! {
+        section class="score-history" {
+            p class="average" {
+                "Average " (work.average_score().format(1, false)) "/5 from "
+                (work.reviews.len()) @if work.reviews.len() == 1 { " review" } @else { " reviews" }
+            }
+            ol {
+                @for review in work.reviews_by_date() {
+                    li {
+                        a href=(review.source.slug().relative_string()) {
+                            (render_date(review.date()))
+                        }
+                        @if let Some(detail) = &review.detail {
+                            span class="detail" { (detail) }
+                        }
+                        span class="score" { (review.score) "/5" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct RenderReviewedWorkPageTask<'d> {
+    work: ReviewedWork<'d>,
+}
+
+impl<'d> RenderTask for RenderReviewedWorkPageTask<'d> {
+    fn render(self: Box<Self>, renderer: &PageRenderer) -> Result<()> {
+        let work = &self.work;
+
+        let page = Page::new(work.slug.clone(), Some(work.title.to_string()), None)
+            .with_image(work.image.into());
+
+        let content = maud! {
+            (render_score_history(work))
+            @for review in work.reviews_by_date() {
+                article class="work-review" {
+                    h2 {
+                        a href=(review.source.slug().relative_string()) {
+                            (render_date(review.date()))
+                        }
+                    }
+                    (md(&review.source.content(), MarkdownMediaOption::NoMedia))
+                }
+            }
+        };
+
+        let options = PageOptions::new()
+            .with_main_class("reviewed-work-page")
+            .with_image(work.image);
+
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
+
+        renderer.render_page(&work.slug, &rendered, Some(*work.latest_review().date()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        review::reviewed_work::tests::{movie_review_event, movie_review_events},
+        timeline_event::TimelineEvents,
+    };
+
+    #[test]
+    fn it_should_bucket_works_by_latest_score_reviewed_year_and_decade() {
+        let events = movie_review_events();
+        let works = reviewed_works(ReviewedWorkKind::Movie, &events);
+
+        let filters = ReviewedWorksFilters::from_works(
+            ReviewedWorkKind::Movie,
+            &works,
+            &Listening::default(),
+        );
+
+        assert_eq!(filters.scores, BTreeSet::from([4, 5]));
+        assert_eq!(filters.years, BTreeSet::from([2023, 2024]));
+        assert_eq!(filters.decades, BTreeSet::from([1970, 2020]));
+        assert_eq!(
+            filters.genres,
+            BTreeSet::from(["Science Fiction".to_string()])
+        );
+        assert!(!filters.has_listening);
+
+        assert_eq!(
+            filters.decade_slug(2020),
+            Slug::new("/interests/movies/decade/2020s")
+        );
+        assert_eq!(
+            filters.year_slug(2023),
+            Slug::new("/interests/movies/watched/2023")
+        );
+    }
+
+    #[test]
+    fn it_should_only_keep_the_most_recently_reviewed_work_for_a_slug() {
+        let events = TimelineEvents::from_events(vec![
+            movie_review_event("What's Up, Doc?", 1972, 6949, "2024-01-05T20:00:00Z", 4),
+            movie_review_event("What's Up Doc", 1972, 1, "2023-01-05T20:00:00Z", 2),
+        ]);
+        let works = reviewed_works(ReviewedWorkKind::Movie, &events);
+
+        assert_eq!(works.len(), 2);
+        assert_eq!(
+            works[0].slug,
+            Slug::new("/interests/movies/what-s-up-doc-1972")
+        );
+
+        let unique = works_with_unique_slugs(&works);
+
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].title, "What's Up, Doc?");
+    }
+}
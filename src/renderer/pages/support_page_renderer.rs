@@ -7,7 +7,7 @@ use crate::services::page_renderer::PageRenderer;
 use hypertext::prelude::*;
 
 use crate::renderer::partials::md::MarkdownMediaOption;
-use crate::renderer::partials::md::md;
+use crate::renderer::partials::md::md_single;
 use crate::renderer::partials::page::PageOptions;
 use crate::renderer::partials::page::render_page;
 
@@ -29,13 +29,13 @@ impl RenderTask for RenderSupportPageTask {
 
         let content = maud! {
             article {
-                (md(&CONTENT, MarkdownMediaOption::WithMedia))
+                (md_single(&CONTENT, MarkdownMediaOption::WithMedia))
             }
         };
 
         let options = PageOptions::new().with_main_class("support-page");
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
         renderer.render_page(&slug, &rendered, None)
     }
@@ -3,29 +3,39 @@ use hypertext::prelude::*;
 use crate::domain::models::data::Data;
 use crate::domain::models::page::Page;
 use crate::domain::models::review::review_source::ReviewSource;
+use crate::domain::models::structured_data::StructuredData;
 use crate::domain::models::timeline_event::{TimelineEvent, TimelineEventReview};
 use crate::domain::models::tv_show::TvShow;
 use crate::prelude::*;
-use crate::renderer::partials::md::{self, md};
+use crate::renderer::partials::md::{self, md_single};
 use crate::renderer::partials::page::{PageOptions, render_page};
+use crate::renderer::partials::work_metadata::render_tv_show_metadata;
 use crate::renderer::{RenderTask, RenderTasks};
 
 // TODO Clicking on cover image should link you to tmdb page
 
 pub fn render_tv_review_pages<'d>(data: &'d Data, tasks: &mut RenderTasks<'d>) {
     data.timeline_events
-        .all_by_date()
-        .iter()
+        .all_including_unlisted()
         .filter_map(|event| match event {
-            TimelineEvent::Review(TimelineEventReview::TvShowReview {
-                tv_show, source, ..
-            }) => Some((tv_show, source)),
+            TimelineEvent::Review(
+                event @ TimelineEventReview::TvShowReview {
+                    tv_show, source, ..
+                },
+            ) => Some((event, tv_show, source)),
             _ => None,
         })
-        .for_each(|(tv_show, source)| tasks.add(RenderTvShowReviewPageTask { tv_show, source }));
+        .for_each(|(event, tv_show, source)| {
+            tasks.add(RenderTvShowReviewPageTask {
+                event,
+                tv_show,
+                source,
+            })
+        });
 }
 
 struct RenderTvShowReviewPageTask<'l> {
+    event: &'l TimelineEventReview,
     tv_show: &'l TvShow,
     source: &'l ReviewSource,
 }
@@ -37,23 +47,40 @@ impl<'l> RenderTask for RenderTvShowReviewPageTask<'l> {
     ) -> Result<()> {
         let source = self.source;
 
+        let tv_show = self.tv_show;
+
         let content = maud! {
             article {
-                (md(&source.content(), md::MarkdownMediaOption::NoMedia))
+                (md_single(&source.content(), md::MarkdownMediaOption::NoMedia))
+            }
+            (render_tv_show_metadata(tv_show, source.status().is_listed()))
+            @if source.status().is_listed() {
+                p class="work-link" {
+                    a href=(tv_show.slug().relative_string()) { "All reviews of " (tv_show.title) }
+                }
             }
         };
 
+        let structured_data = StructuredData::from(self.event);
+
         let options = PageOptions::new()
             .with_main_class("tv-show-review-post-page")
             .use_date_as_title()
-            .with_image(&self.tv_show.poster);
+            .with_image(&tv_show.poster)
+            .with_structured_data(&structured_data);
 
         let page = Page::new(source.slug().clone(), None, None)
             .with_date(*source.date())
-            .with_tags(source.tags().clone());
+            .with_tags(source.tags().clone())
+            .with_status(source.status());
 
-        let rendered = render_page(&page, &options, &content, maud! {});
+        let rendered = render_page(&page, &options, renderer.site_links(), &content, maud! {});
 
-        renderer.render_page(&source.slug(), &rendered, Some(*source.date()))
+        renderer.render_post_page(
+            &source.slug(),
+            &rendered,
+            Some(*source.date()),
+            source.status(),
+        )
     }
 }
@@ -0,0 +1,21 @@
+use hypertext::prelude::*;
+
+use crate::{domain::models::backlinks::Backlink, renderer::partials::date::render_date};
+
+pub fn render_backlinks<'l>(backlinks: &'l [Backlink]) -> impl Renderable + 'l {
+    maud! {
+        @if !backlinks.is_empty() {
+            aside class="backlinks" {
+                h2 { "Referenced by" }
+                ul {
+                    @for backlink in backlinks {
+                        li {
+                            a href=(backlink.slug.relative_string()) { (backlink.title) }
+                            (render_date(&backlink.date))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use hypertext::prelude::*;
+
+const WIDTH: f32 = 600.0;
+const ROW_HEIGHT: f32 = 24.0;
+const ROW_LABEL_WIDTH: f32 = 140.0;
+const COLUMN_AREA_HEIGHT: f32 = 160.0;
+const COLUMN_LABEL_HEIGHT: f32 = 20.0;
+const VALUE_WIDTH: f32 = 50.0;
+const GAP: f32 = 4.0;
+// Column charts only label every nth column past this many, so labels don't overlap
+const MAX_COLUMN_LABELS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarChartLayout {
+    // Horizontal bars, for categories
+    Rows,
+    // Vertical bars, for anything over time
+    Columns,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bar {
+    pub label: String,
+    pub value: f32,
+    pub value_text: String,
+}
+
+impl Bar {
+    pub fn count(label: impl Into<String>, count: usize) -> Self {
+        Self {
+            label: label.into(),
+            value: count as f32,
+            value_text: count.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BarChart {
+    // Unique on the page, used to label the svg
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub label_heading: String,
+    pub value_heading: String,
+    pub layout: BarChartLayout,
+    pub bars: Vec<Bar>,
+}
+
+// SVG elements used by the chart, on top of the standard HTML ones
+mod hypertext_elements {
+    use hypertext::define_elements;
+    pub use hypertext::validation::hypertext_elements::*;
+
+    define_elements! {
+        svg {
+            viewBox
+            xmlns
+        }
+
+        desc
+
+        rect {
+            x
+            y
+            width
+            height
+        }
+
+        text {
+            x
+            y
+            text_anchor
+        }
+    }
+}
+
+fn coordinate(value: f32) -> String {
+    format!("{:.1}", value)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: f32,
+    y: f32,
+    anchor: &'static str,
+}
+
+// Where each part of a bar is drawn
+#[derive(Debug, Clone, Copy)]
+struct PlacedBar<'l> {
+    bar: &'l Bar,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    value: Point,
+    label: Option<Point>,
+}
+
+fn place_rows(bars: &[Bar], max: f32) -> (f32, Vec<PlacedBar<'_>>) {
+    let bar_width = WIDTH - ROW_LABEL_WIDTH - VALUE_WIDTH - GAP * 2.0;
+
+    let placed = bars
+        .iter()
+        .enumerate()
+        .map(|(index, bar)| {
+            let y = index as f32 * ROW_HEIGHT;
+            let width = bar.value / max * bar_width;
+            let text_y = y + ROW_HEIGHT / 2.0 + 4.0;
+
+            PlacedBar {
+                bar,
+                x: ROW_LABEL_WIDTH + GAP,
+                y: y + GAP / 2.0,
+                width,
+                height: ROW_HEIGHT - GAP,
+                value: Point {
+                    x: ROW_LABEL_WIDTH + GAP * 2.0 + width,
+                    y: text_y,
+                    anchor: "start",
+                },
+                label: Some(Point {
+                    x: ROW_LABEL_WIDTH,
+                    y: text_y,
+                    anchor: "end",
+                }),
+            }
+        })
+        .collect();
+
+    (bars.len() as f32 * ROW_HEIGHT, placed)
+}
+
+fn place_columns(bars: &[Bar], max: f32) -> (f32, Vec<PlacedBar<'_>>) {
+    let column_width = WIDTH / bars.len() as f32;
+    let label_every = bars.len().div_ceil(MAX_COLUMN_LABELS);
+
+    let placed = bars
+        .iter()
+        .enumerate()
+        .map(|(index, bar)| {
+            let x = index as f32 * column_width;
+            let height = bar.value / max * (COLUMN_AREA_HEIGHT - ROW_HEIGHT);
+            let center = x + column_width / 2.0;
+
+            PlacedBar {
+                bar,
+                x: x + GAP / 2.0,
+                y: COLUMN_AREA_HEIGHT - height,
+                width: column_width - GAP,
+                height,
+                value: Point {
+                    x: center,
+                    y: COLUMN_AREA_HEIGHT - height - GAP,
+                    anchor: "middle",
+                },
+                label: (index % label_every == 0).then_some(Point {
+                    x: center,
+                    y: COLUMN_AREA_HEIGHT + COLUMN_LABEL_HEIGHT - GAP,
+                    anchor: "middle",
+                }),
+            }
+        })
+        .collect();
+
+    (COLUMN_AREA_HEIGHT + COLUMN_LABEL_HEIGHT, placed)
+}
+
+fn render_svg<'l>(chart: &'l BarChart) -> impl Renderable + 'l {
+    let max = chart
+        .bars
+        .iter()
+        .map(|bar| bar.value)
+        .fold(0.0, f32::max)
+        .max(1.0);
+
+    let (height, placed) = match chart.layout {
+        BarChartLayout::Rows => place_rows(&chart.bars, max),
+        BarChartLayout::Columns => place_columns(&chart.bars, max),
+    };
+
+    let id = &chart.id;
+
+    maud! {
+        svg
+            viewBox=(format!("0 0 {} {}", WIDTH, height))
+            role="img"
+            aria-labelledby=(format!("{id}-title {id}-desc"))
+            xmlns="http://www.w3.org/2000/svg"
+        {
+            title id=(format!("{id}-title")) { (chart.title) }
+            desc id=(format!("{id}-desc")) { (chart.description) }
+            @for placed in &placed {
+                rect
+                    x=(coordinate(placed.x))
+                    y=(coordinate(placed.y))
+                    width=(coordinate(placed.width))
+                    height=(coordinate(placed.height))
+                    class="bar"
+                {}
+                text
+                    x=(coordinate(placed.value.x))
+                    y=(coordinate(placed.value.y))
+                    text-anchor=(placed.value.anchor)
+                    class="value"
+                {
+                    (placed.bar.value_text)
+                }
+                @if let Some(label) = placed.label {
+                    text
+                        x=(coordinate(label.x))
+                        y=(coordinate(label.y))
+                        text-anchor=(label.anchor)
+                        class="label"
+                    {
+                        (placed.bar.label)
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Drawn at build time so there's no client side code, the table has the same data for anything
+// that can't see the chart
+pub fn render_bar_chart<'l>(chart: &'l BarChart) -> impl Renderable + 'l {
+    maud! {
+        @if !chart.bars.is_empty() {
+            figure class="bar-chart" {
+                figcaption { (chart.title) }
+                (render_svg(chart))
+                details {
+                    summary { "Show as table" }
+                    table {
+                        thead {
+                            tr {
+                                th scope="col" { (chart.label_heading) }
+                                th scope="col" { (chart.value_heading) }
+                            }
+                        }
+                        tbody {
+                            @for bar in &chart.bars {
+                                tr {
+                                    th scope="row" { (bar.label) }
+                                    td { (bar.value_text) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_draw_an_escaped_svg_for_the_bars() {
+        let chart = BarChart {
+            id: "by-genre".to_string(),
+            title: "Average score by genre".to_string(),
+            description: "Scores <out of 5>".to_string(),
+            label_heading: "Genre".to_string(),
+            value_heading: "Average score".to_string(),
+            layout: BarChartLayout::Rows,
+            bars: vec![Bar::count("Action & Adventure", 3), Bar::count("Drama", 1)],
+        };
+
+        let html = render_bar_chart(&chart).render().into_inner();
+
+        assert!(html.contains(
+            r#"<svg viewBox="0 0 600 48" role="img" aria-labelledby="by-genre-title by-genre-desc""#
+        ));
+        assert!(html.contains(r#"<desc id="by-genre-desc">Scores &lt;out of 5&gt;</desc>"#));
+        assert!(html.contains(r#"text-anchor="end" class="label">Action &amp; Adventure</text>"#));
+        assert_eq!(html.matches("<rect").count(), 2);
+    }
+}
@@ -0,0 +1,100 @@
+use hypertext::{Buffer, prelude::*};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+use crate::domain::models::mastodon_post::{MastodonPostEngagement, MastodonReplyThread};
+use crate::renderer::partials::date::render_date;
+
+static HTML_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<[^>]*>"#).unwrap());
+static PARAGRAPH_BREAK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)</p>\s*<p>|<br\s*/?>"#).unwrap());
+
+// Replies are other people's HTML, so only their text is rendered
+fn reply_paragraphs(content: &str) -> Vec<String> {
+    let content = PARAGRAPH_BREAK_REGEX.replace_all(content, "\n");
+    let content = HTML_TAG_REGEX.replace_all(&content, "");
+    let content = html_escape::decode_html_entities(&content);
+
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+fn plural(count: u32, singular: &str, plural: &str) -> String {
+    match count {
+        1 => format!("1 {}", singular),
+        _ => format!("{} {}", count, plural),
+    }
+}
+
+struct RenderableReplyThread<'l> {
+    thread: &'l MastodonReplyThread<'l>,
+}
+
+impl<'l> Renderable for RenderableReplyThread<'l> {
+    fn render_to(&self, output: &mut Buffer) {
+        let reply = self.thread.reply;
+
+        maud! {
+            li class="mastodon-reply" {
+                div class="author-and-date" {
+                    a class="author" href=(reply.account.url.as_str()) rel="nofollow" {
+                        @if reply.account.display_name.is_empty() {
+                            (&reply.account.acct)
+                        } @else {
+                            (&reply.account.display_name)
+                        }
+                    }
+                    a class="date" href=(reply.url.as_str()) rel="nofollow" {
+                        (render_date(&reply.created_at))
+                    }
+                }
+                div class="content" {
+                    @for paragraph in reply_paragraphs(&reply.content) {
+                        p { (paragraph) }
+                    }
+                }
+                @if !self.thread.children.is_empty() {
+                    ul class="mastodon-replies" {
+                        @for child in &self.thread.children {
+                            (RenderableReplyThread { thread: child })
+                        }
+                    }
+                }
+            }
+        }
+        .render_to(output);
+    }
+}
+
+pub fn render_mastodon_engagement<'l>(
+    engagement: &'l MastodonPostEngagement,
+    status_id: &'l str,
+    original_uri: &'l Url,
+) -> impl Renderable + 'l {
+    let threads = engagement.reply_threads(status_id);
+
+    maud! {
+        section class="mastodon-engagement" data-pagefind-ignore {
+            p class="counts" {
+                span class="boosts" { (plural(engagement.reblogs_count, "boost", "boosts")) }
+                span class="favourites" { (plural(engagement.favourites_count, "favourite", "favourites")) }
+                a href=(original_uri.as_str()) class="link" target="_blank" rel="noopener" {
+                    ("Reply on Mastodon")
+                }
+            }
+            @if !threads.is_empty() {
+                h2 { ("Replies") }
+                ul class="mastodon-replies" {
+                    @for thread in &threads {
+                        (RenderableReplyThread { thread })
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,17 +2,22 @@ use hypertext::{Raw, prelude::*};
 
 use crate::renderer::formatters::format_markdown::FormatMarkdown;
 
-pub enum MarkdownMediaOption {
+use crate::domain::models::media::Media;
+
+pub enum MarkdownMediaOption<'l> {
     WithMedia,
+    // Swaps inline images for their processed versions
+    WithProcessedMedia(&'l [Media]),
     NoMedia,
 }
 
 pub fn md<'l>(
     md: &'l impl FormatMarkdown,
-    media_option: MarkdownMediaOption,
+    media_option: MarkdownMediaOption<'l>,
 ) -> impl Renderable + 'l {
     let md = match media_option {
         MarkdownMediaOption::WithMedia => md.to_html(),
+        MarkdownMediaOption::WithProcessedMedia(media) => md.to_html_with_media(media),
         MarkdownMediaOption::NoMedia => md.remove_media().to_html(),
     };
 
@@ -20,3 +25,19 @@ pub fn md<'l>(
         (Raw::dangerously_create(&md))
     }
 }
+
+// Headings and footnotes get ids, so this is only for pages showing a single post or page
+pub fn md_single<'l>(
+    md: &'l impl FormatMarkdown,
+    media_option: MarkdownMediaOption<'l>,
+) -> impl Renderable + 'l {
+    let md = match media_option {
+        MarkdownMediaOption::WithMedia => md.to_single_html(&[]),
+        MarkdownMediaOption::WithProcessedMedia(media) => md.to_single_html(media),
+        MarkdownMediaOption::NoMedia => md.remove_media().to_single_html(&[]),
+    };
+
+    maud! {
+        (Raw::dangerously_create(&md))
+    }
+}
@@ -3,27 +3,96 @@ use hypertext::prelude::*;
 
 use crate::domain::models::image::{Image, SizedImage};
 
-fn render_image<'l>(image: &'l SizedImage, description: &'l str) -> impl Renderable + 'l {
+const MISSING_DESCRIPTION_ALT: &str = "Image without a description";
+
+fn render_image<'l>(
+    image: &'l SizedImage,
+    description: &'l str,
+    lazy: bool,
+) -> impl Renderable + 'l {
     maud! {
-        img
-            src={(image.file.as_cdn_url().as_str())}
-            alt={(description)}
-            width={(image.dimensions.width)}
-            height={(image.dimensions.height)};
+        @if description.trim().is_empty() {
+            span class="missing-description" {
+                img
+                    src={(image.file.as_cdn_url().as_str())}
+                    alt={(MISSING_DESCRIPTION_ALT)}
+                    width={(image.dimensions.width)}
+                    height={(image.dimensions.height)}
+                    loading="lazy"[lazy];
+                span class="missing-description-label" aria-hidden="true" { ("No description") }
+            }
+        } @else {
+            img
+                src={(image.file.as_cdn_url().as_str())}
+                alt={(description)}
+                width={(image.dimensions.width)}
+                height={(image.dimensions.height)}
+                loading="lazy"[lazy];
+        }
     }
 }
 
 impl Image {
     pub fn render_original<'l>(&'l self) -> impl Renderable + 'l {
-        render_image(&self.original, &self.description)
+        render_image(&self.original, &self.description, false)
     }
 
     pub fn render_large<'l>(&'l self) -> impl Renderable + 'l {
-        render_image(&self.large, &self.description)
+        render_image(&self.large, &self.description, false)
     }
 
     pub fn render_small<'l>(&'l self) -> impl Renderable + 'l {
-        render_image(&self.small, &self.description)
+        render_image(&self.small, &self.description, false)
+    }
+}
+
+fn srcset(image: &Image) -> String {
+    [&image.small, &image.large, &image.original]
+        .iter()
+        .map(|sized| {
+            format!(
+                "{} {}w",
+                sized.file.as_cdn_url().as_str(),
+                sized.dimensions.width
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl Image {
+    // Lets the browser pick the smallest derivative that fills `sizes`
+    pub fn render_responsive<'l>(&'l self, sizes: &'l str) -> impl Renderable + 'l {
+        maud! {
+            img
+                src={(self.large.file.as_cdn_url().as_str())}
+                srcset={(srcset(self))}
+                sizes={(sizes)}
+                alt={(&self.description)}
+                width={(self.large.dimensions.width)}
+                height={(self.large.dimensions.height)};
+        }
+    }
+}
+
+// Images written inline in markdown content. The title, if there is one, becomes a caption
+pub fn render_markdown_image<'l>(
+    image: &'l Image,
+    caption: Option<&'l str>,
+) -> impl Renderable + 'l {
+    maud! {
+        @if let Some(caption) = caption {
+            figure class="markdown-image" {
+                a href=(image.original.file.as_cdn_url().as_str()) {
+                    (render_image(&image.large, &image.description, true))
+                }
+                figcaption { (caption) }
+            }
+        } @else {
+            a href=(image.original.file.as_cdn_url().as_str()) {
+                (render_image(&image.large, &image.description, true))
+            }
+        }
     }
 }
 
@@ -1,7 +1,14 @@
+pub mod backlinks;
+pub mod bar_chart;
 pub mod date;
 pub mod javascript;
+pub mod mastodon;
 pub mod md;
 pub mod media;
 pub mod page;
+pub mod series;
+pub mod shortcodes;
+pub mod table_of_contents;
 pub mod tag;
 pub mod timeline_events_list;
+pub mod work_metadata;
@@ -1,14 +1,23 @@
 use crate::{
     build_data::BUILD_DATE,
-    domain::models::{image::Image, page::PagePaginationData, site_config::SITE_CONFIG, tag::Tag},
-    renderer::partials::{date::render_date, tag::render_tags},
+    domain::models::{
+        image::Image,
+        page::PagePaginationData,
+        site_config::{SITE_CONFIG, SiteLinks},
+        structured_data::StructuredData,
+        tag::Tag,
+    },
+    renderer::{
+        formatters::format_number::FormatNumber,
+        partials::{date::render_date, tag::render_tags},
+    },
 };
 use chrono::{DateTime, Utc};
 use hypertext::{Raw, prelude::*};
 
 use crate::domain::models::page::Page;
 
-pub fn nav_bar<'l>(page: &'l Page) -> impl Renderable + 'l {
+pub fn nav_bar<'l>(page: &'l Page, links: &'l SiteLinks) -> impl Renderable + 'l {
     maud! {
         nav
             data-pagefind-ignore
@@ -37,7 +46,7 @@ pub fn nav_bar<'l>(page: &'l Page) -> impl Renderable + 'l {
                 }
             }
             ul class="links" {
-                @for link in &page.header_links {
+                @for link in &links.header_links {
                     li class=(link.url.replace("/", "")) {
                         @if page.slug.as_str().starts_with(&link.url) {
                             a
@@ -89,14 +98,14 @@ pub fn nav_bar<'l>(page: &'l Page) -> impl Renderable + 'l {
 }
 
 #[component]
-pub fn render_footer<'l>(page: &'l Page) -> impl Renderable + 'l {
+pub fn render_footer<'l>(links: &'l SiteLinks) -> impl Renderable + 'l {
     maud! {
         footer
             data-pagefind-ignore
             aria-label="Secondary Navigation"
         {
             ul class="groups" {
-                @for group in &page.page_links {
+                @for group in &links.page_links {
                     li {
                         p { (group.name) }
                         ul {
@@ -165,12 +174,18 @@ pub fn render_pagination<'l>(pagination: &'l PagePaginationData) -> impl Rendera
     }
 }
 
-fn render_page_base<'l>(page: &'l Page, body: &'l impl Renderable) -> impl Renderable + 'l {
+fn render_page_base<'l>(
+    page: &'l Page,
+    structured_data: Option<&'l StructuredData>,
+    body: &'l impl Renderable,
+) -> impl Renderable + 'l {
     let title = match &page.title {
         Some(t) => format!("{} | {}", t, SITE_CONFIG.title),
         None => SITE_CONFIG.title.clone(),
     };
 
+    let json_ld = structured_data.and_then(|structured_data| structured_data.to_json_ld());
+
     maud! {
         !DOCTYPE
         html lang={ (page.language) } {
@@ -193,6 +208,10 @@ fn render_page_base<'l>(page: &'l Page, body: &'l impl Renderable) -> impl Rende
                 meta name="og:title" content=(title);
                 meta name="twitter:title" content=(title);
 
+                @if page.no_index {
+                    meta name="robots" content="noindex";
+                }
+
                 meta name="description" content=(page.description);
                 meta name="og:description" content=(page.description);
                 meta name="twitter:description" content=(page.description);
@@ -209,6 +228,13 @@ fn render_page_base<'l>(page: &'l Page, body: &'l impl Renderable) -> impl Rende
                 meta name="fediverse:creator" content="@geekyaubergine@social.lol";
 
                 link rel="stylesheet" href={"/assets/css/styles-" BUILD_DATE ".css"};
+                link rel="stylesheet" href={"/assets/css/syntax-" BUILD_DATE ".css"};
+
+                @if let Some(json_ld) = &json_ld {
+                    script type="application/ld+json" {
+                        (Raw::dangerously_create(json_ld))
+                    }
+                }
 
                 script src="https://cdn.usefathom.com/script.js" data-site="XPKVFMEO" defer {}
             }
@@ -225,6 +251,7 @@ pub struct PageOptions<'l> {
     image: Option<&'l Image>,
     hide_header: bool,
     hide_footer: bool,
+    structured_data: Option<&'l StructuredData>,
 }
 
 impl<'l> Default for PageOptions<'l> {
@@ -242,6 +269,7 @@ impl<'l> PageOptions<'l> {
             image: None,
             hide_header: false,
             hide_footer: false,
+            structured_data: None,
         }
     }
 
@@ -274,12 +302,18 @@ impl<'l> PageOptions<'l> {
         self.hide_footer = true;
         self
     }
+
+    pub fn with_structured_data(mut self, structured_data: &'l StructuredData) -> Self {
+        self.structured_data = Some(structured_data);
+        self
+    }
 }
 
 enum HeaderData<'l> {
     Title {
         title: String,
         date: Option<&'l DateTime<Utc>>,
+        reading: Option<String>,
         tags: &'l Vec<Tag>,
         image: Option<&'l Image>,
     },
@@ -308,6 +342,7 @@ impl<'l> HeaderData<'l> {
             Some(title) => HeaderData::Title {
                 title: title.clone(),
                 date: page.date(),
+                reading: render_reading(page.word_count(), page.read_time()),
                 tags: &page.tags,
                 image: options.image,
             },
@@ -316,10 +351,22 @@ impl<'l> HeaderData<'l> {
     }
 }
 
+pub fn render_reading(word_count: Option<usize>, read_time: Option<&str>) -> Option<String> {
+    match (word_count, read_time) {
+        (Some(word_count), Some(read_time)) => Some(format!(
+            "{} words · {}",
+            word_count.format(0, true),
+            read_time
+        )),
+        (None, Some(read_time)) => Some(read_time.to_string()),
+        _ => None,
+    }
+}
+
 fn render_header<'l>(data: &'l HeaderData<'l>) -> impl Renderable + 'l {
     maud! {
         @match &data {
-            HeaderData::Title { title, date, tags, image } => {
+            HeaderData::Title { title, date, reading, tags, image } => {
                 div class="page-header" {
                     h1 { (title) }
                     @if let Some(image) = image {
@@ -328,6 +375,9 @@ fn render_header<'l>(data: &'l HeaderData<'l>) -> impl Renderable + 'l {
                     @if let Some(date) = &date {
                         (render_date(date))
                     }
+                    @if let Some(reading) = reading {
+                        p class="reading" { (reading) }
+                    }
                     (render_tags(tags, None))
                 }
             }
@@ -348,6 +398,7 @@ fn render_header<'l>(data: &'l HeaderData<'l>) -> impl Renderable + 'l {
 pub fn render_page<'l>(
     page: &'l Page,
     options: &'l PageOptions<'l>,
+    links: &'l SiteLinks,
     content: impl Renderable + 'l,
     scripts: impl Renderable + 'l,
 ) -> impl Renderable + 'l {
@@ -359,7 +410,7 @@ pub fn render_page<'l>(
 
     let body = maud! {
         body class=(&body_class) {
-            (nav_bar(page))
+            (nav_bar(page, links))
             main class=(main_class) {
                 @if !options.hide_header {
                     (render_header(&header_data))
@@ -370,13 +421,13 @@ pub fn render_page<'l>(
                 }
             }
             @if !options.hide_footer {
-                (render_footer(page))
+                (render_footer(links))
             }
             (scripts)
         }
     };
 
     maud! {
-        (render_page_base(page, &body))
+        (render_page_base(page, options.structured_data, &body))
     }
 }
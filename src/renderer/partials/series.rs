@@ -0,0 +1,33 @@
+use hypertext::prelude::*;
+
+use crate::domain::models::blog_series::BlogSeries;
+
+pub fn render_series_navigation<'l>(
+    series: &'l BlogSeries<'l>,
+    index: usize,
+) -> impl Renderable + 'l {
+    let part = series.part(series.posts[index]);
+    let previous = series.previous(index);
+    let next = series.next(index);
+
+    maud! {
+        nav class="series-navigation" aria-label="Series" data-pagefind-ignore {
+            p {
+                "Part " (part) " of " (series.posts.len()) " in "
+                a href=(series.slug().relative_string()) { (series.config.title) }
+            }
+            div class="links" {
+                @if let Some(previous) = previous {
+                    a class="previous" rel="prev" href=(previous.slug.relative_string()) {
+                        "← " (previous.title)
+                    }
+                }
+                @if let Some(next) = next {
+                    a class="next" rel="next" href=(next.slug.relative_string()) {
+                        (next.title) " →"
+                    }
+                }
+            }
+        }
+    }
+}
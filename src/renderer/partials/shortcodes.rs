@@ -0,0 +1,113 @@
+use hypertext::{Raw, prelude::*};
+use url::Url;
+
+use crate::{
+    domain::models::{
+        albums::{album::Album, album_photo::AlbumPhoto},
+        book::Book,
+        mastodon_post::MastodonPost,
+        movie::Movie,
+    },
+    renderer::partials::{
+        date::render_date,
+        media::{MediaGripOptions, render_media_grid},
+    },
+};
+
+const YOUTUBE_EMBED_URL: &str = "https://www.youtube-nocookie.com/embed/";
+
+pub fn render_album_shortcode<'l>(album: &'l Album) -> impl Renderable + 'l {
+    maud! {
+        a class="shortcode-album" href=(album.slug.relative_string()) {
+            div class=(if album.cover_images().len() > 1 { "preview-multi" } else { "preview-single" }) {
+                @for photo in album.cover_images() {
+                    (photo.render_small())
+                }
+            }
+            div class="title-and-date" {
+                span class="title" { (album.title) }
+                (render_date(&album.date))
+            }
+        }
+    }
+}
+
+pub fn render_photo_shortcode<'l>(photo: &'l AlbumPhoto) -> impl Renderable + 'l {
+    maud! {
+        figure class="markdown-image shortcode-photo" {
+            a href=(photo.slug.relative_string()) {
+                (photo.image.render_large())
+            }
+            @if !photo.description.is_empty() {
+                figcaption { (photo.description) }
+            }
+        }
+    }
+}
+
+pub fn render_youtube_shortcode<'l>(id: &'l str) -> impl Renderable + 'l {
+    maud! {
+        div class="shortcode-embed youtube" {
+            iframe
+                src={(YOUTUBE_EMBED_URL) (id)}
+                title="YouTube video"
+                loading="lazy"
+                allow="encrypted-media; picture-in-picture"
+                allowfullscreen {}
+        }
+    }
+}
+
+// Our own statuses are already archived, anyone else's is embedded from their server
+pub fn render_own_toot_shortcode<'l>(post: &'l MastodonPost) -> impl Renderable + 'l {
+    let options = MediaGripOptions::for_list();
+
+    maud! {
+        blockquote class="shortcode-toot" {
+            div class="content" {
+                (Raw::dangerously_create(post.content()))
+            }
+            (render_media_grid(post.media(), &options))
+            a class="date" href=(post.slug().relative_string()) {
+                (render_date(post.created_at()))
+            }
+        }
+    }
+}
+
+pub fn render_remote_toot_shortcode<'l>(url: &'l Url) -> impl Renderable + 'l {
+    maud! {
+        div class="shortcode-embed toot" {
+            iframe
+                src={(url.as_str().trim_end_matches('/')) "/embed"}
+                title="Mastodon post"
+                loading="lazy"
+                allowfullscreen {}
+        }
+    }
+}
+
+pub fn render_movie_shortcode<'l>(movie: &'l Movie, link: &'l str) -> impl Renderable + 'l {
+    maud! {
+        a class="shortcode-work" href=(link) {
+            (movie.poster.render_small())
+            span class="title" { (movie.title) " (" (movie.year) ")" }
+        }
+    }
+}
+
+pub fn render_book_shortcode<'l>(book: &'l Book, link: Option<&'l str>) -> impl Renderable + 'l {
+    maud! {
+        @if let Some(link) = link {
+            a class="shortcode-work" href=(link) {
+                (book.cover.render_small())
+                span class="title" { (book.title) }
+            }
+        } @else {
+            span class="shortcode-work" {
+                (book.cover.render_small())
+                span class="title" { (book.title) }
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use hypertext::prelude::*;
+
+use crate::renderer::formatters::format_markdown::TableOfContentsEntry;
+
+// Deeper headings are too fine grained to be worth listing
+const MAX_LEVEL: u8 = 3;
+
+pub fn render_table_of_contents<'l>(entries: &'l [TableOfContentsEntry]) -> impl Renderable + 'l {
+    maud! {
+        @if entries.iter().any(|entry| entry.level <= MAX_LEVEL) {
+            aside class="table-of-contents" aria-label="Table of Contents" data-pagefind-ignore {
+                p class="title" { ("Contents") }
+                ol {
+                    @for entry in entries.iter().filter(|entry| entry.level <= MAX_LEVEL) {
+                        li class=(format!("level-{}", entry.level)) {
+                            a href=(format!("#{}", entry.id)) { (&entry.title) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::domain::models::mastodon_post::MastodonPost;
 use crate::domain::models::media::Media;
 use crate::domain::models::micro_post::MicroPost;
 use crate::domain::models::movie::Movie;
+use crate::domain::models::music_album::MusicAlbum;
 use crate::domain::models::page::Page;
 use crate::domain::models::review::review_source::ReviewSource;
 use crate::domain::models::slug::Slug;
@@ -43,6 +44,7 @@ pub fn render_timeline_events_list<'l>(events: &'l [&TimelineEvent]) -> impl Ren
                         TimelineEventReview::BookReview { book, source, .. } => (render_book_review(book, source)),
                         TimelineEventReview::MovieReview { movie, source, .. } => (render_movie_review(movie, source)),
                         TimelineEventReview::TvShowReview { tv_show, source, .. } => (render_tv_show_review(tv_show, source)),
+                        TimelineEventReview::AlbumReview { album, source, .. } => (render_album_review(album, source)),
                     },
                     TimelineEvent::GameAchievementUnlock(achievement) => @match achievement {
                         TimelineEventGameAchievementUnlock::SteamAchievementUnlocked {
@@ -230,6 +232,26 @@ pub fn render_tv_show_review<'l>(
     )
 }
 
+pub fn render_album_review<'l>(
+    album: &'l MusicAlbum,
+    source: &'l ReviewSource,
+) -> impl Renderable + 'l {
+    let content = maud! {
+        div class="prose" {
+            (md(&source.content(), MarkdownMediaOption::NoMedia))
+        }
+    };
+
+    render_post(
+        source.slug(),
+        source.date(),
+        content,
+        None,
+        Some(source.tags()),
+        Some(&album.cover),
+    )
+}
+
 pub fn render_game_achievement<'l>(
     game: &'l SteamGame,
     achievement: &'l SteamGameAchievementUnlocked,
@@ -318,7 +340,13 @@ impl<'p> RenderTask for RenderTimelineEventsListTask<'p> {
 
         let content = render_timeline_events_list(&self.paginator_page.data);
 
-        let rendered = render_page(&page, &self.page_options, &content, maud! {});
+        let rendered = render_page(
+            &page,
+            &self.page_options,
+            renderer.site_links(),
+            &content,
+            maud! {},
+        );
 
         renderer.render_page(&slug, &rendered, None)
     }
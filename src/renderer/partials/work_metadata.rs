@@ -0,0 +1,129 @@
+use hypertext::prelude::*;
+
+use crate::domain::models::{
+    book::Book, movie::Movie, music_album::MusicAlbum, review::reviewed_work::ReviewedWorkKind,
+    slug::Slug, tv_show::TvShow,
+};
+
+type MetadataValues = Vec<(String, Option<Slug>)>;
+
+fn plain(values: &[String]) -> MetadataValues {
+    values.iter().map(|value| (value.clone(), None)).collect()
+}
+
+// Unlisted reviews aren't on the index pages, so they don't link to them
+fn linked(values: &[String], link: bool, to_slug: impl Fn(&str) -> Slug) -> MetadataValues {
+    values
+        .iter()
+        .map(|value| (value.clone(), link.then(|| to_slug(value))))
+        .collect()
+}
+
+fn single(value: Option<String>) -> MetadataValues {
+    value.into_iter().map(|value| (value, None)).collect()
+}
+
+fn format_runtime(minutes: u16) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+fn render_metadata(rows: Vec<(&'static str, MetadataValues)>) -> impl Renderable {
+    let rows = rows
+        .into_iter()
+        .filter(|(_, values)| !values.is_empty())
+        .collect::<Vec<_>>();
+
+    maud! {
+        @if !rows.is_empty() {
+            dl class="work-metadata" {
+                @for (label, values) in &rows {
+                    div {
+                        dt { (label) }
+                        dd {
+                            @for (index, (text, slug)) in values.iter().enumerate() {
+                                @if index > 0 { ", " }
+                                @if let Some(slug) = slug {
+                                    a href=(slug.relative_string()) { (text) }
+                                } @else {
+                                    (text)
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn render_movie_metadata(movie: &Movie, link: bool) -> impl Renderable {
+    let kind = ReviewedWorkKind::Movie;
+
+    render_metadata(vec![
+        ("Released", single(Some(movie.year.to_string()))),
+        ("Directed by", plain(&movie.directors)),
+        ("Starring", plain(&movie.cast)),
+        ("Runtime", single(movie.runtime.map(format_runtime))),
+        (
+            "Genres",
+            linked(&movie.genres, link, |genre| kind.genre_slug(genre)),
+        ),
+    ])
+}
+
+pub fn render_tv_show_metadata(tv_show: &TvShow, link: bool) -> impl Renderable {
+    let kind = ReviewedWorkKind::TvShow;
+
+    render_metadata(vec![
+        (
+            "First aired",
+            single(tv_show.year.map(|year| year.to_string())),
+        ),
+        ("Created by", plain(&tv_show.creators)),
+        (
+            "Seasons",
+            single(tv_show.season_count.map(|count| count.to_string())),
+        ),
+        (
+            "Genres",
+            linked(&tv_show.genres, link, |genre| kind.genre_slug(genre)),
+        ),
+    ])
+}
+
+pub fn render_book_metadata(book: &Book, link: bool) -> impl Renderable {
+    let kind = ReviewedWorkKind::Book;
+
+    render_metadata(vec![
+        (
+            "By",
+            linked(&book.authors, link, |author| kind.author_slug(author)),
+        ),
+        (
+            "First published",
+            single(book.first_publish_year.map(|year| year.to_string())),
+        ),
+        (
+            "Pages",
+            single(book.page_count.map(|count| count.to_string())),
+        ),
+        ("Subjects", plain(&book.subjects)),
+    ])
+}
+
+pub fn render_album_metadata(album: &MusicAlbum, link: bool) -> impl Renderable {
+    let kind = ReviewedWorkKind::Album;
+
+    render_metadata(vec![
+        ("By", single(Some(album.artist.clone()))),
+        ("Released", single(album.year.map(|year| year.to_string()))),
+        (
+            "Genres",
+            linked(&album.genres, link, |genre| kind.genre_slug(genre)),
+        ),
+    ])
+}
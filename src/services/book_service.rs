@@ -5,6 +5,8 @@ use tracing::{debug, info, instrument, warn};
 use url::Url;
 
 use crate::domain::models::book::{Book, BookID};
+use crate::domain::models::review::review_ids::ReviewIds;
+use crate::domain::models::review::review_overrides::{BookOverride, override_cover_cdn_file};
 use crate::prelude::*;
 
 use crate::services::cdn_service::CdnFile;
@@ -13,6 +15,11 @@ use crate::services::media_service::MediaService;
 use crate::{domain::models::tag::Tag, services::ServiceContext};
 
 const FILE_NAME: &str = "book_cache.json";
+const COVER_DIRECTORY: &str = "books";
+const SUBJECT_COUNT: usize = 10;
+
+// Bump when fetching more metadata, cached books from older versions are refetched
+const METADATA_VERSION: u32 = 1;
 
 const BLACK_LIBRARY_PUBLISHER: &str = "Black Library";
 const BLACK_LIBRARY_THE_PUBLISHER: &str = "Black Library, The";
@@ -39,6 +46,194 @@ struct OpenLibrarySearchResponse {
     docs: Vec<OpenLibraryBook>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct OpenLibraryWork {
+    covers: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenLibraryWorkReference {
+    key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenLibraryEdition {
+    covers: Option<Vec<i64>>,
+    works: Option<Vec<OpenLibraryWorkReference>>,
+}
+
+// Search has the author names and page counts the works endpoint doesn't
+#[derive(Debug, Clone, Deserialize)]
+struct OpenLibraryWorkDetails {
+    author_name: Option<Vec<String>>,
+    number_of_pages_median: Option<u32>,
+    first_publish_year: Option<u16>,
+    subject: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenLibraryWorkDetailsResponse {
+    docs: Vec<OpenLibraryWorkDetails>,
+}
+
+fn make_work_details_url(work_key: &str) -> Url {
+    Url::parse_with_params(
+        "https://openlibrary.org/search.json",
+        &[
+            ("q", format!("key:\"{}\"", work_key).as_str()),
+            (
+                "fields",
+                "key,author_name,number_of_pages_median,first_publish_year,subject",
+            ),
+        ],
+    )
+    .unwrap()
+}
+
+fn make_work_url(key: &str) -> Url {
+    format!("https://openlibrary.org/works/{}.json", key)
+        .parse()
+        .unwrap()
+}
+
+fn make_isbn_url(isbn: &str) -> Url {
+    format!("https://openlibrary.org/isbn/{}.json", isbn)
+        .parse()
+        .unwrap()
+}
+
+// Missing covers are listed as -1
+fn first_cover(covers: Option<Vec<i64>>) -> Option<u32> {
+    covers
+        .unwrap_or_default()
+        .into_iter()
+        .find(|cover| *cover > 0)
+        .map(|cover| cover as u32)
+}
+
+// e.g. /works/OL45804W is 45804
+fn work_id(work_key: &str) -> Option<u32> {
+    work_key
+        .trim_start_matches("/works/")
+        .strip_prefix("OL")?
+        .strip_suffix('W')?
+        .parse()
+        .ok()
+}
+
+fn work_link(key: &str) -> String {
+    format!("https://openlibrary.org/{}", key)
+}
+
+// Books cached before work_key was stored still link their cover to the work
+fn work_key_from_cover_link(book: &Book) -> Option<String> {
+    let key = book
+        .cover
+        .link_on_click
+        .as_deref()?
+        .strip_prefix("https://openlibrary.org/")?
+        .trim_start_matches('/');
+
+    Some(format!("/{}", key))
+}
+
+fn apply_details(book: &mut Book, details: Option<OpenLibraryWorkDetails>, author: &str) {
+    let details = details.unwrap_or(OpenLibraryWorkDetails {
+        author_name: None,
+        number_of_pages_median: None,
+        first_publish_year: None,
+        subject: None,
+    });
+
+    // Fall back to the author from the review
+    book.authors = match details.author_name {
+        Some(authors) if !authors.is_empty() => authors,
+        _ if !author.is_empty() => vec![author.to_string()],
+        _ => vec![],
+    };
+    book.page_count = details.number_of_pages_median;
+    book.first_publish_year = details.first_publish_year;
+    book.subjects = details
+        .subject
+        .unwrap_or_default()
+        .into_iter()
+        .take(SUBJECT_COUNT)
+        .collect();
+    book.metadata_version = METADATA_VERSION;
+}
+
+#[instrument(err, skip_all)]
+fn query_work_details_api(
+    ctx: &ServiceContext,
+    work_key: Option<&str>,
+) -> Result<Option<OpenLibraryWorkDetails>> {
+    let Some(work_key) = work_key else {
+        return Ok(None);
+    };
+
+    let response = ctx
+        .network
+        .download_json::<OpenLibraryWorkDetailsResponse>(&make_work_details_url(work_key))?;
+
+    Ok(response.docs.into_iter().next())
+}
+
+fn cached_book_matches_override(book: &Option<Book>, book_override: Option<&BookOverride>) -> bool {
+    let Some(book_override) = book_override else {
+        return true;
+    };
+
+    let Some(book) = book else {
+        return false;
+    };
+
+    let work_key = book
+        .work_key
+        .clone()
+        .or_else(|| work_key_from_cover_link(book));
+
+    book_override
+        .open_library
+        .as_ref()
+        .is_none_or(|key| work_key == Some(format!("/works/{}", key)))
+        && book_override.cover.as_ref().is_none_or(|cover| {
+            book.cover.original.file == override_cover_cdn_file(COVER_DIRECTORY, cover)
+        })
+}
+
+#[instrument(err, skip_all, fields(book.open_library=%key))]
+fn query_work_api(ctx: &ServiceContext, key: &str) -> Result<OpenLibraryBook> {
+    let work = ctx
+        .network
+        .download_json::<OpenLibraryWork>(&make_work_url(key))?;
+
+    Ok(OpenLibraryBook {
+        cover_i: first_cover(work.covers),
+        publisher: None,
+        author_name: None,
+        key: Some(format!("/works/{}", key)),
+    })
+}
+
+#[instrument(err, skip_all, fields(book.isbn=%isbn))]
+fn query_isbn_api(ctx: &ServiceContext, isbn: &str) -> Result<OpenLibraryBook> {
+    let edition = ctx
+        .network
+        .download_json::<OpenLibraryEdition>(&make_isbn_url(isbn))?;
+
+    Ok(OpenLibraryBook {
+        cover_i: first_cover(edition.covers),
+        publisher: None,
+        author_name: None,
+        key: edition
+            .works
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .map(|work| work.key),
+    })
+}
+
 fn make_search_url(title: &str) -> Url {
     let title = decode(title.as_bytes()).to_string().unwrap();
 
@@ -59,6 +254,7 @@ fn query_book_api(
     title: &str,
     author: &str,
     tags: &[Tag],
+    require_cover: bool,
 ) -> Result<Option<OpenLibraryBook>> {
     let url = &make_search_url(title);
 
@@ -83,7 +279,7 @@ fn query_book_api(
                 }
                 None => false,
             })
-            .filter(|doc| doc.cover_i.is_some())
+            .filter(|doc| !require_cover || doc.cover_i.is_some())
             .collect::<Vec<OpenLibraryBook>>();
 
         if !books.is_empty() {
@@ -102,7 +298,7 @@ fn query_book_api(
             }
             None => false,
         })
-        .filter(|doc| doc.cover_i.is_some())
+        .filter(|doc| !require_cover || doc.cover_i.is_some())
         .collect::<Vec<OpenLibraryBook>>();
 
     Ok(books.first().cloned())
@@ -124,46 +320,117 @@ impl BookService {
         title: &str,
         author: &str,
         tags: &[Tag],
+        ids: &ReviewIds,
     ) -> Result<Option<Book>> {
-        if let Some(book) = self.books.get(title) {
-            match book.clone() {
-                Some(book) => return Ok(Some(book.clone())),
-                None => {
-                    warn!("Did not find cover for book [{title}]");
-                    return Ok(None);
-                }
+        let book_override = ctx.review_overrides.book(title, author);
+
+        let key = match (book_override, ids.open_library_cache_key()) {
+            (None, Some(key)) => key,
+            _ => title.to_string(),
+        };
+
+        let cached = self
+            .books
+            .get(&key)
+            .map(|book| book.clone())
+            .filter(|book| cached_book_matches_override(book, book_override));
+
+        match cached {
+            Some(Some(book)) if book.metadata_version < METADATA_VERSION => {
+                // Falls back to the cached book, leaving its version to retry the refresh next build
+                return match self.refresh_details(ctx, key, book.clone(), author) {
+                    Ok(book) => Ok(Some(book)),
+                    Err(e) => {
+                        warn!("Unable to refresh details for book [{title}]: {e}");
+                        Ok(Some(book))
+                    }
+                };
+            }
+            Some(Some(book)) => return Ok(Some(book)),
+            Some(None) => {
+                warn!("Did not find cover for book [{title}]");
+                return Ok(None);
             }
+            None => {}
         }
 
-        let book = query_book_api(ctx, title, author, tags)?;
+        let work_key = book_override
+            .and_then(|o| o.open_library.as_ref())
+            .or(ids.open_library.as_ref());
+
+        // Books without a cover on OpenLibrary are only usable when the override has one
+        let override_cover = book_override.and_then(|o| o.cover.as_ref());
+
+        let book = match (work_key, &ids.isbn) {
+            (Some(work_key), _) => Some(query_work_api(ctx, work_key)?),
+            (None, Some(isbn)) => Some(query_isbn_api(ctx, isbn)?),
+            (None, None) => query_book_api(ctx, title, author, tags, override_cover.is_none())?,
+        };
 
         if let Some(book) = book
-            && let Some(cover_id) = book.cover_i
-            && let Some(key) = book.key
+            && let Some(work_key) = book.key
+            && let Some(id) = match (book.cover_i, override_cover) {
+                (Some(cover_id), _) => Some(BookID::OpenLibrary { id: cover_id }),
+                (None, Some(_)) => work_id(&work_key).map(|id| BookID::OpenLibraryWork { id }),
+                (None, None) => None,
+            }
         {
-            debug!("Found cover [{cover_id}] for book [{title}]");
-            let image_url = &format!("https://covers.openlibrary.org/b/id/{:?}-L.jpg", cover_id)
-                .parse()
-                .unwrap();
-
-            let cdn_file = CdnFile::from_path(&format!("books/{}-cover-400.jpg", cover_id));
-
-            let image = MediaService::image_from_url(
-                ctx,
-                image_url,
-                &cdn_file,
-                &format!("Cover for book {}", title),
-                Some(&format!("https://openlibrary.org/{}", key)),
-                None,
-            )?;
-
-            let book = Book {
+            debug!("Found book [{}] for [{title}]", id.as_string());
+            let alt = format!("Cover for book {}", title);
+            let link = work_link(&work_key);
+
+            let image = match override_cover {
+                Some(cover) => MediaService::image_from_file(
+                    ctx,
+                    &FileService::content(cover.into()),
+                    &override_cover_cdn_file(COVER_DIRECTORY, cover),
+                    &alt,
+                    Some(&link),
+                    None,
+                )?,
+                // Only books with a cover get this far without an override
+                None => {
+                    let image_url = &format!(
+                        "https://covers.openlibrary.org/b/id/{}-L.jpg",
+                        id.as_string()
+                    )
+                    .parse()
+                    .unwrap();
+
+                    let cdn_file = CdnFile::from_path(&format!(
+                        "{}/{}-cover-400.jpg",
+                        COVER_DIRECTORY,
+                        id.as_string()
+                    ));
+
+                    MediaService::image_from_url(
+                        ctx,
+                        image_url,
+                        &cdn_file,
+                        &alt,
+                        Some(&link),
+                        None,
+                    )?
+                }
+            };
+
+            let details = query_work_details_api(ctx, Some(&work_key))?;
+
+            let mut book = Book {
                 title: title.to_string(),
                 cover: image,
-                id: BookID::OpenLibrary { id: cover_id },
+                id,
+                work_key: Some(work_key),
+                authors: vec![],
+                page_count: None,
+                first_publish_year: None,
+                subjects: vec![],
+                metadata_version: 0,
             };
 
-            self.books.insert(title.to_string(), Some(book.clone()));
+            apply_details(&mut book, details, author);
+
+            self.books.insert(key, Some(book.clone()));
 
             self.file.write_json(&self.books.clone())?;
 
@@ -171,10 +438,62 @@ impl BookService {
         }
 
         warn!("Did not find cover for book [{title}]");
-        self.books.insert(title.to_string(), None);
+        self.books.insert(key, None);
 
         self.file.write_json(&self.books.clone())?;
 
         Ok(None)
     }
+
+    fn refresh_details(
+        &self,
+        ctx: &ServiceContext,
+        key: String,
+        mut book: Book,
+        author: &str,
+    ) -> Result<Book> {
+        if book.work_key.is_none() {
+            book.work_key = work_key_from_cover_link(&book);
+        }
+
+        let details = query_work_details_api(ctx, book.work_key.as_deref())?;
+
+        apply_details(&mut book, details, author);
+
+        self.books.insert(key, Some(book.clone()));
+
+        self.file.write_json(&self.books.clone())?;
+
+        Ok(book)
+    }
+
+    pub fn evict(&self, titles: &[String]) -> Result<usize> {
+        let count = self.books.len();
+
+        self.books.retain(|key, book| {
+            book.is_some()
+                && !titles
+                    .iter()
+                    .any(|title| key.eq_ignore_ascii_case(title.trim()))
+        });
+
+        self.file.write_json(&self.books.clone())?;
+
+        Ok(count - self.books.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_get_the_id_from_a_work_key() {
+        assert_eq!(work_id("/works/OL45804W"), Some(45804));
+        assert_eq!(
+            BookID::OpenLibraryWork { id: 45804 }.as_string(),
+            "OL45804W"
+        );
+        assert_eq!(work_id("/books/OL7353617M"), None);
+    }
 }
@@ -1,9 +1,8 @@
 use std::io::Cursor;
 
 use chrono::{DateTime, Utc};
+use comrak::{Arena, Options, nodes::NodeValue};
 use image::{DynamicImage, GenericImageView, ImageFormat, ImageReader};
-use once_cell::sync::Lazy;
-use regex::Regex;
 use tracing::{debug, info, instrument};
 use url::Url;
 
@@ -21,8 +20,30 @@ use crate::{
     },
     utils::resize_image::{ImageSize, resize_image},
 };
-pub static MARKDOWN_IMAGE_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"(?i)!\[([^\]]+)\]\(([^)]+)\)"#).unwrap());
+
+// Urls and alt text of every image, parsed the same way the markdown is rendered so titles
+// (![alt](url "title")) and escaping don't end up in the url
+fn markdown_images(markdown: &str) -> Vec<(String, String)> {
+    let arena = Arena::new();
+    let root = comrak::parse_document(&arena, markdown, &Options::default());
+
+    root.descendants()
+        .filter_map(|node| match &node.data.borrow().value {
+            NodeValue::Image(link) => {
+                let alt = node
+                    .descendants()
+                    .filter_map(|child| match &child.data.borrow().value {
+                        NodeValue::Text(text) => Some(text.to_string()),
+                        _ => None,
+                    })
+                    .collect::<String>();
+
+                Some((link.url.clone(), alt))
+            }
+            _ => None,
+        })
+        .collect()
+}
 
 pub struct MediaService;
 
@@ -45,14 +66,22 @@ impl MediaService {
         Ok(data)
     }
 
-    fn read_or_download_image(
-        ctx: &ServiceContext,
-        url: &Url,
-        cdn_file: &CdnFile,
-    ) -> Result<DynamicImage> {
-        let original_bytes = Self::read_or_download_file(ctx, url, cdn_file)?;
+    fn read_or_copy_file(source: &impl ReadableFile, cdn_path: &CdnFile) -> Result<Vec<u8>> {
+        let file = cdn_path.as_cache_file();
 
-        let original_image = ImageReader::new(Cursor::new(&original_bytes))
+        if file.exists()? {
+            return file.read();
+        }
+
+        let data = source.read()?;
+
+        file.write(&data)?;
+
+        Ok(data)
+    }
+
+    fn decode_image(original_bytes: &[u8]) -> Result<DynamicImage> {
+        let original_image = ImageReader::new(Cursor::new(original_bytes))
             .with_guessed_format()
             .map_err(ImageError::parse_format_error)?
             .decode()
@@ -119,6 +148,53 @@ impl MediaService {
         link_on_click: Option<&String>,
         date: Option<DateTime<Utc>>,
     ) -> Result<Image> {
+        Self::process_image(
+            ctx,
+            url.as_ref(),
+            cdn_file,
+            alt,
+            link_on_click,
+            date,
+            || Self::read_or_download_file(ctx, url, cdn_file),
+        )
+    }
+
+    // For media we already have locally, such as exported archives
+    #[instrument(err, skip_all, fields(file=&file.to_string()))]
+    pub fn image_from_file<F>(
+        ctx: &ServiceContext,
+        file: &F,
+        cdn_file: &CdnFile,
+        alt: &str,
+        link_on_click: Option<&String>,
+        date: Option<DateTime<Utc>>,
+    ) -> Result<Image>
+    where
+        F: ReadableFile + std::fmt::Display,
+    {
+        Self::process_image(
+            ctx,
+            &file.to_string(),
+            cdn_file,
+            alt,
+            link_on_click,
+            date,
+            || Self::read_or_copy_file(file, cdn_file),
+        )
+    }
+
+    fn process_image<R>(
+        ctx: &ServiceContext,
+        source: &str,
+        cdn_file: &CdnFile,
+        alt: &str,
+        link_on_click: Option<&String>,
+        date: Option<DateTime<Utc>>,
+        read_original: R,
+    ) -> Result<Image>
+    where
+        R: FnOnce() -> Result<Vec<u8>>,
+    {
         let large_cdn_file =
             cdn_file.add_suffix_to_file_name(&format!("-{}", ImageSize::Large.as_str()));
         let small_cdn_file =
@@ -132,7 +208,7 @@ impl MediaService {
 
         // If all exist, then don't process
         if original_file.exists()? && large_file.exists()? && small_file.exists()? {
-            debug!("Image already processed [{:?}]", &url.to_string());
+            debug!("Image already processed [{:?}]", source);
             let original_size = Self::read_image_size(&original_file)?;
             let large_size = Self::read_image_size(&large_file)?;
             let small_size = Self::read_image_size(&small_file)?;
@@ -156,9 +232,9 @@ impl MediaService {
             });
         }
 
-        info!("Processing image from URL [{:?}]", &url.to_string());
+        info!("Processing image [{:?}]", source);
 
-        let original_image = Self::read_or_download_image(ctx, url, cdn_file)?;
+        let original_image = Self::decode_image(&read_original()?)?;
 
         ctx.cdn.upload_file(&cdn_file.as_cache_file(), cdn_file)?;
 
@@ -189,16 +265,16 @@ impl MediaService {
     ) -> Result<Vec<Image>> {
         let mut media = vec![];
 
-        let regex = MARKDOWN_IMAGE_REGEX.clone();
-
-        for cap in regex.captures_iter(markdown) {
-            let alt = cap.get(1).map_or("", |m| m.as_str());
-            let url = cap.get(2).map_or("", |m| m.as_str());
+        // Images without alt text are left as they are
+        for (url, alt) in markdown_images(markdown) {
+            if alt.is_empty() {
+                continue;
+            }
 
             let url: Url = url.parse().unwrap();
             let cdn_file = CdnFile::from_path(url.path());
 
-            let image = Self::image_from_url(ctx, &url, &cdn_file, alt, link_on_click, date)?;
+            let image = Self::image_from_url(ctx, &url, &cdn_file, &alt, link_on_click, date)?;
 
             media.push(image);
         }
@@ -206,3 +282,29 @@ impl MediaService {
         Ok(media)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_find_images_with_titles_in_markdown() {
+        let images = markdown_images(
+            "Some text\n\n![A painted knight](https://cdn.geekyaubergine.com/2024/knight.jpg \"Finished at last\")\n\n![](https://cdn.geekyaubergine.com/2024/no-alt.jpg)",
+        );
+
+        assert_eq!(
+            images,
+            vec![
+                (
+                    "https://cdn.geekyaubergine.com/2024/knight.jpg".to_string(),
+                    "A painted knight".to_string()
+                ),
+                (
+                    "https://cdn.geekyaubergine.com/2024/no-alt.jpg".to_string(),
+                    "".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,10 +1,16 @@
 use std::sync::Arc;
 
 use crate::{
+    domain::models::review::review_overrides::ReviewOverrides,
     prelude::*,
     services::{
-        book_service::BookService, cdn_service::CdnService, movie_service::MovieService,
-        network_service::NetworkService, query_limiter_service::QueryLimitingService,
+        book_service::BookService,
+        cdn_service::CdnService,
+        file_service::{FileService, ReadableFile},
+        movie_service::MovieService,
+        music_service::MusicService,
+        network_service::NetworkService,
+        query_limiter_service::QueryLimitingService,
         ts_show_service::TvShowService,
     },
 };
@@ -14,11 +20,23 @@ pub mod cdn_service;
 pub mod file_service;
 pub mod media_service;
 pub mod movie_service;
+pub mod music_service;
 pub mod network_service;
 pub mod page_renderer;
 pub mod query_limiter_service;
 pub mod ts_show_service;
 
+const REVIEW_OVERRIDES_FILE_NAME: &str = "review_overrides.yml";
+
+fn read_review_overrides() -> Result<ReviewOverrides> {
+    let file = FileService::content(REVIEW_OVERRIDES_FILE_NAME.into());
+
+    match file.exists()? {
+        true => file.read_yaml(),
+        false => Ok(ReviewOverrides::default()),
+    }
+}
+
 #[derive(Debug)]
 pub struct ServiceContext {
     pub network: Arc<NetworkService>,
@@ -27,6 +45,8 @@ pub struct ServiceContext {
     pub books: Arc<BookService>,
     pub movies: Arc<MovieService>,
     pub tv_shows: Arc<TvShowService>,
+    pub music: Arc<MusicService>,
+    pub review_overrides: Arc<ReviewOverrides>,
 }
 
 impl ServiceContext {
@@ -38,6 +58,8 @@ impl ServiceContext {
             books: Arc::new(BookService::new()?),
             movies: Arc::new(MovieService::new()?),
             tv_shows: Arc::new(TvShowService::new()?),
+            music: Arc::new(MusicService::new()?),
+            review_overrides: Arc::new(read_review_overrides()?),
         })
     }
 }
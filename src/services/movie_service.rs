@@ -6,7 +6,10 @@ use tracing::{instrument, warn};
 use url::Url;
 
 use crate::config::CONFIG;
+use crate::domain::models::image::Image;
 use crate::domain::models::movie::{Movie, MovieId};
+use crate::domain::models::review::review_ids::ReviewIds;
+use crate::domain::models::review::review_overrides::{MovieOverride, override_cover_cdn_file};
 use crate::error::MovieError;
 use crate::prelude::*;
 
@@ -20,6 +23,11 @@ const FILE_NAME: &str = "movie_cache.json";
 
 const TMDB_LINK_URL: &str = "https://www.themoviedb.org/movie/";
 const TMDB_IMAGE_URL: &str = "https://image.tmdb.org/t/p/w200";
+const POSTER_DIRECTORY: &str = "movies";
+const CAST_SIZE: usize = 5;
+
+// Bump when fetching more metadata, cached movies from older versions are refetched
+const METADATA_VERSION: u32 = 1;
 
 fn name_and_year_to_key(name: &str, year: u16) -> String {
     format!("{} ({})", name, year)
@@ -38,12 +46,56 @@ fn make_search_url(title: &str, year: u16) -> Url {
     .unwrap()
 }
 
+fn make_details_url(id: u32) -> Url {
+    format!(
+        "https://api.themoviedb.org/3/movie/{}?api_key={}&append_to_response=credits",
+        id, CONFIG.tmdb.key
+    )
+    .parse()
+    .unwrap()
+}
+
+fn make_find_by_imdb_url(imdb_id: &str) -> Url {
+    format!(
+        "https://api.themoviedb.org/3/find/{}?api_key={}&external_source=imdb_id",
+        imdb_id, CONFIG.tmdb.key
+    )
+    .parse()
+    .unwrap()
+}
+
+fn key_has_title(key: &str, title: &str) -> bool {
+    let key = key.to_lowercase();
+    let title = title.trim().to_lowercase();
+
+    key == title || key.starts_with(&format!("{} (", title))
+}
+
+// A cached movie is only used if it agrees with the override, so editing the override refetches it
+fn cached_movie_matches_override(
+    movie: &Option<Movie>,
+    movie_override: Option<&MovieOverride>,
+) -> bool {
+    let Some(movie_override) = movie_override else {
+        return true;
+    };
+
+    let Some(movie) = movie else {
+        return false;
+    };
+
+    movie_override
+        .tmdb
+        .is_none_or(|id| movie.id == MovieId::tmdb(id))
+        && movie_override.cover.as_ref().is_none_or(|cover| {
+            movie.poster.original.file == override_cover_cdn_file(POSTER_DIRECTORY, cover)
+        })
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct TmdbSearchResponseSingle {
     id: u32,
-    title: String,
     poster_path: Option<String>,
-    release_date: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -51,6 +103,74 @@ struct TmdbSearchResponse {
     results: Vec<TmdbSearchResponseSingle>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbFindResponse {
+    movie_results: Vec<TmdbSearchResponseSingle>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbGenre {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbCastMember {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbCrewMember {
+    name: String,
+    job: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TmdbCredits {
+    #[serde(default)]
+    cast: Vec<TmdbCastMember>,
+    #[serde(default)]
+    crew: Vec<TmdbCrewMember>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbMovieDetails {
+    id: u32,
+    title: String,
+    poster_path: Option<String>,
+    release_date: String,
+    #[serde(default)]
+    genres: Vec<TmdbGenre>,
+    runtime: Option<u16>,
+    #[serde(default)]
+    credits: TmdbCredits,
+}
+
+fn apply_details(movie: &mut Movie, details: &TmdbMovieDetails) {
+    movie.genres = details
+        .genres
+        .iter()
+        .map(|genre| genre.name.clone())
+        .collect();
+    // TMDB uses 0 when it doesn't know
+    movie.runtime = details.runtime.filter(|runtime| *runtime > 0);
+    movie.directors = details
+        .credits
+        .crew
+        .iter()
+        .filter(|member| member.job == "Director")
+        .map(|member| member.name.clone())
+        .collect();
+    // Already in billing order
+    movie.cast = details
+        .credits
+        .cast
+        .iter()
+        .take(CAST_SIZE)
+        .map(|member| member.name.clone())
+        .collect();
+    movie.metadata_version = METADATA_VERSION;
+}
+
 #[derive(Debug)]
 pub struct MovieService {
     file: ArchiveFile,
@@ -66,65 +186,131 @@ impl MovieService {
         Ok(Self { file, movies: data })
     }
 
+    fn poster_image(
+        ctx: &ServiceContext,
+        movie: &TmdbMovieDetails,
+        movie_override: Option<&MovieOverride>,
+    ) -> Result<Image> {
+        let alt = format!("{} movie poster", movie.title);
+        let link = format!("{}{}", TMDB_LINK_URL, movie.id);
+
+        if let Some(cover) = movie_override.and_then(|o| o.cover.as_ref()) {
+            return MediaService::image_from_file(
+                ctx,
+                &FileService::content(cover.into()),
+                &override_cover_cdn_file(POSTER_DIRECTORY, cover),
+                &alt,
+                Some(&link),
+                None,
+            );
+        }
+
+        let poster = movie
+            .poster_path
+            .as_ref()
+            .ok_or(MovieError::movie_has_no_poster(movie.id))?;
+
+        let image_url = &format!("{}{}", TMDB_IMAGE_URL, poster).parse().unwrap();
+
+        let cdn_file =
+            CdnFile::from_path(&format!("{}/{}-poster-200.jpg", POSTER_DIRECTORY, movie.id));
+
+        MediaService::image_from_url(ctx, image_url, &cdn_file, &alt, Some(&link), None)
+    }
+
     #[instrument(err, skip_all, fields(movie.title=%title, movie.year=&year))]
     pub fn find_movie(
         &self,
         ctx: &ServiceContext,
         title: &str,
         year: u16,
+        ids: &ReviewIds,
     ) -> Result<Option<Movie>> {
-        let key = name_and_year_to_key(title, year);
-
-        if let Some(movie) = self.movies.get(&key) {
-            match movie.clone() {
-                Some(movie) => return Ok(Some(movie.clone())),
-                None => {
-                    warn!("Did not find cover for movie [{title} - {year}]");
-                    return Ok(None);
-                }
+        let movie_override = ctx.review_overrides.movie(title, year);
+
+        let title_key = name_and_year_to_key(title, year);
+
+        let key = match (movie_override, ids.tmdb_cache_key()) {
+            (None, Some(key)) => key,
+            _ => title_key.clone(),
+        };
+
+        let cached = self.movies.get(&key).map(|movie| movie.clone());
+
+        let cached = match cached {
+            Some(movie) => Some(movie),
+            None => self
+                .move_from_title_key(ctx, &key, &title_key, ids)?
+                .map(Some),
+        }
+        .filter(|movie| cached_movie_matches_override(movie, movie_override));
+
+        match cached {
+            Some(Some(movie)) if movie.metadata_version < METADATA_VERSION => {
+                // Keep what's cached if the refresh fails, it's tried again next build
+                return match self.refresh_details(ctx, key, movie.clone()) {
+                    Ok(movie) => Ok(Some(movie)),
+                    Err(e) => {
+                        warn!("Unable to refresh details for movie [{title} - {year}]: {e}");
+                        Ok(Some(movie))
+                    }
+                };
             }
+            Some(Some(movie)) => return Ok(Some(movie)),
+            Some(None) => {
+                warn!("Did not find cover for movie [{title} - {year}]");
+                return Ok(None);
+            }
+            None => {}
         }
 
-        let results = ctx
-            .network
-            .download_json::<TmdbSearchResponse>(&make_search_url(title, year))?;
-
-        let results = results
-            .results
-            .iter()
-            .filter(|r| r.poster_path.is_some())
-            .collect::<Vec<_>>();
-
-        match results.first() {
-            Some(movie) => {
-                let poster = movie
-                    .poster_path
-                    .as_ref()
-                    .ok_or(MovieError::movie_has_no_poster(movie.id))?;
-
-                let image_url = &format!("{}{}", TMDB_IMAGE_URL, poster).parse().unwrap();
-
-                let cdn_file = CdnFile::from_path(&format!("movies/{}-poster-200.jpg", movie.id));
-
-                let image = MediaService::image_from_url(
-                    ctx,
-                    image_url,
-                    &cdn_file,
-                    &format!("{} movie poster", movie.title),
-                    Some(&format!("https://www.themoviedb.org/movie/{}", movie.id)),
-                    None,
-                )?;
-
-                let date = parse_date(&movie.release_date)?;
-
-                let movie = Movie {
-                    title: movie.title.clone(),
+        // Without a poster there's nothing to show, unless the override brings its own
+        let has_cover_override = movie_override.is_some_and(|o| o.cover.is_some());
+
+        // Overrides win over ids in the post, which win over searching by title
+        let tmdb_id = match (movie_override.and_then(|o| o.tmdb).or(ids.tmdb), &ids.imdb) {
+            (Some(id), _) => Some(id),
+            (None, Some(imdb_id)) => ctx
+                .network
+                .download_json::<TmdbFindResponse>(&make_find_by_imdb_url(imdb_id))?
+                .movie_results
+                .iter()
+                .find(|r| has_cover_override || r.poster_path.is_some())
+                .map(|movie| movie.id),
+            (None, None) => ctx
+                .network
+                .download_json::<TmdbSearchResponse>(&make_search_url(title, year))?
+                .results
+                .iter()
+                .find(|r| has_cover_override || r.poster_path.is_some())
+                .map(|movie| movie.id),
+        };
+
+        match tmdb_id {
+            Some(tmdb_id) => {
+                let details = ctx
+                    .network
+                    .download_json::<TmdbMovieDetails>(&make_details_url(tmdb_id))?;
+
+                let image = Self::poster_image(ctx, &details, movie_override)?;
+
+                let date = parse_date(&details.release_date)?;
+
+                let mut movie = Movie {
+                    title: details.title.clone(),
                     year: date.year() as u16,
                     poster: image,
-                    id: MovieId::tmdb(movie.id),
-                    link: format!("{}{}", TMDB_LINK_URL, movie.id).parse().unwrap(),
+                    id: MovieId::tmdb(details.id),
+                    link: format!("{}{}", TMDB_LINK_URL, details.id).parse().unwrap(),
+                    genres: vec![],
+                    runtime: None,
+                    directors: vec![],
+                    cast: vec![],
+                    metadata_version: 0,
                 };
 
+                apply_details(&mut movie, &details);
+
                 self.movies.insert(key, Some(movie.clone()));
 
                 self.file.write_json(&self.movies.clone())?;
@@ -133,7 +319,7 @@ impl MovieService {
             }
             None => {
                 warn!("Did not find cover for movie [{title}]");
-                self.movies.insert(title.to_string(), None);
+                self.movies.insert(key, None);
 
                 self.file.write_json(&self.movies.clone())?;
 
@@ -141,4 +327,79 @@ impl MovieService {
             }
         }
     }
+
+    // Movies cached by title before ids were read from reviews are moved to the id key rather
+    // than fetched again, as long as they're the same movie the post's id points at. IMDb ids
+    // are looked up on TMDB first, so a remake found by title isn't kept under the wrong id
+    fn move_from_title_key(
+        &self,
+        ctx: &ServiceContext,
+        key: &str,
+        title_key: &str,
+        ids: &ReviewIds,
+    ) -> Result<Option<Movie>> {
+        if key == title_key {
+            return Ok(None);
+        }
+
+        let Some(movie) = self.movies.get(title_key).and_then(|movie| movie.clone()) else {
+            return Ok(None);
+        };
+
+        let tmdb_id = match (ids.tmdb, &ids.imdb) {
+            (Some(id), _) => Some(id),
+            (None, Some(imdb_id)) => ctx
+                .network
+                .download_json::<TmdbFindResponse>(&make_find_by_imdb_url(imdb_id))?
+                .movie_results
+                .first()
+                .map(|movie| movie.id),
+            (None, None) => None,
+        };
+
+        if tmdb_id.is_none_or(|id| movie.id != MovieId::tmdb(id)) {
+            return Ok(None);
+        }
+
+        self.movies.insert(key.to_string(), Some(movie.clone()));
+
+        self.file.write_json(&self.movies.clone())?;
+
+        Ok(Some(movie))
+    }
+
+    // Migrates a movie cached before the current metadata version, keeping its poster
+    fn refresh_details(
+        &self,
+        ctx: &ServiceContext,
+        key: String,
+        mut movie: Movie,
+    ) -> Result<Movie> {
+        let MovieId::Tmdb { id } = movie.id;
+
+        let details = ctx
+            .network
+            .download_json::<TmdbMovieDetails>(&make_details_url(id))?;
+
+        apply_details(&mut movie, &details);
+
+        self.movies.insert(key, Some(movie.clone()));
+
+        self.file.write_json(&self.movies.clone())?;
+
+        Ok(movie)
+    }
+
+    // Drops cached misses so they're searched for again, along with any of the given titles
+    pub fn evict(&self, titles: &[String]) -> Result<usize> {
+        let count = self.movies.len();
+
+        self.movies.retain(|key, movie| {
+            movie.is_some() && !titles.iter().any(|title| key_has_title(key, title))
+        });
+
+        self.file.write_json(&self.movies.clone())?;
+
+        Ok(count - self.movies.len())
+    }
 }
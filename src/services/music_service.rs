@@ -0,0 +1,248 @@
+use dashmap::DashMap;
+use serde::Deserialize;
+use tracing::{instrument, warn};
+use url::Url;
+
+use crate::domain::models::music_album::{MusicAlbum, MusicAlbumId};
+use crate::error::{Error, NetworkError};
+use crate::prelude::*;
+
+use crate::services::ServiceContext;
+use crate::services::cdn_service::CdnFile;
+use crate::services::file_service::{ArchiveFile, FileService, ReadableFile, WritableFile};
+use crate::services::media_service::MediaService;
+
+const FILE_NAME: &str = "music_cache.json";
+const MUSICBRAINZ_LINK_URL: &str = "https://musicbrainz.org/release-group/";
+const COVER_DIRECTORY: &str = "music";
+const GENRE_COUNT: usize = 5;
+
+#[derive(Debug, Clone, Deserialize)]
+struct MusicBrainzArtistCredit {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MusicBrainzGenre {
+    name: String,
+    count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MusicBrainzReleaseGroup {
+    id: String,
+    title: String,
+    #[serde(rename = "first-release-date")]
+    first_release_date: Option<String>,
+    #[serde(rename = "artist-credit", default)]
+    artist_credit: Vec<MusicBrainzArtistCredit>,
+    #[serde(default)]
+    genres: Vec<MusicBrainzGenre>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MusicBrainzSearchResponse {
+    #[serde(rename = "release-groups")]
+    release_groups: Vec<MusicBrainzReleaseGroup>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CoverArtThumbnails {
+    large: Option<Url>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CoverArtImage {
+    front: bool,
+    image: Url,
+    thumbnails: CoverArtThumbnails,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CoverArtResponse {
+    images: Vec<CoverArtImage>,
+}
+
+fn make_search_url(title: &str, artist: &str) -> Url {
+    Url::parse_with_params(
+        "https://musicbrainz.org/ws/2/release-group/",
+        &[
+            (
+                "query",
+                format!(
+                    "releasegroup:\"{}\" AND artist:\"{}\"",
+                    title.replace('"', ""),
+                    artist.replace('"', "")
+                )
+                .as_str(),
+            ),
+            ("fmt", "json"),
+            ("limit", "5"),
+        ],
+    )
+    .unwrap()
+}
+
+// Search results don't include genres
+fn make_release_group_url(id: &str) -> Url {
+    format!(
+        "https://musicbrainz.org/ws/2/release-group/{}?inc=artist-credits+genres&fmt=json",
+        id
+    )
+    .parse()
+    .unwrap()
+}
+
+fn make_cover_art_url(id: &str) -> Url {
+    format!("https://coverartarchive.org/release-group/{}", id)
+        .parse()
+        .unwrap()
+}
+
+#[derive(Debug)]
+pub struct MusicService {
+    file: ArchiveFile,
+    albums: DashMap<String, Option<MusicAlbum>>,
+}
+
+impl MusicService {
+    pub fn new() -> Result<Self> {
+        let file = FileService::archive(FILE_NAME.into());
+
+        let data = file.read_json_or_default()?;
+
+        Ok(Self { file, albums: data })
+    }
+
+    // The archive 404s for albums without art, which is treated as not found
+    fn cover_url(ctx: &ServiceContext, id: &str) -> Result<Option<Url>> {
+        // Only a missing cover is cached as no cover, anything else is retried next build
+        let response = match ctx
+            .network
+            .download_json::<CoverArtResponse>(&make_cover_art_url(id))
+        {
+            Ok(response) => response,
+            Err(Error::NetworkError(NetworkError::NotFound(_))) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ok(response
+            .images
+            .into_iter()
+            .find(|image| image.front)
+            .map(|front| front.thumbnails.large.unwrap_or(front.image)))
+    }
+
+    #[instrument(err, skip_all, fields(album.title=%title, album.artist=%artist))]
+    pub fn find_album(
+        &self,
+        ctx: &ServiceContext,
+        title: &str,
+        artist: &str,
+        musicbrainz: Option<&str>,
+    ) -> Result<Option<MusicAlbum>> {
+        // Lookups by id are cached under the id, like the other review services
+        let key = match musicbrainz {
+            Some(id) => format!("musicbrainz:{}", id),
+            None => format!("{} by {}", title, artist),
+        };
+
+        if let Some(album) = self.albums.get(&key).map(|album| album.clone()) {
+            if album.is_none() {
+                warn!("Did not find cover for album [{title}]");
+            }
+            return Ok(album);
+        }
+
+        let id = match musicbrainz {
+            Some(id) => Some(id.to_string()),
+            None => ctx
+                .network
+                .download_json::<MusicBrainzSearchResponse>(&make_search_url(title, artist))?
+                .release_groups
+                .into_iter()
+                .next()
+                .map(|release_group| release_group.id),
+        };
+
+        let album = match id {
+            Some(id) => self.fetch_album(ctx, &id)?,
+            None => None,
+        };
+
+        if album.is_none() {
+            warn!("Did not find cover for album [{title}]");
+        }
+
+        self.albums.insert(key, album.clone());
+
+        self.file.write_json(&self.albums.clone())?;
+
+        Ok(album)
+    }
+
+    fn fetch_album(&self, ctx: &ServiceContext, id: &str) -> Result<Option<MusicAlbum>> {
+        let release_group = ctx
+            .network
+            .download_json::<MusicBrainzReleaseGroup>(&make_release_group_url(id))?;
+
+        let Some(cover_url) = Self::cover_url(ctx, id)? else {
+            return Ok(None);
+        };
+
+        let artist = release_group
+            .artist_credit
+            .iter()
+            .map(|credit| credit.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        let link = format!("{}{}", MUSICBRAINZ_LINK_URL, release_group.id);
+
+        let cover = MediaService::image_from_url(
+            ctx,
+            &cover_url,
+            &CdnFile::from_path(&format!("{}/{}-cover-500.jpg", COVER_DIRECTORY, id)),
+            &format!("Cover for album {} by {}", release_group.title, artist),
+            Some(&link),
+            None,
+        )?;
+
+        let mut genres = release_group.genres;
+        genres.sort_by_key(|genre| std::cmp::Reverse(genre.count));
+
+        Ok(Some(MusicAlbum {
+            title: release_group.title,
+            artist,
+            cover,
+            id: MusicAlbumId::musicbrainz(&release_group.id),
+            link: link.parse().unwrap(),
+            year: release_group
+                .first_release_date
+                .as_ref()
+                .and_then(|date| date.get(..4))
+                .and_then(|year| year.parse().ok()),
+            genres: genres
+                .into_iter()
+                .take(GENRE_COUNT)
+                .map(|genre| genre.name)
+                .collect(),
+        }))
+    }
+
+    pub fn evict(&self, titles: &[String]) -> Result<usize> {
+        let count = self.albums.len();
+
+        self.albums.retain(|key, album| {
+            album.is_some()
+                && !titles.iter().any(|title| {
+                    key.to_lowercase()
+                        .starts_with(&format!("{} by ", title.trim().to_lowercase()))
+                })
+        });
+
+        self.file.write_json(&self.albums.clone())?;
+
+        Ok(count - self.albums.len())
+    }
+}
@@ -6,7 +6,10 @@ use governor::{
     clock::DefaultClock,
     state::{InMemoryState, NotKeyed},
 };
-use reqwest::blocking::{Client, Response};
+use reqwest::{
+    StatusCode,
+    blocking::{Client, Response},
+};
 use serde::de::DeserializeOwned;
 use tracing::instrument;
 use url::Url;
@@ -15,6 +18,17 @@ use crate::{config::CONFIG, error::NetworkError, prelude::*};
 
 const ALLOW_LIST: [&str; 1] = [CONFIG.cdn_url];
 
+// MusicBrainz blocks clients without a descriptive user agent and contact address
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " ( https://zoeaubert.me )"
+);
+
+// Domains that ask for fewer requests than the default
+const SLOW_DOMAINS: [&str; 1] = ["musicbrainz.org"];
+
 fn is_on_allow_list(domain: &str) -> bool {
     ALLOW_LIST
         .iter()
@@ -26,15 +40,18 @@ type DomainLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
 #[derive(Debug)]
 struct DomainRateLimiter {
     quota: Quota,
+    slow_quota: Quota,
     limiters: DashMap<String, Arc<DomainLimiter>>,
 }
 
 impl DomainRateLimiter {
     pub fn new() -> Self {
         let quota = Quota::per_second(NonZeroU32::new(5).unwrap());
+        let slow_quota = Quota::per_second(NonZeroU32::new(1).unwrap());
 
         Self {
             quota,
+            slow_quota,
             limiters: DashMap::new(),
         }
     }
@@ -44,7 +61,12 @@ impl DomainRateLimiter {
             return limiter.clone();
         }
 
-        let limiter = Arc::new(RateLimiter::direct(self.quota));
+        let quota = match SLOW_DOMAINS.contains(&domain) {
+            true => self.slow_quota,
+            false => self.quota,
+        };
+
+        let limiter = Arc::new(RateLimiter::direct(quota));
         self.limiters.insert(domain.to_string(), limiter.clone());
         limiter
     }
@@ -96,7 +118,10 @@ impl Default for NetworkService {
 impl NetworkService {
     pub fn new() -> Self {
         Self {
-            client: reqwest::blocking::Client::new(),
+            client: reqwest::blocking::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap(),
             limiter: DomainRateLimiter::new(),
         }
     }
@@ -112,6 +137,10 @@ impl NetworkService {
     {
         let resp = get(&self.client, &self.limiter, url)?;
 
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Err(NetworkError::not_found(url));
+        }
+
         let json = resp.json::<J>().map_err(NetworkError::fetch_error)?;
 
         Ok(json)
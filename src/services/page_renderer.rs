@@ -1,6 +1,6 @@
 use crate::{
-    domain::models::slug::Slug,
-    error::{Error, TemplateError},
+    domain::models::{post_status::PostStatus, site_config::SiteLinks, slug::Slug},
+    error::{Error, SiteBuildError, TemplateError},
     prelude::*,
     services::file_service::WritableFile,
 };
@@ -11,6 +11,7 @@ use hypertext::{Renderable, Rendered};
 use tracing::debug;
 
 use std::{
+    collections::HashSet,
     path::PathBuf,
     sync::{Arc, RwLock},
 };
@@ -32,27 +33,43 @@ pub struct SitemapTemplate {
 }
 
 pub struct PageRenderer {
+    site_links: SiteLinks,
     site_map_pages: Arc<RwLock<Vec<SiteMapPage>>>,
-}
-
-impl Default for PageRenderer {
-    fn default() -> Self {
-        Self::new()
-    }
+    // Every file written this build, so two pages claiming the same route fail the build rather
+    // than one silently replacing the other
+    rendered_paths: Arc<RwLock<HashSet<String>>>,
 }
 
 impl PageRenderer {
-    pub fn new() -> Self {
+    pub fn new(site_links: SiteLinks) -> Self {
         Self {
+            site_links,
             site_map_pages: Arc::new(RwLock::new(Vec::new())),
+            rendered_paths: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
+    // Every page has the same navigation
+    pub fn site_links(&self) -> &SiteLinks {
+        &self.site_links
+    }
+
     pub fn render_page(
         &self,
         slug: &Slug,
         rendered: impl Renderable,
         last_modified: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.render_post_page(slug, rendered, last_modified, PostStatus::Published)
+    }
+
+    // Unlisted posts still get a page, they're just not advertised in the sitemap
+    pub fn render_post_page(
+        &self,
+        slug: &Slug,
+        rendered: impl Renderable,
+        last_modified: Option<DateTime<Utc>>,
+        status: PostStatus,
     ) -> Result<()> {
         debug!("Rendering page: {}", slug);
 
@@ -62,6 +79,10 @@ impl PageRenderer {
 
         self.save_file(&path, rendered.as_inner())?;
 
+        if !status.is_listed() {
+            return Ok(());
+        }
+
         self.site_map_pages
             .write()
             .map_err(|_| Error::Unknown())?
@@ -117,7 +138,40 @@ impl PageRenderer {
         Ok(pages.len())
     }
 
+    fn claim_path(&self, path: &str) -> Result<()> {
+        let is_new_path = self
+            .rendered_paths
+            .write()
+            .map_err(|_| Error::Unknown())?
+            .insert(path.trim_start_matches('/').to_string());
+
+        match is_new_path {
+            true => Ok(()),
+            false => Err(SiteBuildError::path_rendered_twice(path)),
+        }
+    }
+
     fn save_file(&self, path: &str, rendered: &str) -> Result<()> {
+        self.claim_path(path)?;
+
         FileService::output(PathBuf::from(path)).write_text(rendered)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_fail_when_two_pages_render_to_the_same_path() {
+        let renderer = PageRenderer::new(SiteLinks::new(vec![], vec![]));
+
+        assert!(renderer.claim_path("/now/index.html").is_ok());
+        assert!(renderer.claim_path("/colophon/index.html").is_ok());
+
+        assert!(matches!(
+            renderer.claim_path("now/index.html"),
+            Err(Error::SiteBuildError(SiteBuildError::PathRenderedTwice(..)))
+        ));
+    }
+}
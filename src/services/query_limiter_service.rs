@@ -41,14 +41,19 @@ impl QueryLimitingService {
         };
 
         if can_query {
-            self.data.queries.insert(query.to_string(), Utc::now());
-
-            self.file.write_json(&self.data)?;
+            self.record_query(query)?;
         }
 
         Ok(can_query)
     }
 
+    // For queries that ran regardless of the limit, so the next limited run waits again
+    pub fn record_query(&self, query: &str) -> Result<()> {
+        self.data.queries.insert(query.to_string(), Utc::now());
+
+        self.file.write_json(&self.data)
+    }
+
     pub fn can_query_within_hour(&self, query: &str) -> Result<bool> {
         self.can_query(query, &ONE_HOUR_PERIOD)
     }
@@ -5,6 +5,9 @@ use tracing::{instrument, warn};
 use url::Url;
 
 use crate::config::CONFIG;
+use crate::domain::models::image::Image;
+use crate::domain::models::review::review_ids::ReviewIds;
+use crate::domain::models::review::review_overrides::{TvShowOverride, override_cover_cdn_file};
 use crate::domain::models::tv_show::{TvShow, TvShowId};
 use crate::error::TvShowsError;
 use crate::prelude::*;
@@ -17,6 +20,10 @@ use crate::services::media_service::MediaService;
 const FILE_NAME: &str = "tv_shows_cache.json";
 const TMDB_LINK_URL: &str = "https://www.themoviedb.org/tv/";
 const TMDB_IMAGE_URL: &str = "https://image.tmdb.org/t/p/w200";
+const POSTER_DIRECTORY: &str = "tv";
+
+// Bump when fetching more metadata, cached shows from older versions are refetched
+const METADATA_VERSION: u32 = 1;
 
 fn make_search_url(title: &str) -> Url {
     let title = decode(title.as_bytes()).to_string().unwrap();
@@ -31,10 +38,47 @@ fn make_search_url(title: &str) -> Url {
     .unwrap()
 }
 
+fn make_details_url(id: u32) -> Url {
+    format!(
+        "https://api.themoviedb.org/3/tv/{}?api_key={}",
+        id, CONFIG.tmdb.key
+    )
+    .parse()
+    .unwrap()
+}
+
+fn make_find_by_imdb_url(imdb_id: &str) -> Url {
+    format!(
+        "https://api.themoviedb.org/3/find/{}?api_key={}&external_source=imdb_id",
+        imdb_id, CONFIG.tmdb.key
+    )
+    .parse()
+    .unwrap()
+}
+
+fn cached_tv_show_matches_override(
+    tv_show: &Option<TvShow>,
+    tv_show_override: Option<&TvShowOverride>,
+) -> bool {
+    let Some(tv_show_override) = tv_show_override else {
+        return true;
+    };
+
+    let Some(tv_show) = tv_show else {
+        return false;
+    };
+
+    tv_show_override
+        .tmdb
+        .is_none_or(|id| tv_show.id == TvShowId::tmdb(id))
+        && tv_show_override.cover.as_ref().is_none_or(|cover| {
+            tv_show.poster.original.file == override_cover_cdn_file(POSTER_DIRECTORY, cover)
+        })
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct TmdbSearchResponseSingle {
     id: u32,
-    name: String,
     poster_path: Option<String>,
 }
 
@@ -43,6 +87,44 @@ struct TmdbSearchResponse {
     results: Vec<TmdbSearchResponseSingle>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbFindResponse {
+    tv_results: Vec<TmdbSearchResponseSingle>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbNamed {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TmdbTvShowDetails {
+    id: u32,
+    name: String,
+    poster_path: Option<String>,
+    #[serde(default)]
+    first_air_date: Option<String>,
+    number_of_seasons: Option<u16>,
+    #[serde(default)]
+    genres: Vec<TmdbNamed>,
+    #[serde(default)]
+    created_by: Vec<TmdbNamed>,
+}
+
+fn apply_details(tv_show: &mut TvShow, details: &TmdbTvShowDetails) {
+    let names = |named: &[TmdbNamed]| named.iter().map(|n| n.name.clone()).collect();
+
+    tv_show.genres = names(&details.genres);
+    tv_show.creators = names(&details.created_by);
+    tv_show.season_count = details.number_of_seasons;
+    tv_show.year = details
+        .first_air_date
+        .as_ref()
+        .and_then(|date| date.get(..4))
+        .and_then(|year| year.parse().ok());
+    tv_show.metadata_version = METADATA_VERSION;
+}
+
 #[derive(Debug)]
 pub struct TvShowService {
     file: ArchiveFile,
@@ -61,57 +143,128 @@ impl TvShowService {
         })
     }
 
+    fn poster_image(
+        ctx: &ServiceContext,
+        tv_show: &TmdbTvShowDetails,
+        tv_show_override: Option<&TvShowOverride>,
+    ) -> Result<Image> {
+        let alt = format!("{} movie poster", tv_show.name);
+        let link = format!("{}{}", TMDB_LINK_URL, tv_show.id);
+
+        if let Some(cover) = tv_show_override.and_then(|o| o.cover.as_ref()) {
+            return MediaService::image_from_file(
+                ctx,
+                &FileService::content(cover.into()),
+                &override_cover_cdn_file(POSTER_DIRECTORY, cover),
+                &alt,
+                Some(&link),
+                None,
+            );
+        }
+
+        let poster = tv_show
+            .poster_path
+            .as_ref()
+            .ok_or(TvShowsError::tv_show_has_no_poster(tv_show.id))?;
+
+        let image_url = &format!("{}{}", TMDB_IMAGE_URL, poster).parse().unwrap();
+
+        let cdn_file = CdnFile::from_path(&format!(
+            "{}/{}-poster-400.jpg",
+            POSTER_DIRECTORY, tv_show.id
+        ));
+
+        MediaService::image_from_url(ctx, image_url, &cdn_file, &alt, Some(&link), None)
+    }
+
     #[instrument(err, skip_all, fields(tv_show.title=%title))]
-    pub fn find_tv_show(&self, ctx: &ServiceContext, title: &str) -> Result<Option<TvShow>> {
-        if let Some(tv_show) = self.tv_shows.get(title) {
-            match tv_show.value() {
-                Some(movie) => return Ok(Some(movie.clone())),
-                None => {
-                    warn!("Did not find cover for tv show [{title}]");
-                    return Ok(None);
-                }
+    pub fn find_tv_show(
+        &self,
+        ctx: &ServiceContext,
+        title: &str,
+        ids: &ReviewIds,
+    ) -> Result<Option<TvShow>> {
+        let tv_show_override = ctx.review_overrides.tv_show(title);
+
+        let key = match (tv_show_override, ids.tmdb_cache_key()) {
+            (None, Some(key)) => key,
+            _ => title.to_string(),
+        };
+
+        let cached = self.tv_shows.get(&key).map(|tv_show| tv_show.clone());
+
+        let cached = match cached {
+            Some(tv_show) => Some(tv_show),
+            None => self.move_from_title_key(ctx, &key, title, ids)?.map(Some),
+        }
+        .filter(|tv_show| cached_tv_show_matches_override(tv_show, tv_show_override));
+
+        match cached {
+            Some(Some(tv_show)) if tv_show.metadata_version < METADATA_VERSION => {
+                // The cached show is still usable without the new metadata
+                return match self.refresh_details(ctx, key, tv_show.clone()) {
+                    Ok(tv_show) => Ok(Some(tv_show)),
+                    Err(e) => {
+                        warn!("Unable to refresh details for tv show [{title}]: {e}");
+                        Ok(Some(tv_show))
+                    }
+                };
+            }
+            Some(Some(tv_show)) => return Ok(Some(tv_show)),
+            Some(None) => {
+                warn!("Did not find cover for tv show [{title}]");
+                return Ok(None);
             }
+            None => {}
         }
 
-        let results = ctx
-            .network
-            .download_json::<TmdbSearchResponse>(&make_search_url(title))?;
-
-        let results = results
-            .results
-            .iter()
-            .filter(|r| r.poster_path.is_some())
-            .collect::<Vec<_>>();
-
-        match results.first() {
-            Some(tv_show) => {
-                let poster = tv_show
-                    .poster_path
-                    .as_ref()
-                    .ok_or(TvShowsError::tv_show_has_no_poster(tv_show.id))?;
-
-                let image_url = &format!("{}{}", TMDB_IMAGE_URL, poster).parse().unwrap();
-
-                let cdn_file = CdnFile::from_path(&format!("tv/{}-poster-400.jpg", tv_show.id));
-
-                let image = MediaService::image_from_url(
-                    ctx,
-                    image_url,
-                    &cdn_file,
-                    &format!("{} movie poster", tv_show.name),
-                    Some(&format!("https://www.themoviedb.org/tv/{}", tv_show.id)),
-                    None,
-                )?;
-
-                let tv_show = TvShow {
-                    title: tv_show.name.clone(),
+        // Search results need a poster of their own unless the override has one
+        let has_cover_override = tv_show_override.is_some_and(|o| o.cover.is_some());
+
+        let tmdb_id = match (
+            tv_show_override.and_then(|o| o.tmdb).or(ids.tmdb),
+            &ids.imdb,
+        ) {
+            (Some(id), _) => Some(id),
+            (None, Some(imdb_id)) => ctx
+                .network
+                .download_json::<TmdbFindResponse>(&make_find_by_imdb_url(imdb_id))?
+                .tv_results
+                .iter()
+                .find(|r| has_cover_override || r.poster_path.is_some())
+                .map(|tv_show| tv_show.id),
+            (None, None) => ctx
+                .network
+                .download_json::<TmdbSearchResponse>(&make_search_url(title))?
+                .results
+                .iter()
+                .find(|r| has_cover_override || r.poster_path.is_some())
+                .map(|tv_show| tv_show.id),
+        };
+
+        match tmdb_id {
+            Some(tmdb_id) => {
+                let details = ctx
+                    .network
+                    .download_json::<TmdbTvShowDetails>(&make_details_url(tmdb_id))?;
+
+                let image = Self::poster_image(ctx, &details, tv_show_override)?;
+
+                let mut tv_show = TvShow {
+                    title: details.name.clone(),
                     poster: image,
-                    id: TvShowId::tmdb(tv_show.id),
-                    link: format!("{}{}", TMDB_LINK_URL, tv_show.id).parse().unwrap(),
+                    id: TvShowId::tmdb(details.id),
+                    link: format!("{}{}", TMDB_LINK_URL, details.id).parse().unwrap(),
+                    genres: vec![],
+                    creators: vec![],
+                    season_count: None,
+                    year: None,
+                    metadata_version: 0,
                 };
 
-                self.tv_shows
-                    .insert(tv_show.title.clone(), Some(tv_show.clone()));
+                apply_details(&mut tv_show, &details);
+
+                self.tv_shows.insert(key, Some(tv_show.clone()));
 
                 self.file.write_json(&self.tv_shows.clone())?;
 
@@ -119,7 +272,7 @@ impl TvShowService {
             }
             None => {
                 warn!("Did not find cover for tv show [{title}]");
-                self.tv_shows.insert(title.to_string(), None);
+                self.tv_shows.insert(key, None);
 
                 self.file.write_json(&self.tv_shows.clone())?;
 
@@ -127,4 +280,83 @@ impl TvShowService {
             }
         }
     }
+
+    // Shows cached by title before ids were read from reviews keep their entry under the id key
+    fn move_from_title_key(
+        &self,
+        ctx: &ServiceContext,
+        key: &str,
+        title_key: &str,
+        ids: &ReviewIds,
+    ) -> Result<Option<TvShow>> {
+        if key == title_key {
+            return Ok(None);
+        }
+
+        let Some(tv_show) = self
+            .tv_shows
+            .get(title_key)
+            .and_then(|tv_show| tv_show.clone())
+        else {
+            return Ok(None);
+        };
+
+        // A show found by title could be a different one with the same name
+        let tmdb_id = match (ids.tmdb, &ids.imdb) {
+            (Some(id), _) => Some(id),
+            (None, Some(imdb_id)) => ctx
+                .network
+                .download_json::<TmdbFindResponse>(&make_find_by_imdb_url(imdb_id))?
+                .tv_results
+                .first()
+                .map(|tv_show| tv_show.id),
+            (None, None) => None,
+        };
+
+        if tmdb_id.is_none_or(|id| tv_show.id != TvShowId::tmdb(id)) {
+            return Ok(None);
+        }
+
+        self.tv_shows.insert(key.to_string(), Some(tv_show.clone()));
+
+        self.file.write_json(&self.tv_shows.clone())?;
+
+        Ok(Some(tv_show))
+    }
+
+    fn refresh_details(
+        &self,
+        ctx: &ServiceContext,
+        key: String,
+        mut tv_show: TvShow,
+    ) -> Result<TvShow> {
+        let TvShowId::Tmdb { id } = tv_show.id;
+
+        let details = ctx
+            .network
+            .download_json::<TmdbTvShowDetails>(&make_details_url(id))?;
+
+        apply_details(&mut tv_show, &details);
+
+        self.tv_shows.insert(key, Some(tv_show.clone()));
+
+        self.file.write_json(&self.tv_shows.clone())?;
+
+        Ok(tv_show)
+    }
+
+    pub fn evict(&self, titles: &[String]) -> Result<usize> {
+        let count = self.tv_shows.len();
+
+        self.tv_shows.retain(|key, tv_show| {
+            tv_show.is_some()
+                && !titles
+                    .iter()
+                    .any(|title| key.eq_ignore_ascii_case(title.trim()))
+        });
+
+        self.file.write_json(&self.tv_shows.clone())?;
+
+        Ok(count - self.tv_shows.len())
+    }
 }
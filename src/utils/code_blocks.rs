@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+const CODE_FENCES: [&str; 2] = ["```", "~~~"];
+
+// Rewrites Markdown line by line, leaving fenced code blocks alone so syntax can be written about
+pub fn map_lines_outside_code_blocks<F>(content: &str, mut f: F) -> Result<String>
+where
+    F: FnMut(&str) -> Result<String>,
+{
+    let mut in_code_block = false;
+
+    let lines = content
+        .split('\n')
+        .map(|line| {
+            if CODE_FENCES
+                .iter()
+                .any(|fence| line.trim_start().starts_with(fence))
+            {
+                in_code_block = !in_code_block;
+                return Ok(line.to_string());
+            }
+
+            match in_code_block {
+                true => Ok(line.to_string()),
+                false => f(line),
+            }
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(lines.join("\n"))
+}
@@ -1,5 +1,7 @@
+pub mod code_blocks;
 pub mod date;
 pub mod paginator;
+pub mod read_time;
 // pub mod parse_omni_post_content_into_movie_review;
 // pub mod parse_omni_post_into_tv_show_reviews;
 pub mod cover_photos_for_album;
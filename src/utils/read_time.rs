@@ -0,0 +1,55 @@
+use comrak::{Arena, Options, nodes::NodeValue};
+
+const WORDS_PER_MINUTE: usize = 238;
+
+// Counts words in the text of the document, so urls, alt text, markup and code blocks don't inflate it
+pub fn count_words(markdown: &str) -> usize {
+    let arena = Arena::new();
+
+    let root = comrak::parse_document(&arena, markdown, &Options::default());
+
+    root.descendants()
+        .filter(|node| {
+            !node
+                .ancestors()
+                .any(|ancestor| matches!(ancestor.data.borrow().value, NodeValue::Image(_)))
+        })
+        .map(|node| match &node.data.borrow().value {
+            NodeValue::Text(text) => text.split_whitespace().count(),
+            NodeValue::Code(code) => code.literal.split_whitespace().count(),
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn read_time_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_count_words_in_text_only() {
+        let markdown = r#"## Saving my Knight
+
+I [drilled](https://example.com/drilling) out the *broken* pin.
+
+![A broken knight](https://cdn.example.com/knight.jpg)
+
+```rust
+fn main() {}
+```
+"#;
+
+        assert_eq!(count_words(markdown), 9);
+    }
+
+    #[test]
+    fn it_should_round_read_time_up_to_at_least_a_minute() {
+        assert_eq!(read_time_minutes(0), 1);
+        assert_eq!(read_time_minutes(238), 1);
+        assert_eq!(read_time_minutes(239), 2);
+    }
+}